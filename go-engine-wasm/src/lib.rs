@@ -1,12 +1,14 @@
 use std::collections::{HashMap, HashSet};
 
 use go_engine::game_tree::NodeId;
-use go_engine::sgf::convert::MoveTime;
+use go_engine::sgf::convert::{MoveTime, SgfConversion};
+use go_engine::tactics::{self, ReadingLimits};
 use go_engine::{GameTree, Point, Replay, Stone};
 use wasm_bindgen::prelude::*;
 
 mod policy_mcts;
 mod random_mcts;
+mod search_time;
 
 /// Parse SGF text and return metadata as JSON.
 /// Returns JSON: `{ cols, rows, komi?, handicap?, black_name?, ... }`
@@ -24,6 +26,26 @@ pub fn parse_sgf(sgf_text: &str) -> String {
     serde_json::to_string(&conv.metadata).unwrap_or_else(|e| format!(r#"{{"error":"{}"}}"#, e))
 }
 
+/// List the games in an SGF collection.
+/// Returns JSON: `[{ index, cols, rows, black_name?, date?, result?, move_count, ... }]`
+/// On parse error: `{ "error": "message" }`
+#[wasm_bindgen]
+pub fn list_sgf_games(sgf_text: &str) -> String {
+    let collection = match go_engine::sgf::parse(sgf_text) {
+        Ok(c) => c,
+        Err(e) => return format!(r#"{{"error":"{}"}}"#, e),
+    };
+    let games = go_engine::sgf::convert::summarize_collection(&collection);
+    serde_json::to_string(&games).unwrap_or_else(|e| format!(r#"{{"error":"{}"}}"#, e))
+}
+
+/// Parse SGF text and convert the game at `index` of its collection.
+fn convert_sgf_game(sgf_text: &str, index: usize) -> Option<SgfConversion> {
+    let collection = go_engine::sgf::parse(sgf_text).ok()?;
+    let game = collection.get(index)?;
+    Some(go_engine::sgf::convert::sgf_to_game_tree(game))
+}
+
 #[wasm_bindgen]
 pub struct WasmEngine {
     inner: Replay,
@@ -44,6 +66,54 @@ impl WasmEngine {
         self.inner.set_handicap(handicap);
     }
 
+    /// Switch to a preset ruleset by name (`"japanese"`, `"chinese"`, `"aga"`,
+    /// `"new_zealand"`). Returns false for an unknown name.
+    pub fn set_ruleset(&mut self, name: &str) -> bool {
+        match name.parse() {
+            Ok(ruleset) => {
+                self.inner.set_ruleset(ruleset);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Switch between `"fixed"` and `"free"` handicap placement.
+    /// Returns false for an unknown mode.
+    pub fn set_handicap_placement(&mut self, name: &str) -> bool {
+        match name.parse() {
+            Ok(placement) => {
+                self.inner.set_handicap_placement(placement);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Switch between the `"planar"` and `"torus"` board topologies.
+    /// Returns false for an unknown topology.
+    pub fn set_topology(&mut self, name: &str) -> bool {
+        match name.parse() {
+            Ok(topology) => {
+                self.inner.set_topology(topology);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Play Capture Go: the first player to capture `target` stones wins.
+    /// Zero switches back to regular Go.
+    pub fn set_capture_target(&mut self, target: u32) {
+        self.inner
+            .set_capture_target((target > 0).then_some(target));
+    }
+
+    /// Number of free handicap stones Black still has to place.
+    pub fn pending_handicap_stones(&self) -> u8 {
+        self.inner.engine().pending_handicap_stones()
+    }
+
     // -- Game actions (delegate to Replay) --
 
     pub fn try_play(&mut self, col: u8, row: u8) -> bool {
@@ -179,14 +249,15 @@ impl WasmEngine {
     /// Load an SGF tree into the current engine, replacing the existing tree.
     /// Returns true on success, false on parse error.
     pub fn load_sgf_tree(&mut self, sgf_text: &str) -> bool {
-        let collection = match go_engine::sgf::parse(sgf_text) {
-            Ok(c) => c,
-            Err(_) => return false,
-        };
-        let Some(first) = collection.first() else {
+        self.load_sgf_game(sgf_text, 0)
+    }
+
+    /// Load the game at `index` of an SGF collection, replacing the existing tree.
+    /// Returns false on parse error or when there is no such game.
+    pub fn load_sgf_game(&mut self, sgf_text: &str, index: usize) -> bool {
+        let Some(conv) = convert_sgf_game(sgf_text, index) else {
             return false;
         };
-        let conv = go_engine::sgf::convert::sgf_to_game_tree(first);
         self.move_times = conv.move_times;
         self.inner.replace_tree(conv.tree);
         true
@@ -195,14 +266,14 @@ impl WasmEngine {
     /// Parse an SGF and load only the move_times map, without touching the tree.
     /// Use this to restore move_times after the tree has been loaded from localStorage.
     pub fn load_sgf_move_times(&mut self, sgf_text: &str) -> bool {
-        let collection = match go_engine::sgf::parse(sgf_text) {
-            Ok(c) => c,
-            Err(_) => return false,
-        };
-        let Some(first) = collection.first() else {
+        self.load_sgf_game_move_times(sgf_text, 0)
+    }
+
+    /// Like `load_sgf_move_times`, for the game at `index` of the collection.
+    pub fn load_sgf_game_move_times(&mut self, sgf_text: &str, index: usize) -> bool {
+        let Some(conv) = convert_sgf_game(sgf_text, index) else {
             return false;
         };
-        let conv = go_engine::sgf::convert::sgf_to_game_tree(first);
         self.move_times = conv.move_times;
         true
     }
@@ -218,15 +289,34 @@ impl WasmEngine {
         }
     }
 
+    /// Comment, markup and glyphs at the current node as JSON (`{}` when none).
+    pub fn current_annotation(&self) -> String {
+        serde_json::to_string(self.inner.annotation()).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    /// Replace the current node's annotation from JSON. Returns false on parse error.
+    pub fn set_current_annotation(&mut self, json: &str) -> bool {
+        match serde_json::from_str(json) {
+            Ok(annotation) => {
+                self.inner.set_annotation(annotation);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
     /// Export the current tree as an SGF string.
     /// `meta_json` should be a JSON string with SgfMetadata fields.
     pub fn export_sgf(&self, meta_json: &str) -> String {
-        let meta: go_engine::sgf::convert::SgfMetadata = serde_json::from_str(meta_json)
+        let mut meta: go_engine::sgf::convert::SgfMetadata = serde_json::from_str(meta_json)
             .unwrap_or_else(|_| go_engine::sgf::convert::SgfMetadata {
                 cols: self.inner.cols(),
                 rows: self.inner.rows(),
                 ..Default::default()
             });
+        if meta.handicap_stones.is_empty() {
+            meta.handicap_stones = self.inner.free_handicap_stones();
+        }
         go_engine::sgf::convert::game_tree_to_sgf(self.inner.tree(), &meta)
     }
 
@@ -306,6 +396,28 @@ impl WasmEngine {
             .unwrap_or(-1)
     }
 
+    // -- Tactics --
+
+    /// Returns a JSON reading of the chain at (col, row) for UI hints:
+    /// {"ladder":[col,row]|null,"net":[col,row]|null,"escapes":bool}, where
+    /// "ladder" and "net" are the opponent's capturing moves and "escapes"
+    /// says whether the chain survives with its owner moving first.
+    /// Returns "null" on an empty point.
+    pub fn tactical_status(&self, col: u8, row: u8) -> String {
+        let goban = self.inner.engine().goban();
+        let point = (col, row);
+        if !goban.on_board(point) || goban.stone_at(point).is_none() {
+            return "null".into();
+        }
+        let limits = ReadingLimits::default();
+        let status = serde_json::json!({
+            "ladder": tactics::ladder_capture(goban, point, limits).map(|(c, r)| [c, r]),
+            "net": tactics::net_capture(goban, point, limits).map(|(c, r)| [c, r]),
+            "escapes": tactics::can_escape(goban, point, limits),
+        });
+        status.to_string()
+    }
+
     // -- Territory review --
 
     pub fn stage(&self) -> String {
@@ -336,16 +448,31 @@ impl WasmEngine {
     }
 
     /// Returns JSON score object:
-    /// {"black":{"territory":n,"captures":n},"white":{"territory":n,"captures":n},"result":"B+3.5"}
+    /// {"black":{"territory":n,"captures":n,"stones":n},"white":{...},"handicap_bonus":n,"result":"B+3.5"}
     pub fn score(&self, dead_stones_json: &str, komi: f64) -> String {
         let dead = parse_dead_stones(dead_stones_json);
-        let goban = self.inner.engine().goban();
+        let engine = self.inner.engine();
+        let goban = engine.goban();
         let ownership = go_engine::territory::estimate_territory(goban, &dead);
-        let gs = go_engine::territory::score(goban, &ownership, &dead, komi);
+        let gs = go_engine::territory::score(
+            goban,
+            &ownership,
+            &dead,
+            komi,
+            engine.handicap(),
+            engine.ruleset(),
+        );
         let result = gs.result();
         format!(
-            r#"{{"black":{{"territory":{},"captures":{}}},"white":{{"territory":{},"captures":{}}},"result":"{}"}}"#,
-            gs.black.territory, gs.black.captures, gs.white.territory, gs.white.captures, result,
+            r#"{{"black":{{"territory":{},"captures":{},"stones":{}}},"white":{{"territory":{},"captures":{},"stones":{}}},"handicap_bonus":{},"result":"{}"}}"#,
+            gs.black.territory,
+            gs.black.captures,
+            gs.black.stones,
+            gs.white.territory,
+            gs.white.captures,
+            gs.white.stones,
+            gs.handicap_bonus,
+            result,
         )
     }
 
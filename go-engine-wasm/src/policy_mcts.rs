@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use go_engine::goban::Captures;
 use go_engine::mcts::{
     self, BotMove, DeadStoneEvalConfig, ExternalEvaluation, ExternalMctsConfig, ExternalMctsSearch,
@@ -7,6 +9,8 @@ use go_engine::{Engine, GameState, Ko, Stone, Turn};
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
+use crate::search_time::{self, WasmGameClock};
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct PolicyMctsRequest {
@@ -16,6 +20,9 @@ struct PolicyMctsRequest {
     max_policy_actions: Option<usize>,
     komi: Option<f64>,
     dead_stone_eval: Option<PolicyMctsDeadStoneEval>,
+    time_ms: Option<u32>,
+    clock: Option<WasmGameClock>,
+    early_stop: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -37,6 +44,10 @@ struct PolicyMctsEvaluation {
     id: u32,
     policy_logits: Vec<f32>,
     value: f32,
+    /// Per point in board order, 1 for Black to -1 for White.
+    ownership: Option<Vec<f32>>,
+    /// Points ahead for the side to play.
+    score_lead: Option<f32>,
 }
 
 #[derive(Debug, Serialize)]
@@ -69,6 +80,10 @@ struct PolicyMctsSummaryResponse {
     root_edges: Vec<WasmMctsEdge>,
     principal_variation: Vec<WasmBotMove>,
     diagnostics: WasmMctsDiagnostics,
+    elapsed_ms: f64,
+    stop_reason: Option<&'static str>,
+    ownership: Option<Vec<f32>>,
+    score_lead: Option<f32>,
 }
 
 #[derive(Debug, Serialize)]
@@ -165,7 +180,7 @@ struct PolicyRankResponse {
     moves: Vec<WasmMctsEdge>,
 }
 
-#[derive(Debug, Clone, Copy, Serialize)]
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
 #[serde(tag = "kind", rename_all = "camelCase")]
 enum WasmBotMove {
     Play { col: u8, row: u8 },
@@ -216,6 +231,8 @@ impl WasmPolicyMcts {
                     id: evaluation.id,
                     policy_logits: evaluation.policy_logits,
                     value: evaluation.value,
+                    ownership: evaluation.ownership,
+                    score_lead: evaluation.score_lead,
                 })
                 .collect(),
             Err(err) => {
@@ -237,6 +254,43 @@ impl WasmPolicyMcts {
         )
     }
 
+    /// Move the search root past a played move, ours or the opponent's, as
+    /// `{"kind":"play","col":..,"row":..}` or `{"kind":"pass"}`. The visits
+    /// below it are kept and the next batches spend a fresh visit budget.
+    pub fn advance_json(&mut self, move_json: &str) -> String {
+        let Some(search) = self.search.as_mut() else {
+            return status_json(self.error.clone(), 0, 0, true);
+        };
+        let result = serde_json::from_str::<WasmBotMove>(move_json)
+            .map_err(|err| format!("invalid policy MCTS move: {err}"))
+            .and_then(|action| {
+                search
+                    .advance(BotMove::from(action))
+                    .map_err(|err| format!("illegal policy MCTS move: {err}"))
+            });
+
+        status_json(
+            result.err(),
+            search.completed_visits(),
+            search.pending_count(),
+            search.is_complete(),
+        )
+    }
+
+    /// Report how long the search has run, counting the time spent
+    /// evaluating batches. A `timeMs` or `clock` budget stops the search
+    /// once this passes the move time.
+    pub fn set_elapsed_ms(&mut self, elapsed_ms: f64) {
+        if let Some(search) = self.search.as_mut() {
+            let seconds = if elapsed_ms.is_finite() {
+                elapsed_ms.max(0.0) / 1000.0
+            } else {
+                0.0
+            };
+            search.set_elapsed(Duration::from_secs_f64(seconds));
+        }
+    }
+
     pub fn summary_json(&self) -> String {
         let Some(search) = self.search.as_ref() else {
             return serde_json::to_string(&PolicyMctsSummaryResponse {
@@ -262,6 +316,10 @@ impl WasmPolicyMcts {
                     visited_root_moves: 0,
                     visited_root_policy_mass: 0.0,
                 },
+                elapsed_ms: 0.0,
+                stop_reason: None,
+                ownership: None,
+                score_lead: None,
             })
             .unwrap_or_else(|err| error_json(&err.to_string()));
         };
@@ -307,6 +365,10 @@ impl WasmPolicyMcts {
                 visited_root_moves: summary.diagnostics.visited_root_moves,
                 visited_root_policy_mass: summary.diagnostics.visited_root_policy_mass,
             },
+            elapsed_ms: summary.elapsed.as_secs_f64() * 1000.0,
+            stop_reason: summary.stop_reason.map(search_time::stop_reason_name),
+            ownership: summary.ownership,
+            score_lead: summary.score_lead,
         })
         .unwrap_or_else(|err| error_json(&err.to_string()))
     }
@@ -354,6 +416,8 @@ pub fn create(engine: &Engine, request_json: &str) -> WasmPolicyMcts {
                     visits,
                     cpuct,
                     fpu_reduction,
+                    time: search_time::time_budget(request.time_ms, request.clock),
+                    early_stop: request.early_stop.unwrap_or(false),
                 },
                 max_policy_actions,
                 dead_stone_eval,
@@ -433,6 +497,15 @@ impl From<BotMove> for WasmBotMove {
     }
 }
 
+impl From<WasmBotMove> for BotMove {
+    fn from(value: WasmBotMove) -> Self {
+        match value {
+            WasmBotMove::Play { col, row } => Self::Play((col, row)),
+            WasmBotMove::Pass => Self::Pass,
+        }
+    }
+}
+
 fn format_bot_move(action: BotMove, board_rows: u8) -> String {
     match action {
         BotMove::Play((col, row)) => format!("{}{}", gtp_column(col), board_rows - row),
@@ -625,6 +698,28 @@ mod tests {
         assert_eq!(response["requests"][0]["position"]["komi"], 0.5);
     }
 
+    #[test]
+    fn move_time_ends_the_search() {
+        let engine = Engine::new(3, 3);
+        let mut search = create(&engine, r#"{"visits":50,"timeMs":100}"#);
+
+        let json = search.next_batch_json(4);
+        let batch: Value = serde_json::from_str(&json).expect("valid batch json");
+        let id = batch["requests"][0]["id"].as_u64().expect("request id");
+        search.set_elapsed_ms(150.0);
+        let json = search.apply_batch_json(&format!(
+            r#"{{"evaluations":[{{"id":{id},"policyLogits":[0,0,0,0,0,0,0,0,0,0],"value":0}}]}}"#
+        ));
+        let status: Value = serde_json::from_str(&json).expect("valid status json");
+        assert_eq!(status["complete"], true);
+        assert_eq!(status["completedVisits"], 1);
+
+        let summary: Value =
+            serde_json::from_str(&search.summary_json()).expect("valid summary json");
+        assert_eq!(summary["stopReason"], "time");
+        assert_eq!(summary["elapsedMs"], 150.0);
+    }
+
     #[test]
     fn position_json_root_preserves_stones_without_move_history() {
         let position = r#"{
@@ -683,6 +778,34 @@ mod tests {
         assert_ne!(response["moves"][0]["move"], "B2");
     }
 
+    #[test]
+    fn advance_keeps_visits_below_the_played_move() {
+        let engine = Engine::new(3, 3);
+        let mut search = create(&engine, r#"{"visits":2,"maxPolicyActions":1,"komi":0.5}"#);
+        for _ in 0..2 {
+            let batch: Value =
+                serde_json::from_str(&search.next_batch_json(1)).expect("valid batch json");
+            let id = batch["requests"][0]["id"].as_u64().expect("request id");
+            search.apply_batch_json(&format!(
+                r#"{{"evaluations":[{{"id":{id},"policyLogits":[0,0,0,0,0,0,0,0,8,0],"value":0.0}}]}}"#
+            ));
+        }
+
+        let status: Value =
+            serde_json::from_str(&search.advance_json(r#"{"kind":"play","col":2,"row":2}"#))
+                .expect("valid status json");
+        assert!(status["error"].is_null());
+        assert_eq!(status["complete"], false);
+        let summary: Value =
+            serde_json::from_str(&search.summary_json()).expect("valid summary json");
+        assert_eq!(summary["visits"], 1);
+
+        let status: Value =
+            serde_json::from_str(&search.advance_json(r#"{"kind":"play","col":2,"row":2}"#))
+                .expect("valid status json");
+        assert!(status["error"].is_string());
+    }
+
     #[test]
     fn applies_eval_batch_and_returns_summary() {
         let engine = Engine::new(3, 3);
@@ -716,5 +839,28 @@ mod tests {
         assert_eq!(summary["diagnostics"]["rootVisitEntropy"], 0.0);
         assert_eq!(summary["diagnostics"]["visitedRootMoves"], 0);
         assert_eq!(summary["diagnostics"]["visitedRootPolicyMass"], 0.0);
+        assert!(summary["ownership"].is_null());
+        assert!(summary["scoreLead"].is_null());
+    }
+
+    #[test]
+    fn summary_averages_ownership_and_score_lead() {
+        let engine = Engine::new(3, 3);
+        let mut search = create(&engine, r#"{"visits":1}"#);
+        let batch: Value =
+            serde_json::from_str(&search.next_batch_json(1)).expect("valid batch json");
+        let id = batch["requests"][0]["id"].as_u64().expect("request id");
+
+        search.apply_batch_json(&format!(
+            r#"{{"evaluations":[{{"id":{id},"policyLogits":[0,0,0,0,0,0,0,0,0,0],"value":0,"ownership":[1,1,1,0,0,0,-1,-1,-0.5],"scoreLead":1.5}}]}}"#
+        ));
+        let summary: Value =
+            serde_json::from_str(&search.summary_json()).expect("valid summary json");
+
+        assert_eq!(
+            summary["ownership"],
+            serde_json::json!([1.0, 1.0, 1.0, 0.0, 0.0, 0.0, -1.0, -1.0, -0.5])
+        );
+        assert_eq!(summary["scoreLead"], 1.5);
     }
 }
@@ -4,6 +4,8 @@ use go_engine::mcts::{
 };
 use serde::{Deserialize, Serialize};
 
+use crate::search_time::{self, WasmGameClock};
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct RandomMctsRequest {
@@ -16,6 +18,9 @@ struct RandomMctsRequest {
     max_policy_actions: Option<usize>,
     root_policy_logits: Option<Vec<f32>>,
     root_value: Option<f32>,
+    time_ms: Option<u32>,
+    clock: Option<WasmGameClock>,
+    early_stop: Option<bool>,
 }
 
 #[derive(Debug, Serialize)]
@@ -31,6 +36,12 @@ struct RandomMctsResponse {
     root_edges: Vec<WasmMctsEdge>,
     principal_variation: Vec<WasmBotMove>,
     diagnostics: WasmMctsDiagnostics,
+    elapsed_ms: f64,
+    stop_reason: Option<&'static str>,
+    /// Per point in board order, 1 for Black to -1 for White.
+    ownership: Option<Vec<f32>>,
+    /// Points ahead for the side to play.
+    score_lead: Option<f32>,
 }
 
 #[derive(Debug, Serialize)]
@@ -91,7 +102,10 @@ pub fn run(engine: &Engine, request_json: &str) -> String {
         visits,
         cpuct,
         fpu_reduction,
+        time: search_time::time_budget(request.time_ms, request.clock),
+        early_stop: request.early_stop.unwrap_or(false),
     };
+    let clock = search_time::start_clock();
 
     let (summary, policy_source, value_source) =
         if let Some(root_policy_logits) = request.root_policy_logits.as_deref() {
@@ -109,7 +123,7 @@ pub fn run(engine: &Engine, request_json: &str) -> String {
             );
 
             (
-                mcts::search(engine, search_config, &mut evaluator),
+                mcts::search_with_clock(engine, search_config, &mut evaluator, &clock),
                 "external-root",
                 "external-root-and-rollout",
             )
@@ -117,7 +131,7 @@ pub fn run(engine: &Engine, request_json: &str) -> String {
             let mut evaluator = RandomRolloutEvaluator::new(rollout_config, komi);
 
             (
-                mcts::search(engine, search_config, &mut evaluator),
+                mcts::search_with_clock(engine, search_config, &mut evaluator, &clock),
                 "baseline-rollout",
                 "rollout",
             )
@@ -158,6 +172,10 @@ pub fn run(engine: &Engine, request_json: &str) -> String {
             visited_root_moves: summary.diagnostics.visited_root_moves,
             visited_root_policy_mass: summary.diagnostics.visited_root_policy_mass,
         },
+        elapsed_ms: summary.elapsed.as_secs_f64() * 1000.0,
+        stop_reason: summary.stop_reason.map(search_time::stop_reason_name),
+        ownership: summary.ownership,
+        score_lead: summary.score_lead,
     };
 
     serde_json::to_string(&response).unwrap_or_else(|err| error_json(&err.to_string()))
@@ -223,15 +241,47 @@ mod tests {
                 .expect("root entropy")
                 >= 0.0
         );
+        assert_eq!(
+            response["ownership"].as_array().expect("ownership").len(),
+            9
+        );
+        assert!(response["scoreLead"].is_number());
         assert_eq!(engine.board(), before.as_slice());
     }
 
+    #[test]
+    fn stops_early_and_reports_why() {
+        let engine = Engine::new(3, 3);
+
+        let json = run(
+            &engine,
+            r#"{"visits":400,"rolloutLimit":4,"seed":99,"maxPolicyActions":1,"earlyStop":true,"clock":{"kind":"byoYomi","mainMs":0,"periodMs":30000,"periods":1}}"#,
+        );
+        let response: Value = serde_json::from_str(&json).expect("valid response json");
+
+        assert_eq!(response["stopReason"], "decided");
+        assert!(response["visits"].as_u64().expect("visits") < 400);
+        assert!(response["elapsedMs"].as_f64().expect("elapsed") >= 0.0);
+
+        let json = run(&engine, r#"{"visits":8,"rolloutLimit":4,"timeMs":60000}"#);
+        let response: Value = serde_json::from_str(&json).expect("valid response json");
+        assert_eq!(response["stopReason"], "visits");
+    }
+
     #[test]
     fn deterministic_for_same_seed() {
         let engine = Engine::new(3, 3);
         let request = r#"{"visits":8,"rolloutLimit":4,"seed":123,"komi":0.5}"#;
 
-        assert_eq!(run(&engine, request), run(&engine, request));
+        // Everything but the wall-clock time.
+        let search = || {
+            let mut response: Value =
+                serde_json::from_str(&run(&engine, request)).expect("valid response json");
+            response["elapsedMs"].take();
+            response
+        };
+
+        assert_eq!(search(), search());
     }
 
     #[test]
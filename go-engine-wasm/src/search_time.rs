@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+use go_engine::mcts::{GameClock, SearchClock, StopReason, TimeBudget};
+use serde::Deserialize;
+
+/// The searching side's clock, in milliseconds.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum WasmGameClock {
+    #[serde(rename_all = "camelCase")]
+    Fischer {
+        remaining_ms: u32,
+        increment_ms: u32,
+    },
+    #[serde(rename_all = "camelCase")]
+    ByoYomi {
+        main_ms: u32,
+        period_ms: u32,
+        periods: u32,
+    },
+}
+
+/// `timeMs` wins over `clock`; with neither the search is limited by
+/// visits alone.
+pub fn time_budget(time_ms: Option<u32>, clock: Option<WasmGameClock>) -> TimeBudget {
+    let ms = |ms: u32| Duration::from_millis(u64::from(ms));
+    if let Some(time_ms) = time_ms {
+        return TimeBudget::PerMove(ms(time_ms));
+    }
+    match clock {
+        Some(WasmGameClock::Fischer {
+            remaining_ms,
+            increment_ms,
+        }) => TimeBudget::Clock(GameClock::Fischer {
+            remaining: ms(remaining_ms),
+            increment: ms(increment_ms),
+        }),
+        Some(WasmGameClock::ByoYomi {
+            main_ms,
+            period_ms,
+            periods,
+        }) => TimeBudget::Clock(GameClock::ByoYomi {
+            main: ms(main_ms),
+            period: ms(period_ms),
+            periods,
+        }),
+        None => TimeBudget::Unlimited,
+    }
+}
+
+pub fn stop_reason_name(reason: StopReason) -> &'static str {
+    match reason {
+        StopReason::Visits => "visits",
+        StopReason::Time => "time",
+        StopReason::Decided => "decided",
+    }
+}
+
+/// `Instant` panics on `wasm32-unknown-unknown`, so the browser build reads
+/// the JS clock.
+#[cfg(target_arch = "wasm32")]
+pub fn start_clock() -> impl SearchClock {
+    let start = js_sys::Date::now();
+    move || Duration::from_secs_f64(((js_sys::Date::now() - start) / 1000.0).max(0.0))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn start_clock() -> impl SearchClock {
+    go_engine::mcts::Stopwatch::start()
+}
@@ -0,0 +1,97 @@
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use go_engine::mcts::{self, BotMove, MctsConfig, RandomRolloutEvaluator, RolloutConfig};
+use go_engine::territory::detect_dead_stones;
+use go_engine::{Engine, Goban, Turn};
+
+const SIZE: u8 = 19;
+const KOMI: f64 = 6.5;
+
+/// A deterministic, reasonably dense game: uniformly random legal moves.
+fn random_game(len: usize) -> Vec<Turn> {
+    let mut engine = Engine::new(SIZE, SIZE);
+    let mut state = 0x9E37_79B9_7F4A_7C15u64;
+    while engine.moves().len() < len {
+        let stone = engine.current_turn_stone();
+        let points: Vec<_> = mcts::legal_actions(&engine, stone)
+            .into_iter()
+            .filter_map(|action| match action {
+                BotMove::Play(point) => Some(point),
+                BotMove::Pass => None,
+            })
+            .collect();
+        if points.is_empty() {
+            break;
+        }
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        let point = points[(state % points.len() as u64) as usize];
+        engine.try_play(stone, point).unwrap();
+    }
+    engine.moves().to_vec()
+}
+
+fn goban(c: &mut Criterion) {
+    let moves = random_game(200);
+    c.bench_function("goban/replay_19x19_200", |b| {
+        b.iter(|| Goban::with_moves(SIZE, SIZE, black_box(&moves)).unwrap())
+    });
+    c.bench_function("goban/clone_per_move_19x19_200", |b| {
+        b.iter(|| {
+            let mut goban = Goban::with_dimensions(SIZE, SIZE);
+            for turn in black_box(&moves) {
+                goban = goban.play(turn.pos.unwrap(), turn.stone).unwrap();
+            }
+            goban
+        })
+    });
+    c.bench_function("goban/make_unmake_19x19_200", |b| {
+        let mut goban = Goban::with_dimensions(SIZE, SIZE);
+        let mut undos = Vec::with_capacity(moves.len());
+        b.iter(|| {
+            for turn in black_box(&moves) {
+                undos.push(goban.make_move(turn.pos.unwrap(), turn.stone).unwrap());
+            }
+            while let Some(undo) = undos.pop() {
+                goban.unmake_move(undo);
+            }
+        })
+    });
+}
+
+fn search(c: &mut Criterion) {
+    let engine = Engine::with_moves(SIZE, SIZE, random_game(80));
+    let stone = engine.current_turn_stone();
+    c.bench_function("mcts/legal_actions_19x19", |b| {
+        b.iter(|| mcts::legal_actions(black_box(&engine), stone))
+    });
+
+    let config = MctsConfig {
+        visits: 16,
+        ..MctsConfig::default()
+    };
+    let mut group = c.benchmark_group("mcts");
+    group.sample_size(10);
+    group.bench_function("random_rollout_search_19x19", |b| {
+        b.iter(|| {
+            let mut evaluator = RandomRolloutEvaluator::new(RolloutConfig::default(), KOMI);
+            mcts::search(black_box(&engine), config, &mut evaluator)
+        })
+    });
+    group.finish();
+}
+
+fn territory(c: &mut Criterion) {
+    let goban = Goban::with_moves(SIZE, SIZE, &random_game(160)).unwrap();
+    let mut group = c.benchmark_group("territory");
+    group.sample_size(10);
+    group.bench_function("detect_dead_stones_19x19", |b| {
+        b.iter(|| detect_dead_stones(black_box(&goban)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, goban, search, territory);
+criterion_main!(benches);
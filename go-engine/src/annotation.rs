@@ -0,0 +1,194 @@
+use serde::{Deserialize, Serialize};
+
+use crate::Point;
+
+/// Board markup on a node, as in SGF `CR`/`TR`/`SQ`/`MA`/`SL`/`DD`/`LB`/`AR`/`LN`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Markup {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub circles: Vec<Point>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub triangles: Vec<Point>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub squares: Vec<Point>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub crosses: Vec<Point>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub selected: Vec<Point>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dimmed: Vec<Point>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<(Point, String)>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub arrows: Vec<(Point, Point)>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub lines: Vec<(Point, Point)>,
+}
+
+impl Markup {
+    pub fn is_empty(&self) -> bool {
+        self.circles.is_empty()
+            && self.triangles.is_empty()
+            && self.squares.is_empty()
+            && self.crosses.is_empty()
+            && self.selected.is_empty()
+            && self.dimmed.is_empty()
+            && self.labels.is_empty()
+            && self.arrows.is_empty()
+            && self.lines.is_empty()
+    }
+
+    fn extend(&mut self, other: Markup) {
+        self.circles.extend(other.circles);
+        self.triangles.extend(other.triangles);
+        self.squares.extend(other.squares);
+        self.crosses.extend(other.crosses);
+        self.selected.extend(other.selected);
+        self.dimmed.extend(other.dimmed);
+        self.labels.extend(other.labels);
+        self.arrows.extend(other.arrows);
+        self.lines.extend(other.lines);
+    }
+
+    fn map_points(&self, f: &impl Fn(Point) -> Point) -> Self {
+        let points = |points: &[Point]| points.iter().copied().map(f).collect();
+        let pairs = |pairs: &[(Point, Point)]| pairs.iter().map(|&(a, b)| (f(a), f(b))).collect();
+        Markup {
+            circles: points(&self.circles),
+            triangles: points(&self.triangles),
+            squares: points(&self.squares),
+            crosses: points(&self.crosses),
+            selected: points(&self.selected),
+            dimmed: points(&self.dimmed),
+            labels: self
+                .labels
+                .iter()
+                .map(|(point, label)| (f(*point), label.clone()))
+                .collect(),
+            arrows: pairs(&self.arrows),
+            lines: pairs(&self.lines),
+        }
+    }
+}
+
+/// Evaluation of a position or move, as in SGF `GB`/`GW`/`DM`/`UC`/`HO`/`BM`/`TE`/`DO`/`IT`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GlyphKind {
+    GoodForBlack,
+    GoodForWhite,
+    Even,
+    Unclear,
+    Hotspot,
+    BadMove,
+    Tesuji,
+    Doubtful,
+    Interesting,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Glyph {
+    pub kind: GlyphKind,
+    /// SGF double value `2`; always false for `DO` and `IT`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub emphasized: bool,
+}
+
+/// Commentary attached to a game tree node.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Annotation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// Estimated score (`V[]`), positive for Black.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<f64>,
+    #[serde(default, skip_serializing_if = "Markup::is_empty")]
+    pub markup: Markup,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub glyphs: Vec<Glyph>,
+}
+
+impl Annotation {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && self.comment.is_none()
+            && self.value.is_none()
+            && self.markup.is_empty()
+            && self.glyphs.is_empty()
+    }
+
+    /// Fold `other` into this annotation. Comments are joined with a blank
+    /// line; single-valued fields take the later value.
+    pub fn merge(&mut self, other: Annotation) {
+        if other.name.is_some() {
+            self.name = other.name;
+        }
+        self.comment = match (self.comment.take(), other.comment) {
+            (Some(a), Some(b)) => Some(format!("{a}\n\n{b}")),
+            (a, b) => a.or(b),
+        };
+        if other.value.is_some() {
+            self.value = other.value;
+        }
+        self.markup.extend(other.markup);
+        for glyph in other.glyphs {
+            if !self.glyphs.contains(&glyph) {
+                self.glyphs.push(glyph);
+            }
+        }
+    }
+
+    /// This annotation with every markup point moved by `f`.
+    pub(crate) fn map_points(&self, f: &impl Fn(Point) -> Point) -> Self {
+        Annotation {
+            markup: self.markup.map_points(f),
+            ..self.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_joins_comments_and_keeps_markup() {
+        let mut a = Annotation {
+            comment: Some("first".into()),
+            markup: Markup {
+                circles: vec![(0, 0)],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        a.merge(Annotation {
+            comment: Some("second".into()),
+            markup: Markup {
+                labels: vec![((1, 1), "A".into())],
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        assert_eq!(a.comment.as_deref(), Some("first\n\nsecond"));
+        assert_eq!(a.markup.circles, vec![(0, 0)]);
+        assert_eq!(a.markup.labels, vec![((1, 1), "A".into())]);
+    }
+
+    #[test]
+    fn serde_skips_empty_fields() {
+        let a = Annotation {
+            glyphs: vec![Glyph {
+                kind: GlyphKind::Tesuji,
+                emphasized: false,
+            }],
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_string(&a).unwrap(),
+            r#"{"glyphs":[{"kind":"tesuji"}]}"#
+        );
+        assert!(serde_json::from_str::<Annotation>("{}").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,575 @@
+//! The incrementally maintained board core behind [`Goban`](crate::Goban).
+//!
+//! Stones of a chain are threaded through a circular `next` list and labelled
+//! with the index of the chain's head; the head records the chain's size and
+//! exact liberty count. Placing a stone only touches the chains around it, so
+//! legality checks read a few counters instead of flood-filling, and every
+//! write can be logged so a move is taken back without cloning the board.
+
+use arrayvec::ArrayVec;
+
+use crate::stone::Stone;
+use crate::topology::Topology;
+use crate::zobrist;
+
+const NONE: u16 = u16::MAX;
+
+/// One overwritten slot, holding the value it had before.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Change {
+    Cell(u16, i8),
+    Head(u16, u16),
+    Next(u16, u16),
+    Size(u16, u16),
+    Libs(u16, u16),
+}
+
+/// Write log for [`Board::place`]; `None` when the move is never taken back.
+pub(crate) type Log<'a> = Option<&'a mut Vec<Change>>;
+
+/// What placing a stone on an empty point would do, read off the chain
+/// counters before anything is written.
+#[derive(Debug, Clone)]
+pub(crate) struct Placement {
+    /// Heads of the opponent chains left without liberties.
+    pub captures: ArrayVec<usize, 4>,
+    pub captured_stones: usize,
+    /// Heads of the mover's chains that join the new stone.
+    pub friends: ArrayVec<usize, 4>,
+    pub empty_neighbors: usize,
+    /// The new stone's chain would have no liberties after its captures.
+    pub suicide: bool,
+}
+
+impl Placement {
+    /// A lone stone taking a lone stone and left with that point as its only
+    /// liberty: the shape that makes an immediate recapture a ko.
+    pub fn is_ko_shape(&self) -> bool {
+        self.captured_stones == 1 && self.friends.is_empty() && self.empty_neighbors == 0
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Board {
+    cols: u8,
+    rows: u8,
+    topology: Topology,
+    cells: Vec<i8>,
+    /// Head index of each stone's chain, `NONE` on empty points.
+    head: Vec<u16>,
+    /// Next stone of the same chain; the list is circular.
+    next: Vec<u16>,
+    /// Chain size, valid at head indices.
+    size: Vec<u16>,
+    /// Distinct liberties of the chain, valid at head indices.
+    libs: Vec<u16>,
+    hash: u64,
+}
+
+impl PartialEq for Board {
+    /// Boards are equal when their stones are; chain labels are bookkeeping.
+    fn eq(&self, other: &Self) -> bool {
+        self.cols == other.cols
+            && self.rows == other.rows
+            && self.topology == other.topology
+            && self.cells == other.cells
+    }
+}
+
+impl Board {
+    pub fn new(cols: u8, rows: u8, topology: Topology, cells: Vec<i8>) -> Self {
+        let len = cells.len();
+        assert_eq!(len, cols as usize * rows as usize, "board size mismatch");
+        let mut board = Board {
+            cols,
+            rows,
+            topology,
+            hash: zobrist::board_hash(&cells),
+            cells,
+            head: vec![NONE; len],
+            next: vec![NONE; len],
+            size: vec![0; len],
+            libs: vec![0; len],
+        };
+        board.rebuild_chains();
+        board
+    }
+
+    pub fn empty(cols: u8, rows: u8) -> Self {
+        Board::new(
+            cols,
+            rows,
+            Topology::default(),
+            vec![0; cols as usize * rows as usize],
+        )
+    }
+
+    // -- Accessors --
+
+    pub fn cells(&self) -> &[i8] {
+        &self.cells
+    }
+
+    pub fn cols(&self) -> u8 {
+        self.cols
+    }
+
+    pub fn rows(&self) -> u8 {
+        self.rows
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[inline]
+    pub fn get(&self, i: usize) -> i8 {
+        self.cells[i]
+    }
+
+    /// Distinct liberties of the chain through `i`, or 0 on an empty point.
+    pub fn liberty_count(&self, i: usize) -> usize {
+        match self.head[i] {
+            NONE => 0,
+            h => self.libs[h as usize] as usize,
+        }
+    }
+
+    /// The stones of the chain through `i`, starting at `i`.
+    pub fn chain(&self, i: usize) -> impl Iterator<Item = usize> + '_ {
+        let mut cursor = (self.head[i] != NONE).then_some(i);
+        std::iter::from_fn(move || {
+            let current = cursor?;
+            let next = self.next[current] as usize;
+            cursor = (next != i).then_some(next);
+            Some(current)
+        })
+    }
+
+    /// 4-connected neighbors as flat indices, wrapping around on a torus.
+    #[inline]
+    pub fn neighbors(&self, i: usize) -> ArrayVec<usize, 4> {
+        let cols = self.cols as usize;
+        let (x, y) = (i % cols, i / cols);
+        let mut result = ArrayVec::new();
+        if self.topology.is_planar() {
+            if x > 0 {
+                result.push(i - 1);
+            }
+            if x + 1 < cols {
+                result.push(i + 1);
+            }
+            if y > 0 {
+                result.push(i - cols);
+            }
+            if y + 1 < self.rows as usize {
+                result.push(i + cols);
+            }
+            return result;
+        }
+        let (x, y) = (x as u8, y as u8);
+        let steps = [
+            self.topology.step(x, -1, self.cols).map(|c| (c, y)),
+            self.topology.step(x, 1, self.cols).map(|c| (c, y)),
+            self.topology.step(y, -1, self.rows).map(|r| (x, r)),
+            self.topology.step(y, 1, self.rows).map(|r| (x, r)),
+        ];
+        for (c, r) in steps.into_iter().flatten() {
+            let n = r as usize * cols + c as usize;
+            if n != i && !result.contains(&n) {
+                result.push(n);
+            }
+        }
+        result
+    }
+
+    // -- Move analysis --
+
+    /// Describe placing `stone` on the empty point `i` without changing anything.
+    pub fn analyze(&self, i: usize, stone: Stone) -> Placement {
+        let sign = stone.to_int();
+        let mut placement = Placement {
+            captures: ArrayVec::new(),
+            captured_stones: 0,
+            friends: ArrayVec::new(),
+            empty_neighbors: 0,
+            suicide: false,
+        };
+        for n in self.neighbors(i) {
+            let cell = self.cells[n];
+            if cell == 0 {
+                placement.empty_neighbors += 1;
+                continue;
+            }
+            let h = self.head[n] as usize;
+            if cell == sign {
+                if !placement.friends.contains(&h) {
+                    placement.friends.push(h);
+                }
+            } else if self.libs[h] == 1 && !placement.captures.contains(&h) {
+                placement.captures.push(h);
+                placement.captured_stones += self.size[h] as usize;
+            }
+        }
+        placement.suicide = placement.captures.is_empty()
+            && placement.empty_neighbors == 0
+            && placement.friends.iter().all(|&h| self.libs[h] == 1);
+        placement
+    }
+
+    /// The board hash after `stone` is played at `i` as described by `placement`.
+    pub fn hash_after(&self, i: usize, stone: Stone, placement: &Placement) -> u64 {
+        let mut hash = self.hash;
+        if placement.suicide {
+            // The placed stone comes straight back off along with its chain.
+            for &h in &placement.friends {
+                for p in self.chain(h) {
+                    hash ^= zobrist::stone_key(p, stone);
+                }
+            }
+        } else {
+            hash ^= zobrist::stone_key(i, stone);
+            for &h in &placement.captures {
+                for p in self.chain(h) {
+                    hash ^= zobrist::stone_key(p, stone.opp());
+                }
+            }
+        }
+        hash
+    }
+
+    // -- Mutation --
+
+    /// Play `stone` at the empty point `i`, removing whatever `placement`
+    /// (from [`Board::analyze`] on this position) says dies. A suicidal
+    /// placement takes the mover's own chain off. Returns the number of the
+    /// mover's stones removed that way.
+    pub fn place(&mut self, i: usize, stone: Stone, placement: &Placement, mut log: Log) -> usize {
+        let sign = stone.to_int();
+        self.set_cell(i, sign, &mut log);
+        self.set_head(i, i as u16, &mut log);
+        self.set_next(i, i as u16, &mut log);
+        self.set_size(i, 1, &mut log);
+        self.set_libs(i, placement.empty_neighbors as u16, &mut log);
+
+        // The point was a liberty of every adjacent chain.
+        for h in self.neighbor_heads(i) {
+            self.set_libs(h, self.libs[h] - 1, &mut log);
+        }
+
+        let mut head = i;
+        for &friend in &placement.friends {
+            head = self.merge(head, friend, &mut log);
+        }
+
+        for &h in &placement.captures {
+            self.remove_chain(h, &mut log);
+        }
+        if placement.suicide {
+            self.remove_chain(head, &mut log)
+        } else {
+            0
+        }
+    }
+
+    /// Put `stone` on `i` regardless of the position around it, as setup
+    /// edits do. Chains are relabelled from scratch.
+    pub fn put(&mut self, i: usize, stone: Stone) {
+        self.write_cell(i, stone.to_int());
+        self.rebuild_chains();
+    }
+
+    /// Empty `i` regardless of the position around it.
+    pub fn clear(&mut self, i: usize) {
+        self.write_cell(i, 0);
+        self.rebuild_chains();
+    }
+
+    pub fn set_topology(&mut self, topology: Topology) {
+        self.topology = topology;
+        self.rebuild_chains();
+    }
+
+    /// Take back the writes in `changes` (in order, as logged) and restore `hash`.
+    pub fn unmake(&mut self, changes: Vec<Change>, hash: u64) {
+        for change in changes.into_iter().rev() {
+            match change {
+                Change::Cell(i, v) => self.cells[i as usize] = v,
+                Change::Head(i, v) => self.head[i as usize] = v,
+                Change::Next(i, v) => self.next[i as usize] = v,
+                Change::Size(i, v) => self.size[i as usize] = v,
+                Change::Libs(i, v) => self.libs[i as usize] = v,
+            }
+        }
+        self.hash = hash;
+    }
+
+    // -- Internal helpers --
+
+    /// Distinct chain heads around `i`.
+    fn neighbor_heads(&self, i: usize) -> ArrayVec<usize, 4> {
+        let mut heads = ArrayVec::new();
+        for n in self.neighbors(i) {
+            let h = self.head[n];
+            if h != NONE && !heads.contains(&(h as usize)) {
+                heads.push(h as usize);
+            }
+        }
+        heads
+    }
+
+    /// Join two adjacent chains of the same color, relabelling the smaller
+    /// one. Only the smaller chain's liberties are visited: those not already
+    /// next to the larger chain are new to it. Returns the surviving head.
+    fn merge(&mut self, a: usize, b: usize, log: &mut Log) -> usize {
+        let (big, small) = if self.size[a] >= self.size[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+
+        let mut fresh: Vec<usize> = Vec::new();
+        for s in self.chain(small) {
+            for n in self.neighbors(s) {
+                if self.cells[n] == 0
+                    && !fresh.contains(&n)
+                    && !self
+                        .neighbors(n)
+                        .iter()
+                        .any(|&m| self.head[m] as usize == big)
+                {
+                    fresh.push(n);
+                }
+            }
+        }
+        self.set_libs(big, self.libs[big] + fresh.len() as u16, log);
+
+        let mut s = small;
+        loop {
+            self.set_head(s, big as u16, log);
+            s = self.next[s] as usize;
+            if s == small {
+                break;
+            }
+        }
+        let (big_next, small_next) = (self.next[big], self.next[small]);
+        self.set_next(big, small_next, log);
+        self.set_next(small, big_next, log);
+        self.set_size(big, self.size[big] + self.size[small], log);
+        big
+    }
+
+    /// Take the chain through `h` off the board; each emptied point becomes a
+    /// liberty of the chains around it. Returns the number of stones removed.
+    fn remove_chain(&mut self, h: usize, log: &mut Log) -> usize {
+        // The `next` links are left alone, so the chain can be walked twice.
+        let mut s = h;
+        loop {
+            self.set_cell(s, 0, log);
+            self.set_head(s, NONE, log);
+            s = self.next[s] as usize;
+            if s == h {
+                break;
+            }
+        }
+        let mut removed = 0;
+        loop {
+            for h2 in self.neighbor_heads(s) {
+                self.set_libs(h2, self.libs[h2] + 1, log);
+            }
+            removed += 1;
+            s = self.next[s] as usize;
+            if s == h {
+                break;
+            }
+        }
+        removed
+    }
+
+    /// Overwrite a cell and keep the hash in step, leaving chains stale.
+    fn write_cell(&mut self, i: usize, value: i8) {
+        if let Some(old) = Stone::from_int(self.cells[i]) {
+            self.hash ^= zobrist::stone_key(i, old);
+        }
+        if let Some(new) = Stone::from_int(value) {
+            self.hash ^= zobrist::stone_key(i, new);
+        }
+        self.cells[i] = value;
+    }
+
+    /// Recompute every chain label, list and counter from the stones alone.
+    fn rebuild_chains(&mut self) {
+        let len = self.cells.len();
+        self.head.fill(NONE);
+        let mut lib_seen = vec![NONE; len];
+        let mut stack = Vec::new();
+        for start in 0..len {
+            let sign = self.cells[start];
+            if sign == 0 || self.head[start] != NONE {
+                continue;
+            }
+            let (mut size, mut libs) = (0u16, 0u16);
+            let mut last = start;
+            self.head[start] = start as u16;
+            stack.push(start);
+            while let Some(p) = stack.pop() {
+                self.next[last] = p as u16;
+                last = p;
+                size += 1;
+                for n in self.neighbors(p) {
+                    if self.cells[n] == 0 {
+                        if lib_seen[n] != start as u16 {
+                            lib_seen[n] = start as u16;
+                            libs += 1;
+                        }
+                    } else if self.cells[n] == sign && self.head[n] == NONE {
+                        self.head[n] = start as u16;
+                        stack.push(n);
+                    }
+                }
+            }
+            self.next[last] = start as u16;
+            self.size[start] = size;
+            self.libs[start] = libs;
+        }
+    }
+
+    fn set_cell(&mut self, i: usize, v: i8, log: &mut Log) {
+        if let Some(log) = log {
+            log.push(Change::Cell(i as u16, self.cells[i]));
+        }
+        self.write_cell(i, v);
+    }
+
+    fn set_head(&mut self, i: usize, v: u16, log: &mut Log) {
+        if let Some(log) = log {
+            log.push(Change::Head(i as u16, self.head[i]));
+        }
+        self.head[i] = v;
+    }
+
+    fn set_next(&mut self, i: usize, v: u16, log: &mut Log) {
+        if let Some(log) = log {
+            log.push(Change::Next(i as u16, self.next[i]));
+        }
+        self.next[i] = v;
+    }
+
+    fn set_size(&mut self, i: usize, v: u16, log: &mut Log) {
+        if let Some(log) = log {
+            log.push(Change::Size(i as u16, self.size[i]));
+        }
+        self.size[i] = v;
+    }
+
+    fn set_libs(&mut self, i: usize, v: u16, log: &mut Log) {
+        if let Some(log) = log {
+            log.push(Change::Libs(i as u16, self.libs[i]));
+        }
+        self.libs[i] = v;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Play `count` pseudo-random legal placements, checking after each one
+    /// that the incremental counters match a board rebuilt from its stones.
+    fn random_placements(board: &mut Board, count: usize, mut log: Option<&mut Vec<Change>>) {
+        let mut state = 0x2545_F491_4F6C_DD1Du64;
+        let mut stone = Stone::Black;
+        for _ in 0..count {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let i = (state % board.len() as u64) as usize;
+            if board.get(i) != 0 {
+                continue;
+            }
+            let placement = board.analyze(i, stone);
+            if placement.suicide {
+                continue;
+            }
+            board.place(i, stone, &placement, log.as_deref_mut());
+            assert_consistent(board);
+            stone = stone.opp();
+        }
+    }
+
+    fn assert_consistent(board: &Board) {
+        let fresh = Board::new(board.cols, board.rows, board.topology, board.cells.clone());
+        assert_eq!(board.hash, fresh.hash);
+        for i in 0..board.len() {
+            assert_eq!(
+                board.liberty_count(i),
+                fresh.liberty_count(i),
+                "libs at {i}"
+            );
+            let mut ours: Vec<_> = board.chain(i).collect();
+            let mut theirs: Vec<_> = fresh.chain(i).collect();
+            ours.sort_unstable();
+            theirs.sort_unstable();
+            assert_eq!(ours, theirs, "chain at {i}");
+        }
+    }
+
+    #[test]
+    fn counters_match_a_rebuilt_board() {
+        let mut board = Board::empty(9, 9);
+        random_placements(&mut board, 400, None);
+
+        let mut torus = Board::empty(7, 5);
+        torus.set_topology(Topology::Torus);
+        random_placements(&mut torus, 300, None);
+    }
+
+    #[test]
+    fn unmake_restores_every_counter() {
+        let mut board = Board::empty(9, 9);
+        random_placements(&mut board, 60, None);
+        let before = board.clone();
+
+        let mut log = Vec::new();
+        random_placements(&mut board, 200, Some(&mut log));
+        board.unmake(log, before.hash);
+
+        assert_eq!(board.cells, before.cells);
+        assert_eq!(board.head, before.head);
+        assert_eq!(board.hash, before.hash);
+        for i in 0..board.len() {
+            assert_eq!(board.liberty_count(i), before.liberty_count(i));
+        }
+        assert_consistent(&board);
+    }
+
+    #[test]
+    fn analyze_reads_captures_and_suicide_from_counters() {
+        // . B .
+        // B W B
+        // . . .
+        let mut cells = vec![0; 9];
+        cells[1] = 1;
+        cells[3] = 1;
+        cells[4] = -1;
+        cells[5] = 1;
+        let board = Board::new(3, 3, Topology::Planar, cells);
+        assert_eq!(board.liberty_count(4), 1);
+
+        let capture = board.analyze(7, Stone::Black);
+        assert_eq!(capture.captured_stones, 1);
+        assert!(!capture.suicide);
+
+        let suicide = board.analyze(0, Stone::White);
+        assert!(suicide.suicide);
+    }
+}
@@ -5,15 +5,21 @@ use serde::{Deserialize, Serialize};
 use crate::Point;
 use crate::error::GoError;
 use crate::goban::{Captures, Goban};
-use crate::handicap;
-use crate::ko::Ko;
+use crate::handicap::{self, HandicapPlacement};
+use crate::ko::{Ko, KoRule, PositionHistory};
+use crate::ruleset::Ruleset;
+use crate::setup::Setup;
 use crate::stone::Stone;
+use crate::topology::Topology;
 use crate::turn::Turn;
+use crate::zobrist;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum Stage {
     Unstarted,
+    /// Black is placing free handicap stones.
+    HandicapPlacement,
     BlackToPlay,
     WhiteToPlay,
     TerritoryReview,
@@ -22,7 +28,19 @@ pub enum Stage {
 
 impl Stage {
     pub fn is_play(&self) -> bool {
-        matches!(self, Stage::BlackToPlay | Stage::WhiteToPlay)
+        matches!(
+            self,
+            Stage::HandicapPlacement | Stage::BlackToPlay | Stage::WhiteToPlay
+        )
+    }
+
+    /// The stage a game enters once it starts, before any move is played.
+    pub fn opening(handicap: u8, placement: HandicapPlacement) -> Stage {
+        match placement {
+            _ if handicap < 2 => Stage::BlackToPlay,
+            HandicapPlacement::Fixed => Stage::WhiteToPlay,
+            HandicapPlacement::Free => Stage::HandicapPlacement,
+        }
     }
 }
 
@@ -30,6 +48,7 @@ impl fmt::Display for Stage {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Stage::Unstarted => write!(f, "unstarted"),
+            Stage::HandicapPlacement => write!(f, "handicap_placement"),
             Stage::BlackToPlay => write!(f, "black_to_play"),
             Stage::WhiteToPlay => write!(f, "white_to_play"),
             Stage::TerritoryReview => write!(f, "territory_review"),
@@ -44,6 +63,7 @@ impl std::str::FromStr for Stage {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "unstarted" => Ok(Stage::Unstarted),
+            "handicap_placement" => Ok(Stage::HandicapPlacement),
             "black_to_play" => Ok(Stage::BlackToPlay),
             "white_to_play" => Ok(Stage::WhiteToPlay),
             "territory_review" => Ok(Stage::TerritoryReview),
@@ -68,8 +88,13 @@ pub struct Engine {
     cols: u8,
     rows: u8,
     handicap: u8,
+    handicap_placement: HandicapPlacement,
+    /// Position edits as `(moves played before, edit)`, in order.
+    setups: Vec<(usize, Setup)>,
     moves: Vec<Turn>,
     goban: Goban,
+    /// Capture Go: the first player to capture this many stones wins.
+    capture_target: Option<u32>,
     result: Option<String>,
 }
 
@@ -91,38 +116,161 @@ impl Engine {
     }
 
     fn create(cols: u8, rows: u8, handicap: u8, moves: Vec<Turn>) -> Self {
-        let goban = Self::rebuild_goban(cols, rows, handicap, &moves);
-        let result = Self::result_from_moves(&moves);
-        Engine {
+        let mut engine = Engine {
             cols,
             rows,
             handicap,
+            handicap_placement: HandicapPlacement::Fixed,
+            setups: Vec::new(),
             moves,
-            goban,
-            result,
+            goban: Goban::with_dimensions(cols, rows),
+            capture_target: None,
+            result: None,
+        };
+        engine.goban = engine
+            .rebuild_goban(Ruleset::default(), Topology::default(), &engine.moves)
+            .expect("invalid move in replay");
+        engine.result = Self::result_from_moves(&engine.moves, &engine.goban, None);
+        engine
+    }
+
+    /// Switch to `ruleset`, replaying the moves so far under it.
+    ///
+    /// Panics if an existing move is illegal under the new rules.
+    pub fn with_ruleset(mut self, ruleset: Ruleset) -> Self {
+        self.goban = self
+            .rebuild_goban(ruleset, self.topology(), &self.moves)
+            .expect("invalid move in replay");
+        self
+    }
+
+    /// Switch how handicap stones are placed, replaying the moves so far.
+    ///
+    /// With [`HandicapPlacement::Free`], Black's first `handicap` moves are
+    /// the handicap stones and no star points are filled in.
+    pub fn with_handicap_placement(mut self, placement: HandicapPlacement) -> Self {
+        self.handicap_placement = placement;
+        self.rebuild()
+    }
+
+    /// Start from `setup` instead of an empty board, replaying the moves so far.
+    /// Replaces any earlier starting position.
+    pub fn with_setup(mut self, setup: Setup) -> Self {
+        self.setups.retain(|(at, _)| *at > 0);
+        if !setup.is_empty() {
+            self.setups.insert(0, (0, setup));
         }
-    }
-
-    fn rebuild_goban(cols: u8, rows: u8, handicap: u8, moves: &[Turn]) -> Goban {
-        let mut goban = Goban::with_dimensions(cols, rows);
-        if handicap >= 2
-            && let Some(pts) = handicap::handicap_points(cols, rows, handicap)
+        self.rebuild()
+    }
+
+    /// Replace the position edits with `setups`, replaying the moves so far.
+    /// Each setup is applied once the given number of moves is played.
+    pub fn with_setups(mut self, setups: Vec<(usize, Setup)>) -> Self {
+        self.setups = setups;
+        self.rebuild()
+    }
+
+    /// Connect the board edges according to `topology`, replaying the moves so far.
+    ///
+    /// Panics if an existing move is illegal on the new board.
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        self.goban = self
+            .rebuild_goban(*self.ruleset(), topology, &self.moves)
+            .expect("invalid move in replay");
+        self
+    }
+
+    /// Replace the moves with `moves`, the record of a game already played,
+    /// and replay them. Superko is not checked against the record (see
+    /// [`Goban::replay_moves`]); a move that cannot be played at all is an error.
+    pub fn replay(mut self, moves: Vec<Turn>) -> Result<Self, GoError> {
+        self.goban = self.rebuild_goban(*self.ruleset(), self.topology(), &moves)?;
+        self.result = Self::result_from_moves(&moves, &self.goban, self.capture_target);
+        self.moves = moves;
+        Ok(self)
+    }
+
+    /// Play Capture Go: the first player to capture `target` stones wins
+    /// immediately. The moves so far are checked against the target.
+    pub fn with_capture_target(mut self, target: u32) -> Self {
+        self.capture_target = Some(target);
+        self.update_capture_result();
+        self
+    }
+
+    /// Switch to `ko_rule`, keeping the rest of the ruleset.
+    ///
+    /// Panics if an existing move is illegal under the new rule.
+    pub fn with_ko_rule(self, ko_rule: KoRule) -> Self {
+        let ruleset = Ruleset {
+            ko_rule,
+            ..*self.ruleset()
+        };
+        self.with_ruleset(ruleset)
+    }
+
+    /// Replay the moves so far on a fresh board, keeping the rules and topology.
+    fn rebuild(mut self) -> Self {
+        self.goban = self
+            .rebuild_goban(*self.ruleset(), self.topology(), &self.moves)
+            .expect("invalid move in replay");
+        self
+    }
+
+    /// A fresh board under `ruleset` and `topology` with this game's
+    /// handicap, setups and `moves` on it.
+    fn rebuild_goban(
+        &self,
+        ruleset: Ruleset,
+        topology: Topology,
+        moves: &[Turn],
+    ) -> Result<Goban, GoError> {
+        let (cols, rows) = (self.cols, self.rows);
+        let mut goban = Goban::with_dimensions(cols, rows)
+            .with_ruleset(ruleset)
+            .with_topology(topology);
+        if self.handicap >= 2
+            && self.handicap_placement == HandicapPlacement::Fixed
+            && let Some(pts) = handicap::handicap_points(cols, rows, self.handicap)
         {
             for pt in pts {
                 goban.set_stone(pt, Stone::Black);
             }
         }
-        goban.replay_moves(moves)
+        let mut played = 0;
+        for (at, setup) in &self.setups {
+            let at = (*at).min(moves.len());
+            goban = goban.replay_moves(&moves[played..at])?.apply_setup(setup);
+            played = played.max(at);
+        }
+        goban.replay_moves(&moves[played..])
+    }
+
+    /// The result implied by the moves: a resignation (`B+R`), or under
+    /// Capture Go a player reaching the capture target (`B+C`).
+    fn result_from_moves(
+        moves: &[Turn],
+        goban: &Goban,
+        capture_target: Option<u32>,
+    ) -> Option<String> {
+        let last = moves.last()?;
+        if last.is_resign() {
+            return Some(format!("{}+R", last.stone.opp().letter()));
+        }
+        let target = capture_target?;
+        // The player who just moved is checked first: with pass stones both
+        // sides can gain prisoners, but only the mover can have just won.
+        [last.stone, last.stone.opp()]
+            .into_iter()
+            .find(|&stone| goban.captures().get(stone) >= target)
+            .map(|stone| format!("{}+C", stone.letter()))
     }
 
-    fn result_from_moves(moves: &[Turn]) -> Option<String> {
-        moves.last().and_then(|t| {
-            if t.is_resign() {
-                Some(format!("{}+R", t.stone.opp().letter()))
-            } else {
-                None
-            }
-        })
+    /// Record a Capture Go win once a move reaches the capture target.
+    fn update_capture_result(&mut self) {
+        if self.result.is_none() {
+            self.result = Self::result_from_moves(&self.moves, &self.goban, self.capture_target);
+        }
     }
 
     // -- Accessors --
@@ -139,6 +287,41 @@ impl Engine {
         self.handicap
     }
 
+    pub fn handicap_placement(&self) -> HandicapPlacement {
+        self.handicap_placement
+    }
+
+    /// Reattach the placement mode after restoring from a [`GameState`].
+    /// Unlike [`Engine::with_handicap_placement`], the moves are not replayed.
+    pub fn restore_handicap_placement(&mut self, placement: HandicapPlacement) {
+        self.handicap_placement = placement;
+    }
+
+    /// Free handicap stones Black still has to place.
+    pub fn pending_handicap_stones(&self) -> u8 {
+        if self.handicap < 2 || self.handicap_placement == HandicapPlacement::Fixed {
+            return 0;
+        }
+        self.handicap.saturating_sub(self.moves.len() as u8)
+    }
+
+    /// The free handicap stones placed so far, in order.
+    pub fn free_handicap_stones(&self) -> Vec<Point> {
+        if self.handicap_placement == HandicapPlacement::Fixed {
+            return Vec::new();
+        }
+        self.moves
+            .iter()
+            .take(self.handicap as usize)
+            .filter_map(|t| t.pos)
+            .collect()
+    }
+
+    /// Position edits as `(moves played before, edit)`; see [`Engine::edit_position`].
+    pub fn setups(&self) -> &[(usize, Setup)] {
+        &self.setups
+    }
+
     pub fn moves(&self) -> &[Turn] {
         &self.moves
     }
@@ -155,6 +338,61 @@ impl Engine {
         self.goban.ko()
     }
 
+    pub fn ruleset(&self) -> &Ruleset {
+        self.goban.ruleset()
+    }
+
+    pub fn ko_rule(&self) -> KoRule {
+        self.goban.ko_rule()
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.goban.topology()
+    }
+
+    pub fn capture_target(&self) -> Option<u32> {
+        self.capture_target
+    }
+
+    /// Reattach the topology after restoring from a [`GameState`], which does not carry it.
+    /// Unlike [`Engine::with_topology`], the moves are not replayed.
+    pub fn restore_topology(&mut self, topology: Topology) {
+        self.goban = self.goban.clone().with_topology(topology);
+    }
+
+    /// Reattach the ruleset after restoring from a [`GameState`], which does not carry it.
+    /// Unlike [`Engine::with_ruleset`], the moves are not replayed.
+    pub fn restore_ruleset(&mut self, ruleset: Ruleset) {
+        self.goban = self.goban.clone().with_ruleset(ruleset);
+    }
+
+    /// Hashes of the positions that came before the current one, used for superko.
+    pub fn position_history(&self) -> &PositionHistory {
+        self.goban.history()
+    }
+
+    /// 64-bit identity of the current position: the Zobrist hash of the
+    /// stones, with the board's shape, the side to move and any ko point
+    /// mixed in. Equal positions hash equally whatever moves led to them.
+    pub fn position_hash(&self) -> u64 {
+        let mut hash =
+            self.goban.hash() ^ zobrist::board_key(self.cols, self.rows, self.topology());
+        if self.current_turn_stone() == Stone::White {
+            hash ^= zobrist::white_to_play_key();
+        }
+        if let Some(ko) = self.goban.ko() {
+            let (col, row) = (ko.pos.0 as usize, ko.pos.1 as usize);
+            hash ^= zobrist::ko_key(row * self.cols as usize + col);
+        }
+        hash
+    }
+
+    /// Reattach a history saved with [`Engine::position_history`] after
+    /// restoring from a [`GameState`], which does not carry it.
+    pub fn restore_position_history(&mut self, history: PositionHistory) {
+        self.goban = self.goban.clone().with_history(history);
+    }
+
     pub fn captures(&self) -> &Captures {
         self.goban.captures()
     }
@@ -168,6 +406,18 @@ impl Engine {
     }
 
     pub fn current_turn_stone(&self) -> Stone {
+        if self.pending_handicap_stones() > 0 {
+            return Stone::Black;
+        }
+        if let Some(stone) = self
+            .setups
+            .iter()
+            .rev()
+            .take_while(|(at, _)| *at == self.moves.len())
+            .find_map(|(_, setup)| setup.to_play)
+        {
+            return stone;
+        }
         match self.moves.last() {
             None if self.handicap >= 2 => Stone::White,
             None => Stone::Black,
@@ -190,8 +440,9 @@ impl Engine {
             return Err(GoError::OutOfTurn);
         }
 
-        self.goban = self.goban.play(point, stone)?;
+        self.goban.play_in_place(point, stone)?;
         self.moves.push(Turn::play(stone, point));
+        self.update_capture_result();
         Ok(self.stage())
     }
 
@@ -199,12 +450,26 @@ impl Engine {
         if stone != self.current_turn_stone() {
             return Err(GoError::OutOfTurn);
         }
+        if self.pending_handicap_stones() > 0 {
+            return Err(GoError::HandicapPlacement);
+        }
 
-        self.goban = self.goban.pass();
+        self.goban.pass_turn(stone);
         self.moves.push(Turn::pass(stone));
+        self.update_capture_result();
         Ok(self.stage())
     }
 
+    /// Add or remove stones at the current position, as in an SGF setup node.
+    /// The edit is kept and replayed if earlier moves are rebuilt.
+    pub fn edit_position(&mut self, setup: Setup) {
+        if setup.is_empty() {
+            return;
+        }
+        self.goban = self.goban.apply_setup(&setup);
+        self.setups.push((self.moves.len(), setup));
+    }
+
     pub fn try_resign(&mut self, stone: Stone) -> Stage {
         if self.result.is_none() {
             self.result = Some(format!("{}+R", stone.opp().letter()));
@@ -214,8 +479,12 @@ impl Engine {
 
     pub fn pop_move(&mut self) -> Option<Turn> {
         let turn = self.moves.pop()?;
-        self.goban = Self::rebuild_goban(self.cols, self.rows, self.handicap, &self.moves);
-        self.result = Self::result_from_moves(&self.moves);
+        let played = self.moves.len();
+        self.setups.retain(|(at, _)| *at <= played);
+        self.goban = self
+            .rebuild_goban(*self.ruleset(), self.topology(), &self.moves)
+            .expect("invalid move in replay");
+        self.result = Self::result_from_moves(&self.moves, &self.goban, self.capture_target);
         Some(turn)
     }
 
@@ -224,7 +493,9 @@ impl Engine {
     }
 
     pub fn stage(&self) -> Stage {
-        if self.moves.is_empty() {
+        if self.result.is_none() && self.pending_handicap_stones() > 0 {
+            Stage::HandicapPlacement
+        } else if self.moves.is_empty() {
             Stage::Unstarted
         } else if self.result.is_some() {
             Stage::Completed
@@ -265,14 +536,17 @@ impl Engine {
         state: GameState,
     ) -> Self {
         let goban = Goban::from_state(state);
-        let result = Self::result_from_moves(&moves);
+        let result = Self::result_from_moves(&moves, &goban, None);
 
         Engine {
             cols,
             rows,
             handicap,
+            handicap_placement: HandicapPlacement::Fixed,
+            setups: Vec::new(),
             moves,
             goban,
+            capture_target: None,
             result,
         }
     }
@@ -292,8 +566,11 @@ mod tests {
             cols,
             rows,
             handicap: 0,
+            handicap_placement: HandicapPlacement::Fixed,
+            setups: Vec::new(),
             moves: Vec::new(),
             goban,
+            capture_target: None,
             result: None,
         }
     }
@@ -388,6 +665,111 @@ mod tests {
         assert_eq!(engine.current_turn_stone(), Stone::White);
     }
 
+    #[test]
+    fn free_handicap_black_places_stones_first() {
+        let mut engine =
+            Engine::with_handicap(9, 9, 2).with_handicap_placement(HandicapPlacement::Free);
+        assert!(engine.goban().is_empty());
+        assert_eq!(engine.stage(), Stage::HandicapPlacement);
+        assert_eq!(engine.current_turn_stone(), Stone::Black);
+        assert_eq!(
+            engine.try_pass(Stone::Black),
+            Err(GoError::HandicapPlacement)
+        );
+        assert_eq!(
+            engine.try_play(Stone::White, (4, 4)),
+            Err(GoError::OutOfTurn)
+        );
+
+        assert_eq!(
+            engine.try_play(Stone::Black, (0, 0)),
+            Ok(Stage::HandicapPlacement)
+        );
+        assert_eq!(engine.pending_handicap_stones(), 1);
+        assert_eq!(
+            engine.try_play(Stone::Black, (8, 8)),
+            Ok(Stage::WhiteToPlay)
+        );
+        assert_eq!(engine.free_handicap_stones(), vec![(0, 0), (8, 8)]);
+        assert_eq!(engine.pending_handicap_stones(), 0);
+    }
+
+    #[test]
+    fn free_handicap_replays_from_moves() {
+        // The first stone sits on a star point that fixed placement would fill.
+        let moves = vec![
+            Turn::play(Stone::Black, (2, 6)),
+            Turn::play(Stone::Black, (8, 8)),
+            Turn::play(Stone::White, (4, 4)),
+        ];
+        let engine = Engine::with_handicap(9, 9, 2)
+            .with_handicap_placement(HandicapPlacement::Free)
+            .replay(moves)
+            .unwrap();
+        assert_eq!(engine.stone_at((6, 2)), None);
+        assert_eq!(engine.stone_at((8, 8)), Some(Stone::Black));
+        assert_eq!(engine.current_turn_stone(), Stone::Black);
+        assert_eq!(engine.stage(), Stage::BlackToPlay);
+    }
+
+    #[test]
+    fn setup_sets_starting_position_and_side_to_move() {
+        let setup = Setup::stones(vec![(2, 2), (6, 6)], vec![(4, 4)]).with_to_play(Stone::White);
+        let mut engine = Engine::new(9, 9).with_setup(setup);
+        assert_eq!(engine.stone_at((2, 2)), Some(Stone::Black));
+        assert_eq!(engine.stone_at((4, 4)), Some(Stone::White));
+        assert_eq!(engine.current_turn_stone(), Stone::White);
+        assert_eq!(
+            engine.try_play(Stone::White, (4, 4)),
+            Err(GoError::Overwrite)
+        );
+
+        engine.try_play(Stone::White, (5, 5)).unwrap();
+        assert_eq!(engine.current_turn_stone(), Stone::Black);
+        engine.pop_move();
+        assert_eq!(engine.stone_at((6, 6)), Some(Stone::Black));
+        assert_eq!(engine.current_turn_stone(), Stone::White);
+    }
+
+    #[test]
+    fn edit_position_survives_rebuild_until_undone() {
+        let mut engine = Engine::with_moves(9, 9, vec![Turn::play(Stone::Black, (0, 0))]);
+        engine.edit_position(Setup {
+            empty: vec![(0, 0)],
+            to_play: Some(Stone::Black),
+            ..Default::default()
+        });
+        assert_eq!(engine.stone_at((0, 0)), None);
+        assert_eq!(engine.current_turn_stone(), Stone::Black);
+
+        // Black may play on the cleared point again.
+        engine.try_play(Stone::Black, (0, 0)).unwrap();
+        let rebuilt = engine.clone().with_ruleset(Ruleset::default());
+        assert_eq!(rebuilt.stone_at((0, 0)), Some(Stone::Black));
+        assert_eq!(rebuilt.setups().len(), 1);
+
+        engine.pop_move();
+        engine.pop_move();
+        assert!(engine.setups().is_empty());
+        assert!(engine.goban().is_empty());
+    }
+
+    #[test]
+    fn opening_stage_depends_on_placement() {
+        assert_eq!(
+            Stage::opening(0, HandicapPlacement::Free),
+            Stage::BlackToPlay
+        );
+        assert_eq!(
+            Stage::opening(3, HandicapPlacement::Fixed),
+            Stage::WhiteToPlay
+        );
+        assert_eq!(
+            Stage::opening(3, HandicapPlacement::Free),
+            Stage::HandicapPlacement
+        );
+    }
+
     // -- Turn management --
 
     #[test]
@@ -510,6 +892,235 @@ mod tests {
         assert!(!engine.is_legal((1, 1), Stone::White));
     }
 
+    // -- Superko --
+
+    /// On a 3x1 board: B a, W c, B b (captures c), W c (captures a-b).
+    /// Black replaying at a recreates the board after move 2 with the other
+    /// player on move.
+    fn positional_cycle(ko_rule: KoRule) -> Engine {
+        let moves = vec![
+            Turn::play(Stone::Black, (0, 0)),
+            Turn::play(Stone::White, (2, 0)),
+            Turn::play(Stone::Black, (1, 0)),
+            Turn::play(Stone::White, (2, 0)),
+        ];
+        Engine::with_moves(3, 1, moves).with_ko_rule(ko_rule)
+    }
+
+    #[test]
+    fn positional_superko_rejects_repeated_board() {
+        let mut engine = positional_cycle(KoRule::PositionalSuperko);
+        assert!(!engine.is_legal((0, 0), Stone::Black));
+        assert_eq!(engine.try_play(Stone::Black, (0, 0)), Err(GoError::Superko));
+        assert_eq!(engine.moves().len(), 4);
+    }
+
+    #[test]
+    fn situational_superko_allows_repeat_with_other_player_to_move() {
+        let mut engine = positional_cycle(KoRule::SituationalSuperko);
+        assert!(engine.is_legal((0, 0), Stone::Black));
+        assert!(engine.try_play(Stone::Black, (0, 0)).is_ok());
+    }
+
+    #[test]
+    fn replay_accepts_repetitions_recorded_before_superko() {
+        let mut simple = positional_cycle(KoRule::Simple);
+        simple.try_play(Stone::Black, (0, 0)).unwrap();
+
+        let replayed = Engine::new(3, 1)
+            .with_ko_rule(KoRule::PositionalSuperko)
+            .replay(simple.moves().to_vec())
+            .unwrap();
+        assert_eq!(replayed.board(), simple.board());
+        assert_eq!(replayed.ko_rule(), KoRule::PositionalSuperko);
+    }
+
+    #[test]
+    fn replay_reports_unplayable_moves() {
+        let moves = vec![
+            Turn::play(Stone::Black, (0, 0)),
+            Turn::play(Stone::White, (0, 0)),
+        ];
+        assert_eq!(
+            Engine::new(3, 3).replay(moves).unwrap_err(),
+            GoError::Overwrite
+        );
+    }
+
+    #[test]
+    fn simple_ko_rule_ignores_long_cycles() {
+        let mut engine = positional_cycle(KoRule::Simple);
+        assert!(engine.is_legal((0, 0), Stone::Black));
+        assert!(engine.try_play(Stone::Black, (0, 0)).is_ok());
+    }
+
+    #[test]
+    fn situational_superko_rejects_repeated_situation() {
+        // B a, W c, B pass, W b (captures a), B a (captures b-c) returns to
+        // the board after move 1 with White on move again.
+        let mut engine = Engine::new(3, 1).with_ko_rule(KoRule::SituationalSuperko);
+        engine.try_play(Stone::Black, (0, 0)).unwrap();
+        engine.try_play(Stone::White, (2, 0)).unwrap();
+        engine.try_pass(Stone::Black).unwrap();
+        engine.try_play(Stone::White, (1, 0)).unwrap();
+
+        assert!(!engine.is_legal((0, 0), Stone::Black));
+        assert_eq!(engine.try_play(Stone::Black, (0, 0)), Err(GoError::Superko));
+    }
+
+    #[test]
+    fn simple_ko_still_reported_as_ko_violation() {
+        let mut engine = engine_from_layout(&["+BW+", "BW+W", "+BW+", "++++"]);
+        engine.try_play(Stone::Black, (2, 1)).unwrap();
+        assert_eq!(
+            engine.try_play(Stone::White, (1, 1)),
+            Err(GoError::KoViolation)
+        );
+    }
+
+    #[test]
+    fn superko_history_survives_pop_move() {
+        let mut engine = positional_cycle(KoRule::PositionalSuperko);
+        engine.try_play(Stone::Black, (1, 0)).unwrap();
+        engine.pop_move().unwrap();
+        assert_eq!(engine.ko_rule(), KoRule::PositionalSuperko);
+        assert_eq!(engine.position_history().len(), 4);
+        assert!(!engine.is_legal((0, 0), Stone::Black));
+    }
+
+    #[test]
+    fn restored_history_keeps_superko() {
+        let engine = positional_cycle(KoRule::PositionalSuperko);
+        let mut restored =
+            Engine::from_game_state(3, 1, 0, engine.moves().to_vec(), engine.game_state());
+        restored.restore_ruleset(*engine.ruleset());
+        assert!(restored.is_legal((0, 0), Stone::Black));
+
+        restored.restore_position_history(engine.position_history().clone());
+        assert!(!restored.is_legal((0, 0), Stone::Black));
+    }
+
+    #[test]
+    fn position_hash_ignores_move_order() {
+        let a = Engine::with_moves(
+            5,
+            5,
+            vec![
+                Turn::play(Stone::Black, (1, 1)),
+                Turn::play(Stone::White, (3, 3)),
+                Turn::play(Stone::Black, (1, 3)),
+            ],
+        );
+        let b = Engine::with_moves(
+            5,
+            5,
+            vec![
+                Turn::play(Stone::Black, (1, 3)),
+                Turn::play(Stone::White, (3, 3)),
+                Turn::play(Stone::Black, (1, 1)),
+            ],
+        );
+        assert_eq!(a.position_hash(), b.position_hash());
+        assert_ne!(a.position_hash(), a.goban().hash());
+    }
+
+    #[test]
+    fn position_hash_tracks_side_to_move_and_ko() {
+        let mut engine = engine_from_layout(&["+BW+", "BW+W", "+BW+", "++++"]);
+        let before = engine.position_hash();
+        engine.try_play(Stone::Black, (2, 1)).unwrap();
+        let with_ko = engine.position_hash();
+        assert_ne!(with_ko, before);
+
+        engine.try_pass(Stone::White).unwrap();
+        // Same stones, ko lifted and Black on move again.
+        assert_ne!(engine.position_hash(), with_ko);
+        assert_eq!(
+            engine.position_hash() ^ zobrist::ko_key(5) ^ zobrist::white_to_play_key(),
+            with_ko
+        );
+    }
+
+    #[test]
+    fn position_hash_differs_across_board_shapes() {
+        assert_ne!(
+            Engine::new(9, 9).position_hash(),
+            Engine::new(19, 19).position_hash()
+        );
+        // Both stones sit on flat index 9.
+        let small = Engine::with_moves(9, 9, vec![Turn::play(Stone::Black, (0, 1))]);
+        let large = Engine::with_moves(19, 19, vec![Turn::play(Stone::Black, (9, 0))]);
+        assert_eq!(small.goban().hash(), large.goban().hash());
+        assert_ne!(small.position_hash(), large.position_hash());
+
+        let torus = Engine::new(9, 9).with_topology(Topology::Torus);
+        assert_ne!(Engine::new(9, 9).position_hash(), torus.position_hash());
+    }
+
+    // -- Rulesets --
+
+    #[test]
+    fn defaults_to_japanese_rules() {
+        let engine = Engine::new(9, 9);
+        assert_eq!(*engine.ruleset(), Ruleset::JAPANESE);
+    }
+
+    #[test]
+    fn multi_stone_suicide_only_with_suicide_rules() {
+        let layout = ["+W+", "BW+", "WW+"];
+
+        let mut japanese = engine_from_layout(&layout);
+        assert!(!japanese.is_legal((0, 0), Stone::Black));
+        assert_eq!(
+            japanese.try_play(Stone::Black, (0, 0)),
+            Err(GoError::Suicide)
+        );
+
+        let mut nz = engine_from_layout(&layout);
+        nz.restore_ruleset(Ruleset::NEW_ZEALAND);
+        assert!(nz.is_legal((0, 0), Stone::Black));
+        nz.try_play(Stone::Black, (0, 0)).unwrap();
+        assert_eq!(nz.stone_at((0, 0)), None);
+        assert_eq!(nz.stone_at((0, 1)), None);
+        assert_eq!(nz.captures().white, 2);
+    }
+
+    #[test]
+    fn single_stone_suicide_is_never_legal() {
+        let mut engine = engine_from_layout(&["+B++", "B+B+", "+B++", "++++"]);
+        engine.restore_ruleset(Ruleset::NEW_ZEALAND);
+        engine.try_play(Stone::Black, (3, 3)).unwrap();
+        assert!(!engine.is_legal((1, 1), Stone::White));
+        assert_eq!(engine.try_play(Stone::White, (1, 1)), Err(GoError::Suicide));
+    }
+
+    #[test]
+    fn pass_stones_go_to_the_opponent() {
+        let mut engine = Engine::new(9, 9).with_ruleset(Ruleset::AGA);
+        engine.try_play(Stone::Black, (4, 4)).unwrap();
+        engine.try_pass(Stone::White).unwrap();
+        assert_eq!(engine.captures().black, 1);
+
+        engine.pop_move().unwrap();
+        assert_eq!(engine.captures().black, 0);
+    }
+
+    #[test]
+    fn no_pass_stones_under_japanese_rules() {
+        let mut engine = Engine::new(9, 9);
+        engine.try_play(Stone::Black, (4, 4)).unwrap();
+        engine.try_pass(Stone::White).unwrap();
+        assert_eq!(engine.captures().black, 0);
+    }
+
+    #[test]
+    fn with_ruleset_replays_under_rules() {
+        let moves = vec![Turn::play(Stone::Black, (4, 4)), Turn::pass(Stone::White)];
+        let engine = Engine::with_moves(9, 9, moves).with_ruleset(Ruleset::AGA);
+        assert_eq!(*engine.ruleset(), Ruleset::AGA);
+        assert_eq!(engine.captures().black, 1);
+    }
+
     // -- Captures tracking --
 
     #[test]
@@ -820,4 +1431,82 @@ mod tests {
             engine.stone_captures(Stone::White)
         );
     }
+
+    // -- Topology --
+
+    #[test]
+    fn torus_replays_moves_with_wrapped_captures() {
+        let moves = vec![
+            Turn::pass(Stone::Black),
+            Turn::play(Stone::White, (1, 0)),
+            Turn::pass(Stone::Black),
+            Turn::play(Stone::White, (0, 1)),
+            // Suicide on a planar board; the wrapped edges still give liberties.
+            Turn::play(Stone::Black, (0, 0)),
+            Turn::play(Stone::White, (4, 0)),
+            Turn::pass(Stone::Black),
+            Turn::play(Stone::White, (0, 4)),
+        ];
+        let engine = Engine::new(5, 5)
+            .with_topology(Topology::Torus)
+            .replay(moves)
+            .unwrap();
+        assert_eq!(engine.topology(), Topology::Torus);
+        assert_eq!(engine.stone_captures(Stone::White), 1);
+        assert_eq!(engine.stone_at((0, 0)), None);
+    }
+
+    #[test]
+    fn restored_topology_keeps_wrapped_liberties() {
+        let engine = Engine::new(4, 4).with_topology(Topology::Torus);
+        let mut restored = Engine::from_game_state(4, 4, 0, vec![], engine.game_state());
+        assert_eq!(restored.topology(), Topology::Planar);
+
+        restored.restore_topology(Topology::Torus);
+        assert_eq!(restored.goban().neighbors((0, 0)).len(), 4);
+    }
+
+    // -- Capture Go --
+
+    fn corner_capture() -> Vec<Turn> {
+        vec![
+            Turn::play(Stone::Black, (0, 1)),
+            Turn::play(Stone::White, (0, 0)),
+            Turn::play(Stone::Black, (1, 0)),
+        ]
+    }
+
+    #[test]
+    fn capture_go_ends_on_reaching_target() {
+        let mut engine = Engine::new(5, 5).with_capture_target(1);
+        for turn in corner_capture() {
+            engine.try_play(turn.stone, turn.pos.unwrap()).unwrap();
+        }
+        assert_eq!(engine.result(), Some("B+C"));
+        assert_eq!(engine.stage(), Stage::Completed);
+    }
+
+    #[test]
+    fn capture_go_keeps_playing_below_target() {
+        let engine = Engine::with_moves(5, 5, corner_capture()).with_capture_target(2);
+        assert_eq!(engine.result(), None);
+        assert_eq!(engine.stage(), Stage::WhiteToPlay);
+    }
+
+    #[test]
+    fn capture_go_result_is_derived_from_replayed_moves() {
+        let mut engine = Engine::with_moves(5, 5, corner_capture()).with_capture_target(1);
+        assert_eq!(engine.result(), Some("B+C"));
+
+        engine.pop_move();
+        assert_eq!(engine.result(), None);
+        assert_eq!(engine.capture_target(), Some(1));
+    }
+
+    #[test]
+    fn captures_do_not_end_regular_games() {
+        let engine = Engine::with_moves(5, 5, corner_capture());
+        assert_eq!(engine.capture_target(), None);
+        assert_eq!(engine.result(), None);
+    }
 }
@@ -7,7 +7,9 @@ pub enum GoError {
     Suicide,
     NotOnBoard,
     KoViolation,
+    Superko,
     NoMovesToUndo,
+    HandicapPlacement,
 }
 
 impl fmt::Display for GoError {
@@ -18,7 +20,9 @@ impl fmt::Display for GoError {
             GoError::Suicide => write!(f, "suicide"),
             GoError::NotOnBoard => write!(f, "not on board"),
             GoError::KoViolation => write!(f, "ko violation"),
+            GoError::Superko => write!(f, "superko violation"),
             GoError::NoMovesToUndo => write!(f, "no moves to undo"),
+            GoError::HandicapPlacement => write!(f, "handicap stones must be placed first"),
         }
     }
 }
@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+use crate::Point;
+use crate::annotation::Annotation;
+use crate::setup::Setup;
 use crate::turn::Turn;
 
 pub type NodeId = usize;
@@ -11,12 +14,23 @@ pub struct TreeNode {
     pub children: Vec<NodeId>,
     #[serde(default)]
     pub depth: usize,
+    /// Position edits applied just before `turn`.
+    #[serde(default, skip_serializing_if = "Setup::is_empty")]
+    pub setup: Setup,
+    #[serde(default, skip_serializing_if = "Annotation::is_empty")]
+    pub annotation: Annotation,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameTree {
     nodes: Vec<TreeNode>,
     root_children: Vec<NodeId>,
+    /// Starting position, before the first move.
+    #[serde(default, skip_serializing_if = "Setup::is_empty")]
+    setup: Setup,
+    /// Commentary on the starting position.
+    #[serde(default, skip_serializing_if = "Annotation::is_empty")]
+    annotation: Annotation,
 }
 
 impl GameTree {
@@ -24,6 +38,15 @@ impl GameTree {
         Self {
             nodes: Vec::new(),
             root_children: Vec::new(),
+            setup: Setup::default(),
+            annotation: Annotation::default(),
+        }
+    }
+
+    pub fn with_setup(setup: Setup) -> Self {
+        Self {
+            setup,
+            ..Self::new()
         }
     }
 
@@ -39,6 +62,16 @@ impl GameTree {
     /// Add a child turn under the given parent (None = root).
     /// If an identical child already exists, returns its id instead of duplicating.
     pub fn add_child(&mut self, parent: Option<NodeId>, turn: Turn) -> NodeId {
+        self.add_child_with_setup(parent, Setup::default(), turn)
+    }
+
+    /// Like [`GameTree::add_child`], with position edits applied before the turn.
+    pub fn add_child_with_setup(
+        &mut self,
+        parent: Option<NodeId>,
+        setup: Setup,
+        turn: Turn,
+    ) -> NodeId {
         let siblings = match parent {
             Some(pid) => &self.nodes[pid].children,
             None => &self.root_children,
@@ -46,7 +79,8 @@ impl GameTree {
 
         // Check for existing identical child
         for &child_id in siblings {
-            if self.nodes[child_id].turn == turn {
+            let child = &self.nodes[child_id];
+            if child.turn == turn && child.setup == setup {
                 return child_id;
             }
         }
@@ -62,6 +96,8 @@ impl GameTree {
             parent,
             children: Vec::new(),
             depth,
+            setup,
+            annotation: Annotation::default(),
         });
 
         match parent {
@@ -92,6 +128,47 @@ impl GameTree {
             .collect()
     }
 
+    /// Position edits along the path to `node_id` (None = root), as
+    /// `(moves played before, edit)` pairs for [`crate::Engine::with_setups`].
+    pub fn setups_to(&self, node_id: Option<NodeId>) -> Vec<(usize, Setup)> {
+        let mut setups = Vec::new();
+        if !self.setup.is_empty() {
+            setups.push((0, self.setup.clone()));
+        }
+        if let Some(id) = node_id {
+            for id in self.path_to(id) {
+                let node = &self.nodes[id];
+                if !node.setup.is_empty() {
+                    setups.push((node.depth - 1, node.setup.clone()));
+                }
+            }
+        }
+        setups
+    }
+
+    pub fn setup(&self) -> &Setup {
+        &self.setup
+    }
+
+    pub fn set_setup(&mut self, setup: Setup) {
+        self.setup = setup;
+    }
+
+    /// Annotation on a node (None = the starting position).
+    pub fn annotation(&self, node_id: Option<NodeId>) -> &Annotation {
+        match node_id {
+            Some(id) => &self.nodes[id].annotation,
+            None => &self.annotation,
+        }
+    }
+
+    pub fn set_annotation(&mut self, node_id: Option<NodeId>, annotation: Annotation) {
+        match node_id {
+            Some(id) => self.nodes[id].annotation = annotation,
+            None => self.annotation = annotation,
+        }
+    }
+
     pub fn children_of(&self, parent: Option<NodeId>) -> &[NodeId] {
         match parent {
             Some(pid) => &self.nodes[pid].children,
@@ -169,6 +246,29 @@ impl GameTree {
         // for the typical usage pattern (small trees in analysis mode).
         true
     }
+
+    /// A copy of this tree with every move, setup and markup point moved by
+    /// `f`. Node ids and structure are unchanged.
+    pub(crate) fn map_points(&self, f: &impl Fn(Point) -> Point) -> GameTree {
+        let nodes = self
+            .nodes
+            .iter()
+            .map(|node| TreeNode {
+                turn: node.turn.map_points(f),
+                parent: node.parent,
+                children: node.children.clone(),
+                depth: node.depth,
+                setup: node.setup.map_points(f),
+                annotation: node.annotation.map_points(f),
+            })
+            .collect();
+        GameTree {
+            nodes,
+            root_children: self.root_children.clone(),
+            setup: self.setup.map_points(f),
+            annotation: self.annotation.map_points(f),
+        }
+    }
 }
 
 impl Default for GameTree {
@@ -358,4 +458,42 @@ mod tests {
         assert_eq!(restored.root_children().len(), 1);
         assert_eq!(restored.children_of(Some(root)).len(), 2);
     }
+
+    #[test]
+    fn setups_to_collects_root_and_node_edits() {
+        let mut tree = GameTree::with_setup(Setup::stones(vec![(2, 2)], vec![]));
+        let a = tree.add_child(None, Turn::play(Stone::White, (4, 4)));
+        let edit = Setup::stones(vec![], vec![(6, 6)]);
+        let b = tree.add_child_with_setup(Some(a), edit.clone(), Turn::play(Stone::Black, (0, 0)));
+        // Same turn without the edit is a different position.
+        let c = tree.add_child(Some(a), Turn::play(Stone::Black, (0, 0)));
+        assert_ne!(b, c);
+
+        let setups = tree.setups_to(Some(b));
+        assert_eq!(setups.len(), 2);
+        assert_eq!(setups[0], (0, tree.setup().clone()));
+        assert_eq!(setups[1], (1, edit));
+        assert_eq!(tree.setups_to(Some(c)).len(), 1);
+
+        let json = serde_json::to_string(&tree).unwrap();
+        let restored: GameTree = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.setups_to(Some(b)), tree.setups_to(Some(b)));
+    }
+
+    #[test]
+    fn annotations_on_root_and_nodes() {
+        let mut tree = GameTree::from_moves(&[Turn::play(Stone::Black, (3, 3))]);
+        let a = tree.root_children()[0];
+        let comment = |text: &str| Annotation {
+            comment: Some(text.into()),
+            ..Default::default()
+        };
+        tree.set_annotation(None, comment("Black to live"));
+        tree.set_annotation(Some(a), comment("Correct"));
+
+        let json = serde_json::to_string(&tree).unwrap();
+        let restored: GameTree = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.annotation(None), &comment("Black to live"));
+        assert_eq!(restored.annotation(Some(a)), &comment("Correct"));
+    }
 }
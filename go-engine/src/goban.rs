@@ -1,8 +1,12 @@
 use arrayvec::ArrayVec;
 
+use crate::board::{Board, Change};
 use crate::error::GoError;
-use crate::ko::Ko;
+use crate::ko::{Ko, KoRule, PositionHistory};
+use crate::ruleset::Ruleset;
+use crate::setup::Setup;
 use crate::stone::Stone;
+use crate::topology::Topology;
 use crate::turn::{Move, Turn};
 use crate::{GameState, Point};
 
@@ -33,14 +37,30 @@ impl Captures {
     }
 }
 
-/// The Go board stored as a flat array.
+/// The Go board, with a move-by-move interface over the incremental
+/// [`Board`] core.
+///
+/// Alongside the stones, the goban keeps a running Zobrist hash of the board
+/// and the hashes of every earlier position so the ruleset's ko rule can
+/// reject superko repetitions.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Goban {
-    board: Vec<i8>,
-    cols: u8,
-    rows: u8,
+    board: Board,
     captures: Captures,
     ko: Option<Ko>,
+    rules: Ruleset,
+    history: PositionHistory,
+}
+
+/// Everything [`Goban::unmake_move`] needs to take back one move made with
+/// [`Goban::make_move`] or [`Goban::make_pass`].
+#[derive(Debug)]
+pub struct Undo {
+    changes: Vec<Change>,
+    hash: u64,
+    captures: Captures,
+    ko: Option<Ko>,
+    history_len: usize,
 }
 
 impl Goban {
@@ -54,72 +74,104 @@ impl Goban {
             "malformed board matrix"
         );
 
-        Goban {
-            board: board.into_iter().flatten().collect(),
-            cols,
-            rows,
-            captures: Captures::new(),
-            ko: None,
-        }
+        let board: Vec<i8> = board.into_iter().flatten().collect();
+        Goban::from_board(Board::new(cols, rows, Topology::default(), board))
     }
 
     /// Create an empty board with the given dimensions.
     pub fn with_dimensions(cols: u8, rows: u8) -> Self {
+        Goban::from_board(Board::empty(cols, rows))
+    }
+
+    fn from_board(board: Board) -> Self {
         Goban {
-            board: vec![0i8; cols as usize * rows as usize],
-            cols,
-            rows,
+            board,
             captures: Captures::new(),
             ko: None,
+            rules: Ruleset::default(),
+            history: PositionHistory::new(),
+        }
+    }
+
+    /// Use `rules` for every later move on this board.
+    pub fn with_ruleset(mut self, rules: Ruleset) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Use `ko_rule` for every later move on this board, keeping the rest of the ruleset.
+    pub fn with_ko_rule(mut self, ko_rule: KoRule) -> Self {
+        self.rules.ko_rule = ko_rule;
+        self
+    }
+
+    /// Connect the board edges according to `topology`.
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        if topology != self.board.topology() {
+            self.board.set_topology(topology);
         }
+        self
+    }
+
+    /// Replace the record of earlier positions, e.g. when restoring from a cache.
+    pub fn with_history(mut self, history: PositionHistory) -> Self {
+        self.history = history;
+        self
     }
 
     /// Replay a list of turns onto an empty board of the given dimensions.
-    pub fn with_moves(cols: u8, rows: u8, moves: &[Turn]) -> Self {
+    pub fn with_moves(cols: u8, rows: u8, moves: &[Turn]) -> Result<Self, GoError> {
         Goban::with_dimensions(cols, rows).replay_moves(moves)
     }
 
     /// Replay a list of turns onto this board, consuming and returning the result.
-    pub fn replay_moves(mut self, moves: &[Turn]) -> Self {
+    ///
+    /// The turns are a record of moves already played, so superko is not
+    /// checked against them: a game stored before its rules forbade a
+    /// repetition still loads. Their positions are recorded, and later moves
+    /// are held to the ko rule as usual.
+    pub fn replay_moves(mut self, moves: &[Turn]) -> Result<Self, GoError> {
+        let ko_rule = self.rules.ko_rule;
+        self.rules.ko_rule = KoRule::Simple;
         for m in moves {
             match m.kind {
                 Move::Play => {
-                    let point = m.pos.expect("play move must have a point");
-                    self = self.play(point, m.stone).expect("invalid move in replay");
+                    let point = m.pos.ok_or(GoError::NotOnBoard)?;
+                    self.play_in_place(point, m.stone)?;
                 }
                 Move::Pass => {
-                    self = self.pass();
+                    self.pass_turn(m.stone);
                 }
                 Move::Resign => {}
             }
         }
+        self.rules.ko_rule = ko_rule;
 
-        self
+        Ok(self)
     }
 
-    /// Restore a goban from serialized state.
+    /// Restore a goban from serialized state. The position history starts empty.
     pub fn from_state(state: GameState) -> Self {
+        let board = Board::new(state.cols, state.rows, Topology::default(), state.board);
         Goban {
-            board: state.board,
-            cols: state.cols,
-            rows: state.rows,
             captures: state.captures,
             ko: state.ko,
+            ..Goban::from_board(board)
         }
     }
 
     // -- Accessors --
 
     pub fn board(&self) -> &[i8] {
-        &self.board
+        self.board.cells()
     }
 
     pub fn cols(&self) -> u8 {
-        self.cols
+        self.board.cols()
     }
 
     pub fn rows(&self) -> u8 {
-        self.rows
+        self.board.rows()
     }
 
     pub fn captures(&self) -> &Captures {
@@ -130,33 +182,87 @@ impl Goban {
         &self.ko
     }
 
+    pub fn ruleset(&self) -> &Ruleset {
+        &self.rules
+    }
+
+    pub fn ko_rule(&self) -> KoRule {
+        self.rules.ko_rule
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.board.topology()
+    }
+
+    pub fn history(&self) -> &PositionHistory {
+        &self.history
+    }
+
+    /// Zobrist hash of the stones on the board, kept up to date on every
+    /// placement, capture and setup edit. This is the hash the superko
+    /// history records.
+    pub fn hash(&self) -> u64 {
+        self.board.hash()
+    }
+
+    /// The incremental board core, for hot loops that work on flat indices.
+    pub(crate) fn core(&self) -> &Board {
+        &self.board
+    }
+
     pub fn stone_at(&self, point: Point) -> Option<Stone> {
         let (col, row) = point;
         if self.on_board(point) {
-            Stone::from_int(self.board[self.idx(col, row)])
+            Stone::from_int(self.board.get(self.idx(col, row)))
         } else {
             None
         }
     }
 
     pub fn on_board(&self, (col, row): Point) -> bool {
-        col < self.cols && row < self.rows
+        col < self.cols() && row < self.rows()
     }
 
     pub fn is_empty(&self) -> bool {
-        self.board.iter().all(|&s| s == 0)
+        self.board().iter().all(|&s| s == 0)
     }
 
     // -- Game actions --
 
     /// Place a stone on the board. Returns a new Goban with the move applied, or an error.
     pub fn play(&self, point: Point, stone: Stone) -> Result<Goban, GoError> {
-        let (mut goban, dead_stones, liberties) = self.place_stone(point, stone)?;
+        let mut goban = self.clone();
+        goban.play_in_place(point, stone)?;
+        Ok(goban)
+    }
 
-        let ko = Self::detect_ko(&goban, &dead_stones, &liberties, point, stone);
-        goban.ko = ko;
+    /// Place a stone on this board. On error the board is left unchanged.
+    pub fn play_in_place(&mut self, point: Point, stone: Stone) -> Result<(), GoError> {
+        self.apply_play(point, stone, None)
+    }
 
-        Ok(goban)
+    /// Place a stone on this board, returning what [`Goban::unmake_move`]
+    /// needs to take it back. Moves must be taken back in reverse order.
+    pub fn make_move(&mut self, point: Point, stone: Stone) -> Result<Undo, GoError> {
+        let mut undo = self.undo_point();
+        self.apply_play(point, stone, Some(&mut undo.changes))?;
+        Ok(undo)
+    }
+
+    /// Pass on behalf of `stone` in place, returning what
+    /// [`Goban::unmake_move`] needs to take it back.
+    pub fn make_pass(&mut self, stone: Stone) -> Undo {
+        let undo = self.undo_point();
+        self.pass_turn(stone);
+        undo
+    }
+
+    /// Restore the position from before the move that produced `undo`.
+    pub fn unmake_move(&mut self, undo: Undo) {
+        self.board.unmake(undo.changes, undo.hash);
+        self.captures = undo.captures;
+        self.ko = undo.ko;
+        self.history.truncate(undo.history_len);
     }
 
     /// Pass: clears ko and returns a new goban (matching `play()`'s pattern).
@@ -166,12 +272,50 @@ impl Goban {
         goban
     }
 
-    /// Place a stone, resolve captures, check for suicide.
-    pub(crate) fn place_stone(
-        &self,
+    /// Pass on behalf of `stone`, recording the position it left for situational superko.
+    /// With pass stones, the opponent also gains a prisoner.
+    pub(crate) fn pass_turn(&mut self, stone: Stone) {
+        self.ko = None;
+        self.history.push(self.board.hash(), stone);
+        if self.rules.pass_stones {
+            self.captures.add(stone.opp(), 1);
+        }
+    }
+
+    /// Apply setup edits: clear `empty`, then add `black` and `white` stones.
+    /// Any ko is lifted; earlier positions stay in the superko history.
+    pub fn apply_setup(&self, setup: &Setup) -> Self {
+        let mut goban = self.pass();
+        for &pt in &setup.empty {
+            goban.clear_stone(pt);
+        }
+        for &pt in &setup.black {
+            goban.set_stone(pt, Stone::Black);
+        }
+        for &pt in &setup.white {
+            goban.set_stone(pt, Stone::White);
+        }
+        goban
+    }
+
+    fn undo_point(&self) -> Undo {
+        Undo {
+            changes: Vec::new(),
+            hash: self.board.hash(),
+            captures: self.captures.clone(),
+            ko: self.ko.clone(),
+            history_len: self.history.len(),
+        }
+    }
+
+    /// Place a stone, resolve captures, check for suicide and repetition.
+    /// Nothing is written until the move is known to be legal.
+    fn apply_play(
+        &mut self,
         point: Point,
         stone: Stone,
-    ) -> Result<(Goban, Vec<Point>, Vec<Point>), GoError> {
+        log: Option<&mut Vec<Change>>,
+    ) -> Result<(), GoError> {
         if !self.on_board(point) {
             return Err(GoError::NotOnBoard);
         }
@@ -184,137 +328,66 @@ impl Goban {
             return Err(GoError::KoViolation);
         }
 
-        let mut goban = self.clone();
-        goban.set_stone(point, stone);
-
-        // Find and remove captured opponent chains
-        let mut dead_stones = Vec::new();
-        let neighbor_chains = goban.opponent_neighbor_chains(point);
-        for chain in &neighbor_chains {
-            if !goban.chain_has_any_liberty(chain) {
-                dead_stones.extend(chain);
-            }
-        }
-
-        goban.capture_mut(&dead_stones);
-
-        // Check suicide + collect chain/liberties for ko detection
-        let (chain, liberties) = goban.chain_and_liberties(point);
-        let _ = chain; // used only to verify the placed stone is alive
-        if liberties.is_empty() {
+        let i = self.idx(point.0, point.1);
+        let placement = self.board.analyze(i, stone);
+        // Only multi-stone suicide is ever legal; a lone stone would just be a pass.
+        if placement.suicide && (!self.rules.suicide_allowed || placement.friends.is_empty()) {
             return Err(GoError::Suicide);
         }
 
-        Ok((goban, dead_stones, liberties))
-    }
-
-    /// Remove captured stones from the board in place.
-    fn capture_mut(&mut self, stones: &[Point]) {
-        if stones.is_empty() {
-            return;
+        if self.repeats_position(self.board.hash_after(i, stone, &placement), stone) {
+            return Err(GoError::Superko);
         }
+        self.history.push(self.board.hash(), stone);
 
-        let stone_color = self.stone_at(stones[0]).unwrap();
-        let capturing_color = stone_color.opp();
+        let lost = self.board.place(i, stone, &placement, log);
+        self.captures.add(stone, placement.captured_stones as u32);
+        self.captures.add(stone.opp(), lost as u32);
 
-        for &pt in stones {
-            self.clear_stone(pt);
-        }
-        self.captures.add(capturing_color, stones.len() as u32);
+        self.ko = if placement.is_ko_shape() {
+            let (col, row) = self.board_point(placement.captures[0]);
+            Some(Ko {
+                pos: (col as i8, row as i8),
+                illegal: stone.opp(),
+            })
+        } else {
+            None
+        };
+
+        Ok(())
     }
 
     // -- Graph algorithms --
 
-    /// Get the 4-connected neighbors that are on the board.
+    /// Get the 4-connected neighbors that are on the board. On a torus the
+    /// edges wrap, so every point has four neighbors unless the board is too
+    /// narrow for them to be distinct.
     pub fn neighbors(&self, (col, row): Point) -> ArrayVec<Point, 4> {
-        let mut result = ArrayVec::new();
-        if col > 0 {
-            result.push((col - 1, row));
-        }
-        if col + 1 < self.cols {
-            result.push((col + 1, row));
-        }
-        if row > 0 {
-            result.push((col, row - 1));
-        }
-        if row + 1 < self.rows {
-            result.push((col, row + 1));
-        }
-        result
+        self.board
+            .neighbors(self.idx(col, row))
+            .into_iter()
+            .map(|n| self.board_point(n))
+            .collect()
     }
 
-    /// Flood-fill connected group of same-colored stones.
+    /// The connected group of same-colored stones through `point`.
     pub fn chain(&self, point: Point) -> Vec<Point> {
-        let stone = match self.stone_at(point) {
-            Some(s) => s,
-            None => return Vec::new(),
-        };
-
-        let mut visited = vec![false; self.board.len()];
-        let mut result = Vec::new();
-        let mut stack = vec![point];
-
-        while let Some(p) = stack.pop() {
-            let vi = self.idx(p.0, p.1);
-            if visited[vi] {
-                continue;
-            }
-            visited[vi] = true;
-            result.push(p);
-            for n in self.neighbors(p) {
-                if self.stone_at(n) == Some(stone) && !visited[self.idx(n.0, n.1)] {
-                    stack.push(n);
-                }
-            }
+        if self.stone_at(point).is_none() {
+            return Vec::new();
         }
-
-        result
-    }
-
-    /// Check whether a chain has at least one liberty, without allocating.
-    fn chain_has_any_liberty(&self, chain: &[Point]) -> bool {
-        chain.iter().any(|&p| {
-            self.neighbors(p)
-                .iter()
-                .any(|&n| self.stone_at(n).is_none())
-        })
+        self.board
+            .chain(self.idx(point.0, point.1))
+            .map(|i| self.board_point(i))
+            .collect()
     }
 
-    /// Single flood-fill that returns both chain members and liberty positions.
-    fn chain_and_liberties(&self, point: Point) -> (Vec<Point>, Vec<Point>) {
-        let stone = match self.stone_at(point) {
-            Some(s) => s,
-            None => return (Vec::new(), Vec::new()),
-        };
-
-        let len = self.board.len();
-        let mut visited = vec![false; len];
-        let mut lib_seen = vec![false; len];
-        let mut chain = Vec::new();
-        let mut liberties = Vec::new();
-        let mut stack = vec![point];
-
-        while let Some(p) = stack.pop() {
-            let vi = self.idx(p.0, p.1);
-            if visited[vi] {
-                continue;
-            }
-            visited[vi] = true;
-            chain.push(p);
-            for n in self.neighbors(p) {
-                let ni = self.idx(n.0, n.1);
-                match self.stone_at(n) {
-                    Some(s) if s == stone && !visited[ni] => stack.push(n),
-                    None if !lib_seen[ni] => {
-                        lib_seen[ni] = true;
-                        liberties.push(n);
-                    }
-                    _ => {}
-                }
-            }
+    /// Number of distinct liberties of the group through `point`, or 0 on an
+    /// empty point. Maintained incrementally, so this does not walk the group.
+    pub fn liberty_count(&self, point: Point) -> usize {
+        if !self.on_board(point) {
+            return 0;
         }
-
-        (chain, liberties)
+        self.board.liberty_count(self.idx(point.0, point.1))
     }
 
     /// Get the liberties of a single stone's connected group.
@@ -339,133 +412,79 @@ impl Goban {
         libs
     }
 
-    /// Find all opponent chains neighboring a given point.
-    fn opponent_neighbor_chains(&self, point: Point) -> Vec<Vec<Point>> {
-        let stone = match self.stone_at(point) {
-            Some(s) => s,
-            _ => return Vec::new(),
-        };
-        let opponent = stone.opp();
-
-        let mut chains = Vec::new();
-        let mut visited = vec![false; self.board.len()];
-
-        for n in self.neighbors(point) {
-            if self.stone_at(n) != Some(opponent) {
-                continue;
-            }
-            if visited[self.idx(n.0, n.1)] {
-                continue;
-            }
-            let ch = self.chain_from(n, &mut visited);
-            if !ch.is_empty() {
-                chains.push(ch);
-            }
-        }
-
-        chains
-    }
-
-    /// Chain flood-fill using a shared visited bitset.
+    /// The chain through `point`, skipping it if already `visited`, and
+    /// marking its stones in the shared visited bitset.
     pub(crate) fn chain_from(&self, point: Point, visited: &mut [bool]) -> Vec<Point> {
-        let stone = match self.stone_at(point) {
-            Some(s) => s,
-            None => return Vec::new(),
-        };
+        if self.stone_at(point).is_none() || visited[self.idx(point.0, point.1)] {
+            return Vec::new();
+        }
 
         let mut result = Vec::new();
-        let mut stack = vec![point];
-
-        while let Some(p) = stack.pop() {
-            let vi = self.idx(p.0, p.1);
-            if visited[vi] {
-                continue;
-            }
-            visited[vi] = true;
-            result.push(p);
-            for n in self.neighbors(p) {
-                if self.stone_at(n) == Some(stone) && !visited[self.idx(n.0, n.1)] {
-                    stack.push(n);
-                }
-            }
+        for i in self.board.chain(self.idx(point.0, point.1)) {
+            visited[i] = true;
+            result.push(self.board_point(i));
         }
 
         result
     }
 
-    /// Lightweight legality check without cloning the board.
+    /// Legality check that reads the chain counters without touching the board.
     pub fn is_legal_move(&self, point: Point, stone: Stone) -> bool {
         if !self.on_board(point) || self.stone_at(point).is_some() || self.is_ko(point, stone) {
             return false;
         }
 
-        let opponent = stone.opp();
-
-        // Fast path: any empty neighbor means immediate liberty
-        if self
-            .neighbors(point)
-            .iter()
-            .any(|&n| self.stone_at(n).is_none())
-        {
+        let i = self.idx(point.0, point.1);
+        let placement = self.board.analyze(i, stone);
+        if placement.suicide && (!self.rules.suicide_allowed || placement.friends.is_empty()) {
+            return false;
+        }
+        if self.rules.ko_rule == KoRule::Simple || self.history.is_empty() {
             return true;
         }
 
-        // Slow path: check captures or friendly liberties without cloning
-        // If any opponent neighbor chain has exactly one liberty (this point),
-        // placing here captures it, so the move is legal.
-        let mut visited = vec![false; self.board.len()];
-        for n in self.neighbors(point) {
-            if self.stone_at(n) == Some(opponent) && !visited[self.idx(n.0, n.1)] {
-                let chain = self.chain_from(n, &mut visited);
-                // Check if this chain has any liberty other than `point`
-                let has_other_liberty = chain.iter().any(|&p| {
-                    self.neighbors(p)
-                        .iter()
-                        .any(|&nb| nb != point && self.stone_at(nb).is_none())
-                });
-                if !has_other_liberty {
-                    return true; // this chain would be captured
-                }
-            }
-        }
+        !self.repeats_position(self.board.hash_after(i, stone, &placement), stone)
+    }
 
-        // Check if any friendly neighbor chain has a liberty other than `point`
-        let mut friendly_visited = vec![false; self.board.len()];
-        for n in self.neighbors(point) {
-            if self.stone_at(n) == Some(stone) && !friendly_visited[self.idx(n.0, n.1)] {
-                let chain = self.chain_from(n, &mut friendly_visited);
-                let has_other_liberty = chain.iter().any(|&p| {
-                    self.neighbors(p)
-                        .iter()
-                        .any(|&nb| nb != point && self.stone_at(nb).is_none())
-                });
-                if has_other_liberty {
-                    return true; // friendly chain has liberty, so placed stone survives
-                }
+    /// Whether `stone` moving into the board `hash` repeats an earlier position.
+    /// The current position counts too, since it is not yet in the history.
+    fn repeats_position(&self, hash: u64, stone: Stone) -> bool {
+        let to_play = stone.opp();
+        let ko_rule = self.rules.ko_rule;
+        match ko_rule {
+            KoRule::Simple => false,
+            KoRule::PositionalSuperko => {
+                hash == self.board.hash() || self.history.repeats(ko_rule, hash, to_play)
             }
+            // The current position has `stone` on move, so it never matches.
+            KoRule::SituationalSuperko => self.history.repeats(ko_rule, hash, to_play),
         }
-
-        false // suicide
     }
 
     // -- Internal helpers --
 
     #[inline]
     fn idx(&self, col: u8, row: u8) -> usize {
-        row as usize * self.cols as usize + col as usize
+        row as usize * self.cols() as usize + col as usize
+    }
+
+    #[inline]
+    fn board_point(&self, i: usize) -> Point {
+        let cols = self.cols() as usize;
+        ((i % cols) as u8, (i / cols) as u8)
     }
 
     pub fn set_stone(&mut self, (col, row): Point, stone: Stone) {
         if self.on_board((col, row)) {
             let i = self.idx(col, row);
-            self.board[i] = stone.to_int();
+            self.board.put(i, stone);
         }
     }
 
     fn clear_stone(&mut self, (col, row): Point) {
         if self.on_board((col, row)) {
             let i = self.idx(col, row);
-            self.board[i] = 0;
+            self.board.clear(i);
         }
     }
 
@@ -474,32 +493,6 @@ impl Goban {
             .as_ref()
             .is_some_and(|ko| ko.pos == (point.0 as i8, point.1 as i8) && ko.illegal == stone)
     }
-
-    fn detect_ko(
-        goban: &Goban,
-        dead_stones: &[Point],
-        liberties: &[Point],
-        point: Point,
-        stone: Stone,
-    ) -> Option<Ko> {
-        let is_ko = dead_stones.len() == 1
-            && liberties.len() == 1
-            && liberties[0] == dead_stones[0]
-            && goban
-                .neighbors(point)
-                .iter()
-                .all(|&n| goban.stone_at(n) != Some(stone));
-
-        if is_ko {
-            let ko_point = dead_stones[0];
-            Some(Ko {
-                pos: (ko_point.0 as i8, ko_point.1 as i8),
-                illegal: stone.opp(),
-            })
-        } else {
-            None
-        }
-    }
 }
 
 #[cfg(test)]
@@ -543,6 +536,26 @@ mod tests {
         assert_eq!(result, Err(GoError::KoViolation));
     }
 
+    #[test]
+    fn apply_setup_edits_stones_and_lifts_ko() {
+        let goban = goban_from_layout(&["+BW+", "BW+W", "+BW+", "++++"]);
+        let goban = goban.play((2, 1), Stone::Black).unwrap();
+        assert!(goban.ko().is_some());
+
+        let setup = Setup {
+            black: vec![(3, 3)],
+            white: vec![(0, 3)],
+            empty: vec![(1, 0)],
+            to_play: None,
+        };
+        let edited = goban.apply_setup(&setup);
+        assert!(edited.ko().is_none());
+        assert_eq!(edited.stone_at((1, 0)), None);
+        assert_eq!(edited.stone_at((3, 3)), Some(Stone::Black));
+        assert_eq!(edited.stone_at((0, 3)), Some(Stone::White));
+        assert_eq!(edited.hash(), crate::zobrist::board_hash(edited.board()));
+    }
+
     #[test]
     fn prevents_suicide() {
         let goban = goban_from_layout(&["+B++", "B+++", "++++", "++++"]);
@@ -615,4 +628,69 @@ mod tests {
         let goban = goban.pass();
         assert!(goban.ko().is_none());
     }
+
+    #[test]
+    fn make_and_unmake_restore_the_position() {
+        let goban = goban_from_layout(&["+BW+", "BW+W", "+BW+", "++++"]);
+        let mut made = goban.clone();
+
+        let capture = made.make_move((2, 1), Stone::Black).unwrap();
+        assert!(made.ko().is_some());
+        assert_eq!(made.captures().black, 1);
+        assert_eq!(
+            made.make_move((1, 1), Stone::White).unwrap_err(),
+            GoError::KoViolation
+        );
+        let pass = made.make_pass(Stone::White);
+        assert!(made.ko().is_none());
+
+        made.unmake_move(pass);
+        made.unmake_move(capture);
+        assert_eq!(made, goban);
+        assert_eq!(made.hash(), goban.hash());
+        assert_eq!(made.liberty_count((1, 1)), 1);
+    }
+
+    #[test]
+    fn liberty_count_follows_captures() {
+        let goban = goban_from_layout(&["+B++", "BWB+", "++++", "++++"]);
+        assert_eq!(goban.liberty_count((1, 1)), 1);
+        assert_eq!(goban.liberty_count((0, 0)), 0);
+
+        let goban = goban.play((1, 2), Stone::Black).unwrap();
+        assert_eq!(goban.liberty_count((1, 0)), 3);
+        assert_eq!(goban.liberty_count((1, 2)), 4);
+    }
+
+    #[test]
+    fn torus_corner_has_four_neighbors() {
+        let goban = Goban::with_dimensions(4, 4).with_topology(Topology::Torus);
+        let mut neighbors = goban.neighbors((0, 0)).to_vec();
+        neighbors.sort();
+        assert_eq!(neighbors, vec![(0, 1), (0, 3), (1, 0), (3, 0)]);
+
+        // On a 2x2 torus both directions reach the same point.
+        let tiny = Goban::with_dimensions(2, 2).with_topology(Topology::Torus);
+        assert_eq!(tiny.neighbors((0, 0)).len(), 2);
+    }
+
+    #[test]
+    fn torus_captures_across_edges() {
+        let torus =
+            goban_from_layout(&["BW++", "W+++", "++++", "++++"]).with_topology(Topology::Torus);
+        let torus = torus.play((3, 0), Stone::White).unwrap();
+        assert_eq!(torus.stone_at((0, 0)), Some(Stone::Black));
+        let torus = torus.play((0, 3), Stone::White).unwrap();
+        assert_eq!(torus.stone_at((0, 0)), None);
+        assert_eq!(torus.captures().white, 1);
+    }
+
+    #[test]
+    fn torus_corner_is_not_suicide_with_wrapped_liberties() {
+        let layout = ["+B++", "B+++", "++++", "++++"];
+        assert!(!goban_from_layout(&layout).is_legal_move((0, 0), Stone::White));
+
+        let torus = goban_from_layout(&layout).with_topology(Topology::Torus);
+        assert!(torus.is_legal_move((0, 0), Stone::White));
+    }
 }
@@ -1,46 +1,88 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
 use crate::Point;
 
+/// How Black's handicap stones are put on the board.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HandicapPlacement {
+    /// On the star points from [`handicap_points`], before the first move.
+    #[default]
+    Fixed,
+    /// Anywhere, as Black's first moves of the game.
+    Free,
+}
+
+impl fmt::Display for HandicapPlacement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HandicapPlacement::Fixed => write!(f, "fixed"),
+            HandicapPlacement::Free => write!(f, "free"),
+        }
+    }
+}
+
+impl std::str::FromStr for HandicapPlacement {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fixed" => Ok(HandicapPlacement::Fixed),
+            "free" => Ok(HandicapPlacement::Free),
+            _ => Err(format!("unknown handicap placement: {s}")),
+        }
+    }
+}
+
 /// Returns the maximum number of handicap stones for a given board size.
 pub fn max_handicap(cols: u8, rows: u8) -> u8 {
-    if cols != rows || cols < 7 || cols.is_multiple_of(2) {
+    if !has_hoshi(cols) || !has_hoshi(rows) {
         return 0;
     }
-    if cols >= 13 {
+    if cols >= 13 && rows >= 13 {
         9
     } else {
-        // Boards smaller than 13: only corners + center (no side hoshi)
+        // Either side shorter than 13: only corners + center (no side hoshi)
         5
     }
 }
 
-/// Hoshi-based handicap stone placement for any odd square board ≥ 7.
+/// Whether a board side is long enough, and odd, to carry star points.
+fn has_hoshi(len: u8) -> bool {
+    len >= 7 && !len.is_multiple_of(2)
+}
+
+/// Near, center and far star lines along one side. The near line is 3 from
+/// the edge on sides ≥ 13 and 2 on shorter ones.
+fn hoshi_lines(len: u8) -> (u8, u8, u8) {
+    let off = if len >= 13 { 3 } else { 2 };
+    (off, len / 2, len - 1 - off)
+}
+
+/// Hoshi-based handicap stone placement for any board whose sides are odd and ≥ 7.
 ///
-/// Returns `None` if the board is non-square, even, too small, or count is invalid.
+/// Rectangular boards lay the points out per axis. Returns `None` if either
+/// side is even or too small, or count is invalid.
 pub fn handicap_points(cols: u8, rows: u8, count: u8) -> Option<Vec<Point>> {
-    if cols != rows
-        || cols < 7
-        || cols.is_multiple_of(2)
-        || count < 2
-        || count > max_handicap(cols, rows)
-    {
+    if count < 2 || count > max_handicap(cols, rows) {
         return None;
     }
 
-    // Hoshi offset from edge: 3 for boards ≥ 13, 2 for smaller
-    let off = if cols >= 13 { 3 } else { 2 };
-    let far = cols - 1 - off;
-    let mid = cols / 2;
+    let (left, center_col, right) = hoshi_lines(cols);
+    let (top, center_row, bottom) = hoshi_lines(rows);
 
-    let tl = (off, off);
-    let tr = (far, off);
-    let bl = (off, far);
-    let br = (far, far);
-    let cc = (mid, mid);
+    let tl = (left, top);
+    let tr = (right, top);
+    let bl = (left, bottom);
+    let br = (right, bottom);
+    let cc = (center_col, center_row);
 
-    let ml = (off, mid);
-    let mr = (far, mid);
-    let tc = (mid, off);
-    let bc = (mid, far);
+    let ml = (left, center_row);
+    let mr = (right, center_row);
+    let tc = (center_col, top);
+    let bc = (center_col, bottom);
 
     let pts = match count {
         2 => vec![tr, bl],
@@ -66,7 +108,8 @@ mod tests {
         assert!(handicap_points(4, 4, 2).is_none()); // too small
         assert!(handicap_points(5, 5, 2).is_none()); // too small
         assert!(handicap_points(6, 6, 2).is_none()); // even
-        assert!(handicap_points(9, 13, 2).is_none()); // non-square
+        assert!(handicap_points(9, 12, 2).is_none()); // even side
+        assert!(handicap_points(5, 19, 2).is_none()); // short side
     }
 
     #[test]
@@ -88,7 +131,10 @@ mod tests {
         assert_eq!(max_handicap(19, 19), 9);
         assert_eq!(max_handicap(4, 4), 0);
         assert_eq!(max_handicap(6, 6), 0);
-        assert_eq!(max_handicap(9, 13), 0);
+        assert_eq!(max_handicap(9, 13), 5);
+        assert_eq!(max_handicap(13, 19), 9);
+        assert_eq!(max_handicap(7, 19), 5);
+        assert_eq!(max_handicap(9, 14), 0);
     }
 
     #[test]
@@ -155,4 +201,21 @@ mod tests {
             assert!(pts.contains(p), "7x7: missing hoshi {p:?}");
         }
     }
+
+    #[test]
+    fn rectangular_hoshi_positions() {
+        // cols: off=2, mid=4, far=6; rows: off=3, mid=9, far=15
+        let pts = handicap_points(9, 19, 5).unwrap();
+        let expected = vec![(2, 3), (6, 3), (2, 15), (6, 15), (4, 9)];
+        assert_eq!(pts.len(), expected.len());
+        for p in &expected {
+            assert!(pts.contains(p), "9x19: missing hoshi {p:?}");
+        }
+        assert!(handicap_points(9, 19, 6).is_none());
+
+        // cols: off=3, mid=6, far=9; rows: off=3, mid=9, far=15
+        let pts = handicap_points(13, 19, 8).unwrap();
+        assert!(pts.contains(&(3, 9)) && pts.contains(&(9, 9)));
+        assert!(pts.contains(&(6, 3)) && pts.contains(&(6, 15)));
+    }
 }
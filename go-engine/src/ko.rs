@@ -1,3 +1,6 @@
+use std::collections::HashMap;
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
 
 use crate::stone::Stone;
@@ -8,3 +11,138 @@ pub struct Ko {
     pub pos: (i8, i8),
     pub illegal: Stone,
 }
+
+/// Which repetitions are forbidden beyond the basic ko point.
+///
+/// `Simple` only rejects the immediate recapture recorded in [`Ko`]. The
+/// superko variants reject any move that recreates an earlier board: the
+/// positional rule compares boards alone, the situational rule also requires
+/// the same player to be on move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KoRule {
+    #[default]
+    Simple,
+    PositionalSuperko,
+    SituationalSuperko,
+}
+
+impl fmt::Display for KoRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KoRule::Simple => write!(f, "simple"),
+            KoRule::PositionalSuperko => write!(f, "positional_superko"),
+            KoRule::SituationalSuperko => write!(f, "situational_superko"),
+        }
+    }
+}
+
+impl std::str::FromStr for KoRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "simple" => Ok(KoRule::Simple),
+            "positional_superko" => Ok(KoRule::PositionalSuperko),
+            "situational_superko" => Ok(KoRule::SituationalSuperko),
+            _ => Err(format!("unknown ko rule: {s}")),
+        }
+    }
+}
+
+/// Zobrist hashes of every earlier position in the game, each paired with
+/// the stone that was on move there.
+///
+/// The entries are kept in order so the history can be truncated on undo,
+/// and counted by position so repetition checks are constant time.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(from = "Vec<(u64, Stone)>", into = "Vec<(u64, Stone)>")]
+pub struct PositionHistory {
+    entries: Vec<(u64, Stone)>,
+    counts: HashMap<(u64, Stone), u32>,
+}
+
+impl PositionHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub(crate) fn push(&mut self, hash: u64, to_play: Stone) {
+        self.entries.push((hash, to_play));
+        *self.counts.entry((hash, to_play)).or_default() += 1;
+    }
+
+    pub(crate) fn truncate(&mut self, len: usize) {
+        for entry in self.entries.drain(len.min(self.entries.len())..) {
+            if let Some(count) = self.counts.get_mut(&entry) {
+                *count -= 1;
+                if *count == 0 {
+                    self.counts.remove(&entry);
+                }
+            }
+        }
+    }
+
+    /// Whether reaching `hash` with `to_play` on move repeats an earlier position under `rule`.
+    pub fn repeats(&self, rule: KoRule, hash: u64, to_play: Stone) -> bool {
+        let seen = |stone| self.counts.contains_key(&(hash, stone));
+        match rule {
+            KoRule::Simple => false,
+            KoRule::PositionalSuperko => seen(Stone::Black) || seen(Stone::White),
+            KoRule::SituationalSuperko => seen(to_play),
+        }
+    }
+}
+
+impl From<Vec<(u64, Stone)>> for PositionHistory {
+    fn from(entries: Vec<(u64, Stone)>) -> Self {
+        let mut history = PositionHistory::new();
+        for (hash, to_play) in entries {
+            history.push(hash, to_play);
+        }
+        history
+    }
+}
+
+impl From<PositionHistory> for Vec<(u64, Stone)> {
+    fn from(history: PositionHistory) -> Self {
+        history.entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncation_forgets_only_dropped_positions() {
+        let mut history = PositionHistory::new();
+        history.push(1, Stone::Black);
+        history.push(2, Stone::White);
+        history.push(1, Stone::Black);
+
+        history.truncate(2);
+        assert!(history.repeats(KoRule::SituationalSuperko, 1, Stone::Black));
+        history.truncate(1);
+        assert!(!history.repeats(KoRule::PositionalSuperko, 2, Stone::Black));
+        assert!(history.repeats(KoRule::PositionalSuperko, 1, Stone::White));
+        assert!(!history.repeats(KoRule::SituationalSuperko, 1, Stone::White));
+    }
+
+    #[test]
+    fn serializes_as_ordered_entries() {
+        let history = PositionHistory::from(vec![(7, Stone::White), (3, Stone::Black)]);
+        let json = serde_json::to_string(&history).unwrap();
+        let restored: PositionHistory = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, history);
+        assert!(restored.repeats(KoRule::SituationalSuperko, 7, Stone::White));
+    }
+}
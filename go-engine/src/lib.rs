@@ -1,3 +1,5 @@
+pub mod annotation;
+mod board;
 pub mod engine;
 pub mod error;
 pub mod game_tree;
@@ -6,20 +8,33 @@ pub mod handicap;
 pub mod ko;
 pub mod mcts;
 pub mod replay;
+pub mod ruleset;
+pub mod setup;
 pub mod sgf;
 pub mod stone;
+pub mod symmetry;
+pub mod tactics;
 pub mod territory;
+pub mod topology;
+pub mod tsumego;
 pub mod turn;
+pub mod zobrist;
 
 pub type Point = (u8, u8);
 
+pub use annotation::{Annotation, Glyph, GlyphKind, Markup};
 pub use engine::{Engine, GameState, Stage};
 pub use error::GoError;
 pub use game_tree::{GameTree, NodeId, TreeNode};
 pub use goban::Goban;
-pub use ko::Ko;
+pub use handicap::HandicapPlacement;
+pub use ko::{Ko, KoRule, PositionHistory};
 pub use replay::Replay;
+pub use ruleset::{HandicapCompensation, Ruleset, ScoringMethod};
+pub use setup::Setup;
 pub use stone::Stone;
+pub use symmetry::Symmetry;
+pub use topology::Topology;
 pub use turn::{Move, Turn};
 
 #[cfg(test)]
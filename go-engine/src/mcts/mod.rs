@@ -1,8 +1,16 @@
 use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
 
+use crate::tactics::{self, ReadingLimits};
 use crate::territory::{AreaOptions, calculate_area, estimate_territory, score};
 use crate::{Engine, GoError, Point, Stage, Stone};
 
+#[cfg(feature = "parallel")]
+mod parallel;
+
+#[cfg(feature = "parallel")]
+pub use parallel::{ParallelMctsConfig, parallel_search, parallel_search_with_clock};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum BotMove {
     Play(Point),
@@ -19,22 +27,25 @@ pub struct ActionPrior {
 pub struct Evaluation {
     pub value: f32,
     pub priors: Vec<ActionPrior>,
+    /// Expected owner of each point in board order, from 1 for Black to -1
+    /// for White.
+    pub ownership: Option<Vec<f32>>,
+    /// Expected points ahead for the side to play.
+    pub score_lead: Option<f32>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Transposition key: [`Engine::position_hash`] plus the pass streak, which
+/// decides whether the game is about to end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct PositionKey {
-    board: Box<[i8]>,
-    to_play: Stone,
-    ko: Option<((i8, i8), Stone)>,
+    hash: u64,
     pass_streak: u8,
 }
 
 impl PositionKey {
     pub fn from_engine(engine: &Engine) -> Self {
         Self {
-            board: engine.board().to_vec().into_boxed_slice(),
-            to_play: engine.current_turn_stone(),
-            ko: engine.ko().as_ref().map(|ko| (ko.pos, ko.illegal)),
+            hash: engine.position_hash(),
             pass_streak: pass_streak(engine),
         }
     }
@@ -160,9 +171,14 @@ pub trait MctsEvaluator {
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct MctsConfig {
+    /// Most visits a search adds; a time budget may stop it sooner.
     pub visits: u32,
     pub cpuct: f32,
     pub fpu_reduction: f32,
+    pub time: TimeBudget,
+    /// Stop once the most visited root move can no longer be overtaken by
+    /// the visits left.
+    pub early_stop: bool,
 }
 
 impl Default for MctsConfig {
@@ -171,10 +187,126 @@ impl Default for MctsConfig {
             visits: 64,
             cpuct: 1.5,
             fpu_reduction: 0.2,
+            time: TimeBudget::Unlimited,
+            early_stop: false,
         }
     }
 }
 
+/// Time kept back from a game clock for move transmission and lag.
+const CLOCK_SAFETY_MARGIN: Duration = Duration::from_millis(500);
+/// Fewest moves a game clock's main time is spread over.
+const MIN_MOVES_LEFT: u32 = 10;
+
+/// How much wall-clock time a search may use.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum TimeBudget {
+    #[default]
+    Unlimited,
+    PerMove(Duration),
+    /// Derive the move time from the searching side's clock.
+    Clock(GameClock),
+}
+
+/// The searching side's clock as it stands before the move.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameClock {
+    Fischer {
+        remaining: Duration,
+        increment: Duration,
+    },
+    /// `main` is the main time left and `periods` the periods left.
+    ByoYomi {
+        main: Duration,
+        period: Duration,
+        periods: u32,
+    },
+}
+
+impl TimeBudget {
+    /// Time to spend on the move from `engine`, or `None` when unlimited.
+    /// Main time is spread over a third of the empty points, and a
+    /// byo-yomi period is never overrun.
+    pub fn move_time(self, engine: &Engine) -> Option<Duration> {
+        let clock = match self {
+            Self::Unlimited => return None,
+            Self::PerMove(time) => return Some(time),
+            Self::Clock(clock) => clock,
+        };
+        let empty_points = engine.board().iter().filter(|&&stone| stone == 0).count() as u32;
+        let moves_left = (empty_points / 3).max(MIN_MOVES_LEFT);
+
+        Some(match clock {
+            GameClock::Fischer {
+                remaining,
+                increment,
+            } => (remaining / moves_left + increment)
+                .min(remaining.saturating_sub(CLOCK_SAFETY_MARGIN)),
+            GameClock::ByoYomi {
+                main,
+                period,
+                periods,
+            } => {
+                let safe_period = if periods == 0 {
+                    Duration::ZERO
+                } else {
+                    period.mul_f32(0.9).saturating_sub(CLOCK_SAFETY_MARGIN)
+                };
+                let from_main = (main / moves_left).min(main.saturating_sub(CLOCK_SAFETY_MARGIN));
+                from_main + safe_period
+            }
+        })
+    }
+}
+
+/// Wall-clock time since a search started.
+pub trait SearchClock {
+    fn elapsed(&self) -> Duration;
+}
+
+impl<F: Fn() -> Duration> SearchClock for F {
+    fn elapsed(&self) -> Duration {
+        self()
+    }
+}
+
+/// A [`SearchClock`] on [`Instant`]. `Instant` is unavailable on
+/// `wasm32-unknown-unknown`, where callers pass their own clock.
+#[derive(Debug, Clone, Copy)]
+pub struct Stopwatch(Option<Instant>);
+
+impl Stopwatch {
+    pub fn start() -> Self {
+        Self(Some(Instant::now()))
+    }
+
+    /// A running stopwatch when `budget` limits time, otherwise one that
+    /// never reads the clock and always reports zero, so visit-only
+    /// searches also run where `Instant` is unavailable.
+    pub fn for_budget(budget: TimeBudget) -> Self {
+        match budget {
+            TimeBudget::Unlimited => Self(None),
+            _ => Self::start(),
+        }
+    }
+}
+
+impl SearchClock for Stopwatch {
+    fn elapsed(&self) -> Duration {
+        self.0.map_or(Duration::ZERO, |start| start.elapsed())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    /// The visit budget was used up.
+    Visits,
+    /// The move time ran out.
+    Time,
+    /// The best move could no longer be overtaken.
+    Decided,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct SearchSummary {
     pub best_move: Option<BotMove>,
@@ -183,9 +315,69 @@ pub struct SearchSummary {
     pub root_edges: Vec<EdgeStats>,
     pub principal_variation: Vec<BotMove>,
     pub diagnostics: SearchDiagnostics,
+    /// Zero for searches without a time budget unless the caller passed a clock.
+    pub elapsed: Duration,
+    /// `None` while an external search may still go on.
+    pub stop_reason: Option<StopReason>,
+    /// The evaluations' ownership averaged over the search, from 1 for
+    /// Black to -1 for White; `None` if the evaluator gives none.
+    pub ownership: Option<Vec<f32>>,
+    /// The evaluations' average score lead for the side to play at the root.
+    pub score_lead: Option<f32>,
+}
+
+/// Running sums of the ownership and score lead that evaluations report.
+#[derive(Debug, Clone, Default)]
+struct OutcomeTotals {
+    ownership_sum: Vec<f32>,
+    ownership_count: u32,
+    score_lead_sum: f32,
+    score_lead_count: u32,
+}
+
+impl OutcomeTotals {
+    /// Add an evaluation of a position where `to_play` moves. Ownership
+    /// maps of the wrong size and non-finite values are skipped.
+    fn add(
+        &mut self,
+        root: &Engine,
+        to_play: Stone,
+        ownership: Option<&[f32]>,
+        score_lead: Option<f32>,
+    ) {
+        if let Some(ownership) = ownership
+            && ownership.len() == root.board().len()
+            && ownership.iter().all(|owner| owner.is_finite())
+        {
+            self.ownership_sum.resize(ownership.len(), 0.0);
+            for (sum, owner) in self.ownership_sum.iter_mut().zip(ownership) {
+                *sum += owner.clamp(-1.0, 1.0);
+            }
+            self.ownership_count += 1;
+        }
+        if let Some(lead) = score_lead.filter(|lead| lead.is_finite()) {
+            self.score_lead_sum += if to_play == root.current_turn_stone() {
+                lead
+            } else {
+                -lead
+            };
+            self.score_lead_count += 1;
+        }
+    }
+
+    fn ownership(&self) -> Option<Vec<f32>> {
+        (self.ownership_count > 0).then(|| {
+            let count = self.ownership_count as f32;
+            self.ownership_sum.iter().map(|sum| sum / count).collect()
+        })
+    }
+
+    fn score_lead(&self) -> Option<f32> {
+        (self.score_lead_count > 0).then(|| self.score_lead_sum / self.score_lead_count as f32)
+    }
 }
 
-#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct SearchDiagnostics {
     pub catch_up_visits: u32,
     pub cycle_visits: u32,
@@ -200,6 +392,9 @@ pub struct SearchDiagnostics {
     pub root_visit_entropy: f32,
     pub visited_root_moves: usize,
     pub visited_root_policy_mass: f32,
+    /// Per search thread, the selections that ran into a leaf another
+    /// thread was evaluating. Empty for single-threaded searches.
+    pub thread_collisions: Vec<u32>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -230,6 +425,9 @@ pub struct ExternalEvaluation {
     pub id: u32,
     pub policy_logits: Vec<f32>,
     pub value: f32,
+    /// As in [`Evaluation`].
+    pub ownership: Option<Vec<f32>>,
+    pub score_lead: Option<f32>,
 }
 
 #[derive(Debug, Clone)]
@@ -326,6 +524,199 @@ fn principal_variation(nodes: &[GraphNode], root_id: NodeId, max_len: usize) ->
     variation
 }
 
+fn summarize(
+    nodes: &[GraphNode],
+    root_id: NodeId,
+    diagnostics: SearchDiagnostics,
+    outcomes: &OutcomeTotals,
+    elapsed: Duration,
+    stop_reason: Option<StopReason>,
+) -> SearchSummary {
+    let root_node = &nodes[root_id.0];
+    let mut root_edges = root_node.edges.clone();
+    root_edges.sort_by(|a, b| {
+        b.visits
+            .cmp(&a.visits)
+            .then_with(|| b.prior.total_cmp(&a.prior))
+    });
+
+    SearchSummary {
+        best_move: root_edges.first().map(EdgeStats::action),
+        visits: root_node.visits,
+        root_value: root_node.mean_value(),
+        principal_variation: principal_variation(nodes, root_id, 8),
+        diagnostics: SearchDiagnostics {
+            root_visit_entropy: root_visit_entropy(&root_edges),
+            visited_root_moves: visited_root_moves(&root_edges),
+            visited_root_policy_mass: visited_root_policy_mass(&root_edges),
+            ..diagnostics
+        },
+        root_edges,
+        elapsed,
+        stop_reason,
+        ownership: outcomes.ownership(),
+        score_lead: outcomes.score_lead(),
+    }
+}
+
+/// Whether the most visited root move stays ahead even if every remaining
+/// visit goes to the runner-up.
+fn root_is_decided(edges: &[EdgeStats], remaining_visits: u32) -> bool {
+    let (mut best, mut second) = (0, 0);
+    for edge in edges {
+        if edge.visits > best {
+            second = best;
+            best = edge.visits;
+        } else if edge.visits > second {
+            second = edge.visits;
+        }
+    }
+    best > second.saturating_add(remaining_visits)
+}
+
+/// Visits a search can still make: what is left of the visit budget, or
+/// what the rate so far fits in the move time, whichever is fewer.
+fn remaining_visits(done: u32, budget: u32, elapsed: Duration, move_time: Option<Duration>) -> u32 {
+    let left = budget.saturating_sub(done);
+    let Some(move_time) = move_time else {
+        return left;
+    };
+    let seconds = elapsed.as_secs_f64();
+    if seconds <= 0.0 {
+        return left;
+    }
+    let projected = done as f64 / seconds * move_time.saturating_sub(elapsed).as_secs_f64();
+    left.min(projected.ceil() as u32)
+}
+
+/// Play `action` on `root` and keep only the part of the graph reachable
+/// from the new position, renumbered so the new root is `NodeId(0)`. A
+/// position the graph never reached starts a fresh one. `root` is untouched
+/// if the action is illegal.
+fn advance_graph(
+    root: &mut Engine,
+    nodes: &mut Vec<GraphNode>,
+    node_by_key: &mut HashMap<PositionKey, NodeId>,
+    action: BotMove,
+) -> Result<NodeId, GoError> {
+    let mut next = root.clone();
+    apply_action(&mut next, action)?;
+    let key = PositionKey::from_engine(&next);
+    *root = next;
+
+    let Some(&root_id) = node_by_key.get(&key) else {
+        nodes.clear();
+        nodes.push(GraphNode::new(key));
+        node_by_key.clear();
+        node_by_key.insert(key, NodeId(0));
+        return Ok(NodeId(0));
+    };
+
+    let mut remap: Vec<Option<NodeId>> = vec![None; nodes.len()];
+    let mut order = vec![root_id];
+    remap[root_id.0] = Some(NodeId(0));
+    let mut next_index = 0;
+    while let Some(&node_id) = order.get(next_index) {
+        for edge in &nodes[node_id.0].edges {
+            if remap[edge.child.0].is_none() {
+                remap[edge.child.0] = Some(NodeId(order.len()));
+                order.push(edge.child);
+            }
+        }
+        next_index += 1;
+    }
+
+    let mut old: Vec<Option<GraphNode>> = std::mem::take(nodes).into_iter().map(Some).collect();
+    *nodes = order
+        .iter()
+        .map(|id| {
+            let mut node = old[id.0].take().expect("each node is kept once");
+            for edge in &mut node.edges {
+                edge.child = remap[edge.child.0].expect("children of kept nodes are kept");
+            }
+            node
+        })
+        .collect();
+    node_by_key.clear();
+    node_by_key.extend(
+        nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (node.key, NodeId(index))),
+    );
+    Ok(NodeId(0))
+}
+
+/// A search graph kept between moves. Each [`SearchTree::search`] adds
+/// `config.visits` visits to what earlier searches built, and
+/// [`SearchTree::advance`] moves the root to the position after a played
+/// move, keeping the statistics below it.
+#[derive(Debug, Clone)]
+pub struct SearchTree {
+    root: Engine,
+    nodes: Vec<GraphNode>,
+    node_by_key: HashMap<PositionKey, NodeId>,
+}
+
+impl SearchTree {
+    pub fn new(root: Engine) -> Self {
+        Self {
+            root,
+            nodes: Vec::new(),
+            node_by_key: HashMap::new(),
+        }
+    }
+
+    pub fn root(&self) -> &Engine {
+        &self.root
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Visits already below the root, which the next search builds on.
+    pub fn root_visits(&self) -> u32 {
+        self.node_by_key
+            .get(&PositionKey::from_engine(&self.root))
+            .map_or(0, |id| self.nodes[id.0].visits())
+    }
+
+    pub fn search<E: MctsEvaluator>(
+        &mut self,
+        config: MctsConfig,
+        evaluator: &mut E,
+    ) -> SearchSummary {
+        self.search_with_clock(config, evaluator, &Stopwatch::for_budget(config.time))
+    }
+
+    pub fn search_with_clock<E: MctsEvaluator, C: SearchClock>(
+        &mut self,
+        config: MctsConfig,
+        evaluator: &mut E,
+        clock: &C,
+    ) -> SearchSummary {
+        let mut search = GraphSearch::new(config, evaluator);
+        search.nodes = std::mem::take(&mut self.nodes);
+        search.node_by_key = std::mem::take(&mut self.node_by_key);
+        let summary = search.search(&self.root, clock);
+        self.nodes = search.nodes;
+        self.node_by_key = search.node_by_key;
+        summary
+    }
+
+    /// Move the root past `action`, played by either side.
+    pub fn advance(&mut self, action: BotMove) -> Result<(), GoError> {
+        advance_graph(
+            &mut self.root,
+            &mut self.nodes,
+            &mut self.node_by_key,
+            action,
+        )?;
+        Ok(())
+    }
+}
+
 #[derive(Debug)]
 struct GraphSearch<'a, E> {
     config: MctsConfig,
@@ -333,6 +724,7 @@ struct GraphSearch<'a, E> {
     nodes: Vec<GraphNode>,
     node_by_key: HashMap<PositionKey, NodeId>,
     diagnostics: SearchDiagnostics,
+    outcomes: OutcomeTotals,
 }
 
 impl<'a, E: MctsEvaluator> GraphSearch<'a, E> {
@@ -343,42 +735,51 @@ impl<'a, E: MctsEvaluator> GraphSearch<'a, E> {
             nodes: Vec::new(),
             node_by_key: HashMap::new(),
             diagnostics: SearchDiagnostics::default(),
+            outcomes: OutcomeTotals::default(),
         }
     }
 
-    fn search(&mut self, root: &Engine) -> SearchSummary {
+    fn search<C: SearchClock>(&mut self, root: &Engine, clock: &C) -> SearchSummary {
         let root_id = self.intern_node(root);
+        let move_time = self.config.time.move_time(root);
+        let mut done = 0;
 
-        for _ in 0..self.config.visits {
-            self.visit(root_id, root.clone());
-        }
+        let stop_reason = loop {
+            if done >= self.config.visits {
+                break StopReason::Visits;
+            }
+            // The first visit always runs so there is a move to play.
+            if done > 0 {
+                let elapsed = clock.elapsed();
+                if move_time.is_some_and(|time| elapsed >= time) {
+                    break StopReason::Time;
+                }
+                if self.config.early_stop
+                    && root_is_decided(
+                        &self.nodes[root_id.0].edges,
+                        remaining_visits(done, self.config.visits, elapsed, move_time),
+                    )
+                {
+                    break StopReason::Decided;
+                }
+            }
+            self.visit(root_id, root);
+            done += 1;
+        };
 
-        let root_node = &self.nodes[root_id.0];
-        let mut root_edges = root_node.edges.clone();
-        root_edges.sort_by(|a, b| {
-            b.visits
-                .cmp(&a.visits)
-                .then_with(|| b.prior.total_cmp(&a.prior))
-        });
-
-        SearchSummary {
-            best_move: root_edges.first().map(EdgeStats::action),
-            visits: root_node.visits,
-            root_value: root_node.mean_value(),
-            principal_variation: principal_variation(&self.nodes, root_id, 8),
-            diagnostics: SearchDiagnostics {
-                root_visit_entropy: root_visit_entropy(&root_edges),
-                visited_root_moves: visited_root_moves(&root_edges),
-                visited_root_policy_mass: visited_root_policy_mass(&root_edges),
-                ..self.diagnostics
-            },
-            root_edges,
-        }
+        summarize(
+            &self.nodes,
+            root_id,
+            self.diagnostics.clone(),
+            &self.outcomes,
+            clock.elapsed(),
+            Some(stop_reason),
+        )
     }
 
-    fn visit(&mut self, root_id: NodeId, root: Engine) {
+    fn visit(&mut self, root_id: NodeId, root: &Engine) {
         let mut node_id = root_id;
-        let mut engine = root;
+        let mut engine = root.clone();
         let mut path: Vec<(NodeId, usize)> = Vec::new();
         let mut active_nodes = HashSet::from([root_id]);
 
@@ -386,6 +787,12 @@ impl<'a, E: MctsEvaluator> GraphSearch<'a, E> {
             if !self.nodes[node_id.0].is_evaluated() {
                 let to_play = engine.current_turn_stone();
                 let evaluation = self.evaluator.evaluate(&engine, to_play);
+                self.outcomes.add(
+                    root,
+                    to_play,
+                    evaluation.ownership.as_deref(),
+                    evaluation.score_lead,
+                );
                 self.nodes[node_id.0].set_raw_value(evaluation.value);
                 self.expand_node(node_id, &engine, evaluation.priors);
                 recompute_node(&mut self.nodes, node_id);
@@ -517,7 +924,7 @@ impl<'a, E: MctsEvaluator> GraphSearch<'a, E> {
         }
 
         let id = NodeId(self.nodes.len());
-        self.nodes.push(GraphNode::new(key.clone()));
+        self.nodes.push(GraphNode::new(key));
         self.node_by_key.insert(key, id);
         id
     }
@@ -534,16 +941,20 @@ pub struct ExternalMctsSearch {
     pending_nodes: HashSet<NodeId>,
     next_eval_id: u32,
     completed_visits: u32,
+    move_time: Option<Duration>,
+    elapsed: Duration,
     diagnostics: SearchDiagnostics,
+    outcomes: OutcomeTotals,
 }
 
 impl ExternalMctsSearch {
     pub fn new(root: Engine, config: ExternalMctsConfig) -> Self {
+        let move_time = config.search.time.move_time(&root);
         let root_key = PositionKey::from_engine(&root);
         let root_id = NodeId(0);
         let mut node_by_key = HashMap::new();
 
-        node_by_key.insert(root_key.clone(), root_id);
+        node_by_key.insert(root_key, root_id);
 
         Self {
             config,
@@ -555,7 +966,10 @@ impl ExternalMctsSearch {
             pending_nodes: HashSet::new(),
             next_eval_id: 1,
             completed_visits: 0,
+            move_time,
+            elapsed: Duration::ZERO,
             diagnostics: SearchDiagnostics::default(),
+            outcomes: OutcomeTotals::default(),
         }
     }
 
@@ -567,8 +981,44 @@ impl ExternalMctsSearch {
         self.pending.len()
     }
 
+    /// Report the time the search has taken. The caller keeps the clock,
+    /// since evaluations run outside the search.
+    pub fn set_elapsed(&mut self, elapsed: Duration) {
+        self.elapsed = elapsed;
+    }
+
+    /// Replace the time budget, for example with the clock as it stands
+    /// after [`ExternalMctsSearch::advance`].
+    pub fn set_time_budget(&mut self, time: TimeBudget) {
+        self.config.search.time = time;
+        self.move_time = time.move_time(&self.root);
+    }
+
+    /// Why the search should stop, or `None` while it may go on.
+    /// Outstanding evaluations can still be applied after it stops.
+    pub fn stop_reason(&self) -> Option<StopReason> {
+        let search = &self.config.search;
+        if self.completed_visits >= search.visits {
+            return Some(StopReason::Visits);
+        }
+        if self.completed_visits == 0 {
+            return None;
+        }
+        if self.move_time.is_some_and(|time| self.elapsed >= time) {
+            return Some(StopReason::Time);
+        }
+        let remaining = remaining_visits(
+            self.completed_visits,
+            search.visits,
+            self.elapsed,
+            self.move_time,
+        );
+        (search.early_stop && root_is_decided(&self.nodes[self.root_id.0].edges, remaining))
+            .then_some(StopReason::Decided)
+    }
+
     pub fn is_complete(&self) -> bool {
-        self.completed_visits >= self.config.search.visits && self.pending.is_empty()
+        self.stop_reason().is_some() && self.pending.is_empty()
     }
 
     pub fn next_evaluations(&mut self, max_batch_size: usize) -> Vec<PendingEvaluation> {
@@ -577,6 +1027,7 @@ impl ExternalMctsSearch {
 
         while evaluations.len() < batch_size
             && self.completed_visits + (self.pending.len() as u32) < self.config.search.visits
+            && self.stop_reason().is_none()
         {
             let Some(evaluation) = self.prepare_evaluation() else {
                 break;
@@ -614,6 +1065,12 @@ impl ExternalMctsSearch {
                 &mut self.diagnostics,
             );
 
+            self.outcomes.add(
+                &self.root,
+                to_play,
+                evaluation.ownership.as_deref(),
+                evaluation.score_lead,
+            );
             self.nodes[pending.node_id.0].set_raw_value(value);
             self.expand_node(pending.node_id, &pending.engine, priors);
             recompute_node(&mut self.nodes, pending.node_id);
@@ -623,27 +1080,35 @@ impl ExternalMctsSearch {
     }
 
     pub fn summary(&self) -> SearchSummary {
-        let root_node = &self.nodes[self.root_id.0];
-        let mut root_edges = root_node.edges.clone();
-        root_edges.sort_by(|a, b| {
-            b.visits
-                .cmp(&a.visits)
-                .then_with(|| b.prior.total_cmp(&a.prior))
-        });
-
-        SearchSummary {
-            best_move: root_edges.first().map(EdgeStats::action),
-            visits: root_node.visits,
-            root_value: root_node.mean_value(),
-            principal_variation: principal_variation(&self.nodes, self.root_id, 8),
-            diagnostics: SearchDiagnostics {
-                root_visit_entropy: root_visit_entropy(&root_edges),
-                visited_root_moves: visited_root_moves(&root_edges),
-                visited_root_policy_mass: visited_root_policy_mass(&root_edges),
-                ..self.diagnostics
-            },
-            root_edges,
-        }
+        summarize(
+            &self.nodes,
+            self.root_id,
+            self.diagnostics.clone(),
+            &self.outcomes,
+            self.elapsed,
+            self.stop_reason(),
+        )
+    }
+
+    /// Move the root past `action`, played by either side, keeping the
+    /// statistics below it. Outstanding evaluations are dropped and their
+    /// results ignored, and the visit and time budgets start over for the
+    /// new root.
+    pub fn advance(&mut self, action: BotMove) -> Result<(), GoError> {
+        self.root_id = advance_graph(
+            &mut self.root,
+            &mut self.nodes,
+            &mut self.node_by_key,
+            action,
+        )?;
+        self.pending.clear();
+        self.pending_nodes.clear();
+        self.completed_visits = 0;
+        self.move_time = self.config.search.time.move_time(&self.root);
+        self.elapsed = Duration::ZERO;
+        self.diagnostics = SearchDiagnostics::default();
+        self.outcomes = OutcomeTotals::default();
+        Ok(())
     }
 
     fn prepare_evaluation(&mut self) -> Option<PendingEvaluation> {
@@ -828,7 +1293,7 @@ impl ExternalMctsSearch {
         }
 
         let id = NodeId(self.nodes.len());
-        self.nodes.push(GraphNode::new(key.clone()));
+        self.nodes.push(GraphNode::new(key));
         self.node_by_key.insert(key, id);
         id
     }
@@ -839,7 +1304,23 @@ pub fn search<E: MctsEvaluator>(
     config: MctsConfig,
     evaluator: &mut E,
 ) -> SearchSummary {
-    GraphSearch::new(config, evaluator).search(engine)
+    search_with_clock(
+        engine,
+        config,
+        evaluator,
+        &Stopwatch::for_budget(config.time),
+    )
+}
+
+/// [`search`] timed by `clock`, for targets without [`Instant`] or for
+/// callers whose move time started before the search.
+pub fn search_with_clock<E: MctsEvaluator, C: SearchClock>(
+    engine: &Engine,
+    config: MctsConfig,
+    evaluator: &mut E,
+    clock: &C,
+) -> SearchSummary {
+    GraphSearch::new(config, evaluator).search(engine, clock)
 }
 
 pub fn genmove<E: MctsEvaluator>(
@@ -889,11 +1370,13 @@ impl RandomRolloutEvaluator {
 impl MctsEvaluator for RandomRolloutEvaluator {
     fn evaluate(&mut self, engine: &Engine, to_play: Stone) -> Evaluation {
         let actions = legal_actions(engine, to_play);
-        let value = rollout_value(engine, to_play, self.config.limit, self.komi, &mut self.rng);
+        let score = rollout_score(engine, to_play, self.config.limit, self.komi, &mut self.rng);
 
         Evaluation {
-            value,
+            value: score.value,
             priors: baseline_rollout_policy_priors(engine, actions, self.config.max_policy_actions),
+            ownership: Some(score.ownership),
+            score_lead: Some(score.lead as f32),
         }
     }
 }
@@ -941,6 +1424,8 @@ impl MctsEvaluator for RootPolicyRolloutEvaluator {
             return Evaluation {
                 value: self.root_value,
                 priors: self.root_priors.clone(),
+                ownership: None,
+                score_lead: None,
             };
         }
 
@@ -1060,9 +1545,13 @@ fn baseline_rollout_policy_priors(
         return Vec::new();
     }
 
+    let tactics = tactical_bonuses(engine);
     let mut scored: Vec<(BotMove, f32)> = actions
         .into_iter()
-        .map(|action| (action, baseline_rollout_policy_score(engine, action)))
+        .map(|action| {
+            let score = baseline_rollout_policy_score(engine, action, &tactics);
+            (action, score)
+        })
         .collect();
     scored.sort_by(|(a_action, a_score), (b_action, b_score)| {
         b_score
@@ -1100,7 +1589,7 @@ pub fn apply_action(engine: &mut Engine, action: BotMove) -> Result<Stage, GoErr
 pub fn legal_actions(engine: &Engine, to_play: Stone) -> Vec<BotMove> {
     if !matches!(
         engine.stage(),
-        Stage::Unstarted | Stage::BlackToPlay | Stage::WhiteToPlay
+        Stage::Unstarted | Stage::HandicapPlacement | Stage::BlackToPlay | Stage::WhiteToPlay
     ) || engine.current_turn_stone() != to_play
     {
         return Vec::new();
@@ -1117,18 +1606,75 @@ pub fn legal_actions(engine: &Engine, to_play: Stone) -> Vec<BotMove> {
         }
     }
 
-    actions.push(BotMove::Pass);
+    if engine.pending_handicap_stones() == 0 {
+        actions.push(BotMove::Pass);
+    }
     actions
 }
 
-fn baseline_rollout_policy_score(engine: &Engine, action: BotMove) -> f32 {
+fn baseline_rollout_policy_score(
+    engine: &Engine,
+    action: BotMove,
+    tactics: &HashMap<Point, f32>,
+) -> f32 {
     match action {
         BotMove::Pass => 0.01,
-        BotMove::Play(point) => point_policy_score(engine, point),
+        BotMove::Play(point) => {
+            let bonus = tactics.get(&point).copied().unwrap_or(0.0);
+            (point_policy_score(engine, point) + bonus).max(0.01)
+        }
     }
 }
 
+/// Priors are recomputed at every expansion, so these reads stay short.
+const PRIOR_READING: ReadingLimits = ReadingLimits {
+    max_nodes: 300,
+    max_depth: 60,
+    max_loose_moves: 1,
+};
+
+/// Prior adjustments from short tactical reads around chains low on
+/// liberties. Random playouts misjudge ladders, so captures, working ladders
+/// and nets, and escapes from atari are boosted, while running with a chain
+/// that is caught anyway is discouraged.
+fn tactical_bonuses(engine: &Engine) -> HashMap<Point, f32> {
+    let goban = engine.goban();
+    let to_play = engine.current_turn_stone();
+    let mut bonuses = HashMap::new();
+    let mut visited = vec![false; goban.board().len()];
+
+    for row in 0..goban.rows() {
+        for col in 0..goban.cols() {
+            let chain = goban.chain_from((col, row), &mut visited);
+            let Some(&point) = chain.first() else {
+                continue;
+            };
+            let liberties = goban.chain_liberties(&chain);
+            let own = goban.stone_at(point) == Some(to_play);
+            let (target, bonus) = match (own, liberties.len()) {
+                (false, 1) => (liberties[0], 3.0),
+                (false, 2) => match tactics::net_capture(goban, point, PRIOR_READING) {
+                    Some(attack) => (attack, 2.0),
+                    None => continue,
+                },
+                (true, 1) if tactics::can_escape(goban, point, PRIOR_READING) => {
+                    (liberties[0], 1.5)
+                }
+                (true, 1) => (liberties[0], -2.0),
+                _ => continue,
+            };
+            *bonuses.entry(target).or_insert(0.0) += bonus;
+        }
+    }
+
+    bonuses
+}
+
 fn point_policy_score(engine: &Engine, point: Point) -> f32 {
+    // A torus has no edges or star points, so only local shape matters.
+    if !engine.topology().is_planar() {
+        return 0.8 + neighbor_shape_bonus(engine, point);
+    }
     let (col, row) = point;
     let cols = engine.cols();
     let rows = engine.rows();
@@ -1180,7 +1726,7 @@ fn neighbor_shape_bonus(engine: &Engine, point: Point) -> f32 {
     let mut own_neighbors = 0;
     let mut opponent_neighbors = 0;
 
-    for neighbor in orthogonal_neighbors(engine, point) {
+    for neighbor in engine.goban().neighbors(point) {
         match engine.stone_at(neighbor) {
             Some(stone) if stone == to_play => own_neighbors += 1,
             Some(_) => opponent_neighbors += 1,
@@ -1191,21 +1737,6 @@ fn neighbor_shape_bonus(engine: &Engine, point: Point) -> f32 {
     0.35 * own_neighbors as f32 + 0.2 * opponent_neighbors as f32
 }
 
-fn orthogonal_neighbors(engine: &Engine, (col, row): Point) -> impl Iterator<Item = Point> + '_ {
-    [
-        col.checked_sub(1).map(|next_col| (next_col, row)),
-        row.checked_sub(1).map(|next_row| (col, next_row)),
-        col.checked_add(1)
-            .filter(|next_col| *next_col < engine.cols())
-            .map(|next_col| (next_col, row)),
-        row.checked_add(1)
-            .filter(|next_row| *next_row < engine.rows())
-            .map(|next_row| (col, next_row)),
-    ]
-    .into_iter()
-    .flatten()
-}
-
 fn bot_move_order(action: BotMove) -> (u8, u8, u8) {
     match action {
         BotMove::Play((col, row)) => (0, row, col),
@@ -1220,13 +1751,13 @@ fn policy_logit_index(action: BotMove, cols: u8, rows: u8) -> usize {
     }
 }
 
-fn rollout_value(
+fn rollout_score(
     engine: &Engine,
     to_play: Stone,
     limit: u32,
     komi: f64,
     rng: &mut DeterministicRng,
-) -> f32 {
+) -> PositionScore {
     let mut rollout = engine.clone();
 
     for _ in 0..limit {
@@ -1246,33 +1777,50 @@ fn rollout_value(
         }
     }
 
-    score_value(&rollout, to_play, komi)
+    score_position(&rollout, to_play, komi)
 }
 
-fn score_value(engine: &Engine, to_play: Stone, komi: f64) -> f32 {
-    let dead_stones = HashSet::new();
-    score_value_with_dead_stones(engine, to_play, komi, &dead_stones)
+/// A scored position: its value and the outcome behind it.
+struct PositionScore {
+    value: f32,
+    /// Territory and stones, 1 for Black and -1 for White.
+    ownership: Vec<f32>,
+    /// Points ahead for `to_play`.
+    lead: f64,
 }
 
-fn score_value_with_dead_stones(
-    engine: &Engine,
-    to_play: Stone,
-    komi: f64,
-    dead_stones: &HashSet<Point>,
-) -> f32 {
-    let ownership = estimate_territory(engine.goban(), dead_stones);
-    let score = score(engine.goban(), &ownership, dead_stones, komi);
+fn score_position(engine: &Engine, to_play: Stone, komi: f64) -> PositionScore {
+    let dead_stones = HashSet::new();
+    let territory = estimate_territory(engine.goban(), &dead_stones);
+    let score = score(
+        engine.goban(),
+        &territory,
+        &dead_stones,
+        komi,
+        engine.handicap(),
+        engine.ruleset(),
+    );
     let diff = score.black_total() - score.white_total();
     let perspective_diff = match to_play {
         Stone::Black => diff,
         Stone::White => -diff,
     };
+    let value = if engine.stage().is_play() {
+        score_diff_to_score_utility(perspective_diff, engine.cols(), engine.rows())
+    } else {
+        score_diff_to_result_value(perspective_diff)
+    };
+    let ownership = territory
+        .iter()
+        .zip(engine.board())
+        .map(|(&owner, &stone)| f32::from(if owner != 0 { owner } else { stone.signum() }))
+        .collect();
 
-    if !engine.stage().is_play() {
-        return score_diff_to_result_value(perspective_diff);
+    PositionScore {
+        value,
+        ownership,
+        lead: perspective_diff,
     }
-
-    score_diff_to_score_utility(perspective_diff, engine.cols(), engine.rows())
 }
 
 fn score_value_with_pass_alive_area(engine: &Engine, to_play: Stone, komi: f64) -> (f32, u32) {
@@ -1284,7 +1832,7 @@ fn score_value_with_pass_alive_area(engine: &Engine, to_play: Stone, komi: f64)
             unsafe_big_territories: true,
         },
     );
-    let mut diff = -komi;
+    let mut diff = -komi - engine.ruleset().handicap_bonus(engine.handicap());
     let mut dead_points = 0;
 
     for (idx, &owner) in area.iter().enumerate() {
@@ -1403,6 +1951,8 @@ mod tests {
             Evaluation {
                 value: self.value,
                 priors,
+                ownership: None,
+                score_lead: None,
             }
         }
     }
@@ -1554,6 +2104,7 @@ mod tests {
                     visits: 2,
                     cpuct: 1.5,
                     fpu_reduction: 0.2,
+                    ..MctsConfig::default()
                 },
                 max_policy_actions: Some(2),
                 dead_stone_eval: DeadStoneEvalConfig::Off,
@@ -1568,6 +2119,8 @@ mod tests {
             id: root[0].id,
             policy_logits: root_logits,
             value: 0.25,
+            ownership: None,
+            score_lead: None,
         }]);
 
         let child = search.next_evaluations(4);
@@ -1576,6 +2129,8 @@ mod tests {
             id: child[0].id,
             policy_logits: vec![0.0; 10],
             value: -0.5,
+            ownership: None,
+            score_lead: None,
         }]);
 
         let summary = search.summary();
@@ -1596,6 +2151,7 @@ mod tests {
                     visits: 1,
                     cpuct: 1.5,
                     fpu_reduction: 0.2,
+                    ..MctsConfig::default()
                 },
                 max_policy_actions: Some(2),
                 dead_stone_eval: DeadStoneEvalConfig::Blend {
@@ -1612,6 +2168,8 @@ mod tests {
             id: root[0].id,
             policy_logits: vec![0.0; 10],
             value: 1.0,
+            ownership: None,
+            score_lead: None,
         }]);
 
         let summary = search.summary();
@@ -1633,6 +2191,7 @@ mod tests {
                     visits: 4,
                     cpuct: 1.5,
                     fpu_reduction: 0.2,
+                    ..MctsConfig::default()
                 },
                 max_policy_actions: Some(4),
                 dead_stone_eval: DeadStoneEvalConfig::Off,
@@ -1644,6 +2203,8 @@ mod tests {
             id: root[0].id,
             policy_logits: vec![0.0; 10],
             value: 0.0,
+            ownership: None,
+            score_lead: None,
         }]);
 
         let batch = search.next_evaluations(3);
@@ -1656,6 +2217,233 @@ mod tests {
         assert_eq!(unique_positions.len(), 3);
     }
 
+    #[test]
+    fn external_mcts_advance_keeps_child_stats() {
+        let engine = Engine::new(3, 3);
+        let mut search = ExternalMctsSearch::new(
+            engine,
+            ExternalMctsConfig {
+                search: MctsConfig {
+                    visits: 2,
+                    cpuct: 1.5,
+                    fpu_reduction: 0.2,
+                    ..MctsConfig::default()
+                },
+                max_policy_actions: Some(2),
+                dead_stone_eval: DeadStoneEvalConfig::Off,
+            },
+        );
+        let mut root_logits = vec![0.0; 10];
+        root_logits[policy_logit_index(BotMove::Play((2, 2)), 3, 3)] = 8.0;
+        for logits in [root_logits, vec![0.0; 10]] {
+            let batch = search.next_evaluations(1);
+            search.apply_evaluations(vec![ExternalEvaluation {
+                id: batch[0].id,
+                policy_logits: logits,
+                value: 0.0,
+                ownership: None,
+                score_lead: None,
+            }]);
+        }
+        assert!(search.is_complete());
+
+        search
+            .advance(BotMove::Play((2, 2)))
+            .expect("advance by a legal move");
+
+        assert_eq!(search.summary().visits, 1);
+        assert_eq!(search.completed_visits(), 0);
+        assert!(!search.is_complete());
+        let next = search.next_evaluations(1);
+        assert_eq!(next.len(), 1);
+        assert_eq!(next[0].engine.moves().len(), 2);
+        assert!(search.advance(BotMove::Play((2, 2))).is_err());
+    }
+
+    #[test]
+    fn search_tree_reuses_the_played_subtree() {
+        let mut evaluator = StaticEvaluator {
+            value: 0.0,
+            priors: HashMap::from([(BotMove::Play((2, 2)), 10.0)]),
+        };
+        let config = MctsConfig {
+            visits: 24,
+            ..MctsConfig::default()
+        };
+        let mut tree = SearchTree::new(Engine::new(3, 3));
+        let summary = tree.search(config, &mut evaluator);
+        let best = summary.root_edges[0].clone();
+        let nodes_before = tree.node_count();
+
+        tree.advance(best.action())
+            .expect("advance by the best move");
+        let kept = tree.root_visits();
+        assert_eq!(kept, best.visits());
+        assert!(kept > 0);
+        assert!(tree.node_count() < nodes_before);
+
+        let summary = tree.search(
+            MctsConfig {
+                visits: 8,
+                ..config
+            },
+            &mut evaluator,
+        );
+        assert_eq!(summary.visits, kept + 8);
+
+        // A reply the search never expanded starts a fresh graph.
+        let mut fresh = SearchTree::new(Engine::new(3, 3));
+        fresh.advance(BotMove::Pass).expect("pass is legal");
+        assert_eq!(fresh.root_visits(), 0);
+        assert_eq!(fresh.root().moves().len(), 1);
+    }
+
+    #[test]
+    fn time_budget_spreads_clock_time_over_the_game() {
+        let engine = Engine::new(9, 9);
+        let secs = Duration::from_secs_f32;
+        let fischer = |remaining, increment| {
+            TimeBudget::Clock(GameClock::Fischer {
+                remaining: secs(remaining),
+                increment: secs(increment),
+            })
+        };
+        let byo_yomi = |main, period, periods| {
+            TimeBudget::Clock(GameClock::ByoYomi {
+                main: secs(main),
+                period: secs(period),
+                periods,
+            })
+        };
+
+        assert_eq!(TimeBudget::Unlimited.move_time(&engine), None);
+        assert_eq!(
+            TimeBudget::PerMove(secs(2.0)).move_time(&engine),
+            Some(secs(2.0))
+        );
+        // 81 empty points: main time is spread over 27 moves.
+        assert_eq!(fischer(270.0, 5.0).move_time(&engine), Some(secs(15.0)));
+        assert_eq!(fischer(1.0, 10.0).move_time(&engine), Some(secs(0.5)));
+        assert_eq!(byo_yomi(54.0, 10.0, 1).move_time(&engine), Some(secs(10.5)));
+        assert_eq!(byo_yomi(0.0, 30.0, 3).move_time(&engine), Some(secs(26.5)));
+        assert_eq!(
+            byo_yomi(0.0, 30.0, 0).move_time(&engine),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn search_stops_when_the_move_time_runs_out() {
+        let mut evaluator = StaticEvaluator {
+            value: 0.0,
+            priors: HashMap::new(),
+        };
+        // Each reading of the clock takes a second.
+        let now = std::cell::Cell::new(Duration::ZERO);
+        let clock = || {
+            now.set(now.get() + Duration::from_secs(1));
+            now.get()
+        };
+        let summary = search_with_clock(
+            &Engine::new(3, 3),
+            MctsConfig {
+                visits: 100,
+                time: TimeBudget::PerMove(Duration::from_secs(3)),
+                ..MctsConfig::default()
+            },
+            &mut evaluator,
+            &clock,
+        );
+
+        assert_eq!(summary.stop_reason, Some(StopReason::Time));
+        assert_eq!(summary.visits, 3);
+        assert_eq!(summary.elapsed, Duration::from_secs(4));
+    }
+
+    #[test]
+    fn visit_only_searches_leave_the_clock_unread() {
+        assert_eq!(
+            Stopwatch::for_budget(TimeBudget::Unlimited).elapsed(),
+            Duration::ZERO
+        );
+        let timed = Stopwatch::for_budget(TimeBudget::PerMove(Duration::from_secs(1)));
+        std::thread::sleep(Duration::from_millis(2));
+        assert!(timed.elapsed() > Duration::ZERO);
+    }
+
+    #[test]
+    fn early_stop_ends_a_decided_search() {
+        let mut evaluator = StaticEvaluator {
+            value: 0.0,
+            priors: HashMap::from([(BotMove::Play((1, 1)), 10.0)]),
+        };
+        let config = MctsConfig {
+            visits: 200,
+            ..MctsConfig::default()
+        };
+        let engine = Engine::new(3, 3);
+
+        let full = search(&engine, config, &mut evaluator);
+        assert_eq!(full.stop_reason, Some(StopReason::Visits));
+        assert_eq!(full.visits, 200);
+
+        let early = search(
+            &engine,
+            MctsConfig {
+                early_stop: true,
+                ..config
+            },
+            &mut evaluator,
+        );
+        assert_eq!(early.stop_reason, Some(StopReason::Decided));
+        assert!(early.visits < 200);
+        assert_eq!(early.best_move, full.best_move);
+        let edges = &early.root_edges;
+        assert!(edges[0].visits() > edges[1].visits() + (200 - early.visits));
+    }
+
+    #[test]
+    fn external_mcts_stops_at_the_move_time() {
+        let mut search = ExternalMctsSearch::new(
+            Engine::new(3, 3),
+            ExternalMctsConfig {
+                search: MctsConfig {
+                    visits: 100,
+                    time: TimeBudget::PerMove(Duration::from_millis(50)),
+                    ..MctsConfig::default()
+                },
+                max_policy_actions: None,
+                dead_stone_eval: DeadStoneEvalConfig::Off,
+            },
+        );
+        search.set_elapsed(Duration::from_millis(80));
+        // The root is evaluated even when the time is already gone.
+        let batch = search.next_evaluations(4);
+        assert_eq!(batch.len(), 1);
+        assert_eq!(search.stop_reason(), None);
+
+        search.apply_evaluations(
+            batch
+                .iter()
+                .map(|pending| ExternalEvaluation {
+                    id: pending.id,
+                    policy_logits: vec![0.0; 10],
+                    value: 0.0,
+                    ownership: None,
+                    score_lead: None,
+                })
+                .collect(),
+        );
+        assert_eq!(search.stop_reason(), Some(StopReason::Time));
+        assert!(search.is_complete());
+        assert!(search.next_evaluations(4).is_empty());
+        assert_eq!(search.summary().elapsed, Duration::from_millis(80));
+
+        search.advance(BotMove::Pass).expect("pass is legal");
+        assert_eq!(search.stop_reason(), None);
+        assert_eq!(search.next_evaluations(1).len(), 1);
+    }
+
     #[test]
     fn apply_action_uses_current_turn_stone() {
         let mut engine = Engine::new(3, 3);
@@ -1666,6 +2454,105 @@ mod tests {
         assert_eq!(engine.current_turn_stone(), Stone::White);
     }
 
+    #[test]
+    fn random_rollout_evaluator_reports_ownership_and_score_lead() {
+        let mut engine = Engine::new(3, 3);
+        play(&mut engine, Stone::Black, (1, 0));
+        engine.try_pass(Stone::White).expect("white pass");
+        play(&mut engine, Stone::Black, (1, 1));
+        engine.try_pass(Stone::White).expect("white pass");
+        play(&mut engine, Stone::Black, (1, 2));
+        let mut evaluator = RandomRolloutEvaluator::new(
+            RolloutConfig {
+                limit: 0,
+                ..RolloutConfig::default()
+            },
+            6.5,
+        );
+
+        let black = evaluator.evaluate(&engine, Stone::Black);
+        let white = evaluator.evaluate(&engine, Stone::White);
+
+        assert_eq!(black.ownership, Some(vec![1.0; 9]));
+        let lead = black.score_lead.expect("black lead");
+        assert_eq!(white.score_lead, Some(-lead));
+    }
+
+    #[test]
+    fn search_averages_ownership_and_score_lead_for_the_root_player() {
+        // Black is always three points ahead and owns the left column.
+        struct OutcomeEvaluator;
+
+        impl MctsEvaluator for OutcomeEvaluator {
+            fn evaluate(&mut self, engine: &Engine, to_play: Stone) -> Evaluation {
+                let ownership = (0..9)
+                    .map(|index| if index % 3 == 0 { 1.0 } else { 0.0 })
+                    .collect();
+                Evaluation {
+                    value: 0.0,
+                    priors: uniform_priors(legal_actions(engine, to_play)),
+                    ownership: Some(ownership),
+                    score_lead: Some(if to_play == Stone::Black { 3.0 } else { -3.0 }),
+                }
+            }
+        }
+
+        let mut engine = Engine::new(3, 3);
+        let config = MctsConfig {
+            visits: 16,
+            ..MctsConfig::default()
+        };
+        let summary = search(&engine, config, &mut OutcomeEvaluator);
+        assert_eq!(summary.score_lead, Some(3.0));
+        let ownership = summary.ownership.expect("ownership");
+        assert_eq!(ownership[..3], [1.0, 0.0, 0.0]);
+
+        play(&mut engine, Stone::Black, (1, 1));
+        let summary = search(&engine, config, &mut OutcomeEvaluator);
+        assert_eq!(summary.score_lead, Some(-3.0));
+
+        let no_outcome = search(
+            &engine,
+            config,
+            &mut StaticEvaluator {
+                value: 0.0,
+                priors: HashMap::new(),
+            },
+        );
+        assert_eq!(no_outcome.ownership, None);
+        assert_eq!(no_outcome.score_lead, None);
+    }
+
+    #[test]
+    fn external_mcts_skips_ownership_of_the_wrong_size() {
+        let mut search = ExternalMctsSearch::new(
+            Engine::new(3, 3),
+            ExternalMctsConfig {
+                search: MctsConfig {
+                    visits: 2,
+                    ..MctsConfig::default()
+                },
+                max_policy_actions: None,
+                dead_stone_eval: DeadStoneEvalConfig::Off,
+            },
+        );
+        for ownership in [vec![-1.0; 9], vec![1.0; 4]] {
+            let batch = search.next_evaluations(1);
+            search.apply_evaluations(vec![ExternalEvaluation {
+                id: batch[0].id,
+                policy_logits: vec![0.0; 10],
+                value: 0.0,
+                ownership: Some(ownership),
+                score_lead: Some(-2.0),
+            }]);
+        }
+
+        let summary = search.summary();
+        assert_eq!(summary.ownership, Some(vec![-1.0; 9]));
+        // The child's lead is White's, so it counts for Black at the root.
+        assert_eq!(summary.score_lead, Some(0.0));
+    }
+
     #[test]
     fn random_rollout_evaluator_returns_scored_priors() {
         let engine = Engine::new(3, 3);
@@ -1745,13 +2632,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn tactical_bonuses_follow_the_ladder() {
+        let mut engine = Engine::new(9, 9);
+        play(&mut engine, Stone::Black, (3, 5));
+        play(&mut engine, Stone::White, (4, 5));
+        play(&mut engine, Stone::Black, (4, 6));
+        play(&mut engine, Stone::White, (0, 8));
+        play(&mut engine, Stone::Black, (3, 4));
+        play(&mut engine, Stone::White, (8, 8));
+
+        let bonuses = tactical_bonuses(&engine);
+        assert!(bonuses[&(5, 5)] > 0.0);
+        assert!(!bonuses.contains_key(&(4, 4)));
+
+        play(&mut engine, Stone::Black, (5, 5));
+        let bonuses = tactical_bonuses(&engine);
+        assert!(bonuses[&(4, 4)] < 0.0);
+    }
+
     #[test]
     fn score_value_uses_requested_perspective() {
         let mut engine = Engine::new(3, 3);
         play(&mut engine, Stone::Black, (0, 0));
 
-        let black_value = score_value(&engine, Stone::Black, 0.5);
-        let white_value = score_value(&engine, Stone::White, 0.5);
+        let black_value = score_position(&engine, Stone::Black, 0.5).value;
+        let white_value = score_position(&engine, Stone::White, 0.5).value;
 
         assert!(black_value > 0.0);
         assert!(black_value < 1.0);
@@ -1764,8 +2670,8 @@ mod tests {
         engine.try_pass(Stone::Black).expect("black pass");
         engine.try_pass(Stone::White).expect("white pass");
 
-        assert_eq!(score_value(&engine, Stone::Black, 0.5), -1.0);
-        assert_eq!(score_value(&engine, Stone::White, 0.5), 1.0);
+        assert_eq!(score_position(&engine, Stone::Black, 0.5).value, -1.0);
+        assert_eq!(score_position(&engine, Stone::White, 0.5).value, 1.0);
     }
 
     #[test]
@@ -1829,7 +2735,7 @@ mod tests {
     fn graph_node_value_recomputes_from_direct_eval_and_child_edges() {
         let engine = Engine::new(3, 3);
         let key = PositionKey::from_engine(&engine);
-        let mut nodes = vec![GraphNode::new(key.clone()), GraphNode::new(key)];
+        let mut nodes = vec![GraphNode::new(key), GraphNode::new(key)];
 
         nodes[1].set_raw_value(-0.6);
         recompute_node(&mut nodes, NodeId(1));
@@ -1850,7 +2756,7 @@ mod tests {
     fn edge_catch_up_detects_child_visits_ahead_of_parent_action() {
         let engine = Engine::new(3, 3);
         let key = PositionKey::from_engine(&engine);
-        let mut nodes = vec![GraphNode::new(key.clone()), GraphNode::new(key)];
+        let mut nodes = vec![GraphNode::new(key), GraphNode::new(key)];
 
         nodes[1].set_raw_value(-0.25);
         recompute_node(&mut nodes, NodeId(1));
@@ -1891,8 +2797,8 @@ mod tests {
         };
         let mut search = GraphSearch::new(MctsConfig::default(), &mut evaluator);
         search.nodes = vec![
-            GraphNode::new(key.clone()),
-            GraphNode::new(key.clone()),
+            GraphNode::new(key),
+            GraphNode::new(key),
             GraphNode::new(key),
         ];
         search.nodes[0].push_edge(EdgeStats::new(BotMove::Pass, NodeId(1), 100.0));
@@ -1936,12 +2842,13 @@ mod tests {
                 visits: 1,
                 cpuct: 0.01,
                 fpu_reduction: 0.2,
+                ..MctsConfig::default()
             },
             &mut evaluator,
         );
         search.nodes = vec![
-            GraphNode::new(key.clone()),
-            GraphNode::new(key.clone()),
+            GraphNode::new(key),
+            GraphNode::new(key),
             GraphNode::new(key),
         ];
         let mut good_for_parent = EdgeStats::new(BotMove::Play((0, 0)), NodeId(1), 1.0);
@@ -1969,12 +2876,13 @@ mod tests {
                 visits: 1,
                 cpuct: 0.0,
                 fpu_reduction: 0.2,
+                ..MctsConfig::default()
             },
             &mut evaluator,
         );
         search.nodes = vec![
-            GraphNode::new(key.clone()),
-            GraphNode::new(key.clone()),
+            GraphNode::new(key),
+            GraphNode::new(key),
             GraphNode::new(key),
         ];
         search.nodes[0].backup_node(0.0);
@@ -2001,6 +2909,7 @@ mod tests {
                 visits: 12,
                 cpuct: 1.5,
                 fpu_reduction: 0.2,
+                ..MctsConfig::default()
             },
             &mut evaluator,
         );
@@ -0,0 +1,507 @@
+use std::collections::{HashMap, HashSet};
+use std::num::NonZero;
+use std::sync::{Condvar, Mutex, MutexGuard};
+use std::thread;
+use std::time::Duration;
+
+use super::{
+    ActionPrior, EdgeStats, Evaluation, GraphNode, MctsConfig, MctsEvaluator, NodeId,
+    OutcomeTotals, PositionKey, SearchClock, SearchDiagnostics, SearchSummary, StopReason,
+    Stopwatch, apply_action, edge_needs_catch_up, recompute_node, remaining_visits,
+    root_is_decided, search_with_clock, summarize,
+};
+use crate::Engine;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParallelMctsConfig {
+    pub search: MctsConfig,
+    /// Search threads. One runs the serial search on the calling thread.
+    pub threads: usize,
+    /// Losses each evaluation in flight adds to the edges on its path, so
+    /// other threads look elsewhere instead of queueing on the same leaf.
+    pub virtual_loss: u32,
+}
+
+impl Default for ParallelMctsConfig {
+    fn default() -> Self {
+        Self {
+            search: MctsConfig::default(),
+            threads: thread::available_parallelism().map_or(1, NonZero::get),
+            virtual_loss: 1,
+        }
+    }
+}
+
+pub fn parallel_search<E, F>(
+    engine: &Engine,
+    config: ParallelMctsConfig,
+    make_evaluator: F,
+) -> SearchSummary
+where
+    E: MctsEvaluator,
+    F: Fn(usize) -> E + Sync,
+{
+    let clock = Stopwatch::for_budget(config.search.time);
+    parallel_search_with_clock(engine, config, make_evaluator, &clock)
+}
+
+/// Search one shared graph from `config.threads` threads, each with its own
+/// evaluator from `make_evaluator(thread_index)`. A single thread runs
+/// [`search_with_clock`] unchanged, so its results stay deterministic.
+pub fn parallel_search_with_clock<E, F, C>(
+    engine: &Engine,
+    config: ParallelMctsConfig,
+    make_evaluator: F,
+    clock: &C,
+) -> SearchSummary
+where
+    E: MctsEvaluator,
+    F: Fn(usize) -> E + Sync,
+    C: SearchClock + Sync,
+{
+    if config.threads <= 1 {
+        let mut summary = search_with_clock(engine, config.search, &mut make_evaluator(0), clock);
+        summary.diagnostics.thread_collisions = vec![0];
+        return summary;
+    }
+
+    let mut graph = SharedGraph::new(config);
+    let root_id = graph.intern_node(engine);
+    let search = ParallelSearch {
+        config,
+        root: engine,
+        root_id,
+        move_time: config.search.time.move_time(engine),
+        clock,
+        graph: Mutex::new(graph),
+        progress: Condvar::new(),
+    };
+    let thread_collisions = thread::scope(|scope| {
+        let handles: Vec<_> = (0..config.threads)
+            .map(|index| {
+                let search = &search;
+                let make_evaluator = &make_evaluator;
+                scope.spawn(move || search.run(make_evaluator(index)))
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("search thread panicked"))
+            .collect()
+    });
+
+    let graph = search.graph.into_inner().expect("search thread panicked");
+    summarize(
+        &graph.nodes,
+        root_id,
+        SearchDiagnostics {
+            thread_collisions,
+            ..graph.diagnostics
+        },
+        &graph.outcomes,
+        clock.elapsed(),
+        graph.stop_reason,
+    )
+}
+
+struct ParallelSearch<'a, C> {
+    config: ParallelMctsConfig,
+    root: &'a Engine,
+    root_id: NodeId,
+    move_time: Option<Duration>,
+    clock: &'a C,
+    graph: Mutex<SharedGraph>,
+    /// Signalled whenever an evaluation lands, for threads that collided.
+    progress: Condvar,
+}
+
+impl<C: SearchClock + Sync> ParallelSearch<'_, C> {
+    /// Select and evaluate leaves until the search stops. Returns the
+    /// thread's collisions.
+    fn run<E: MctsEvaluator>(&self, mut evaluator: E) -> u32 {
+        let mut collisions = 0;
+        let mut graph = self.lock();
+
+        loop {
+            if graph.stop_reason.is_none() {
+                graph.stop_reason = self.stop_reason(&graph);
+            }
+            if graph.stop_reason.is_some() {
+                return collisions;
+            }
+
+            match graph.select(self.root, self.root_id) {
+                Selection::Finished => {}
+                Selection::Collision => {
+                    collisions += 1;
+                    graph = self.progress.wait(graph).expect("search thread panicked");
+                }
+                Selection::Evaluate(leaf) => {
+                    drop(graph);
+                    let to_play = leaf.engine.current_turn_stone();
+                    let evaluation = evaluator.evaluate(&leaf.engine, to_play);
+                    graph = self.lock();
+                    graph.finish(self.root, *leaf, evaluation);
+                    self.progress.notify_all();
+                }
+            }
+        }
+    }
+
+    fn stop_reason(&self, graph: &SharedGraph) -> Option<StopReason> {
+        let search = &self.config.search;
+        if graph.started >= search.visits {
+            return Some(StopReason::Visits);
+        }
+        // The first visit always runs so there is a move to play.
+        if graph.completed == 0 {
+            return None;
+        }
+        let elapsed = self.clock.elapsed();
+        if self.move_time.is_some_and(|time| elapsed >= time) {
+            return Some(StopReason::Time);
+        }
+        let remaining = remaining_visits(graph.completed, search.visits, elapsed, self.move_time);
+        (search.early_stop && root_is_decided(&graph.nodes[self.root_id.0].edges, remaining))
+            .then_some(StopReason::Decided)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, SharedGraph> {
+        self.graph.lock().expect("search thread panicked")
+    }
+}
+
+enum Selection {
+    /// The visit ended without an evaluation: terminal, cycle, catch-up or
+    /// invalid action.
+    Finished,
+    /// The selected leaf is already being evaluated by another thread.
+    Collision,
+    Evaluate(Box<Leaf>),
+}
+
+struct Leaf {
+    node_id: NodeId,
+    path: Vec<(NodeId, usize)>,
+    engine: Engine,
+}
+
+#[derive(Debug)]
+struct SharedGraph {
+    config: ParallelMctsConfig,
+    nodes: Vec<GraphNode>,
+    node_by_key: HashMap<PositionKey, NodeId>,
+    /// Evaluations in flight through each edge.
+    in_flight_edges: HashMap<(NodeId, usize), u32>,
+    in_flight_nodes: HashSet<NodeId>,
+    /// Visits claimed, finished or in flight.
+    started: u32,
+    completed: u32,
+    stop_reason: Option<StopReason>,
+    diagnostics: SearchDiagnostics,
+    outcomes: OutcomeTotals,
+}
+
+impl SharedGraph {
+    fn new(config: ParallelMctsConfig) -> Self {
+        Self {
+            config,
+            nodes: Vec::new(),
+            node_by_key: HashMap::new(),
+            in_flight_edges: HashMap::new(),
+            in_flight_nodes: HashSet::new(),
+            started: 0,
+            completed: 0,
+            stop_reason: None,
+            diagnostics: SearchDiagnostics::default(),
+            outcomes: OutcomeTotals::default(),
+        }
+    }
+
+    fn select(&mut self, root: &Engine, root_id: NodeId) -> Selection {
+        let mut node_id = root_id;
+        let mut engine = root.clone();
+        let mut path: Vec<(NodeId, usize)> = Vec::new();
+        let mut active_nodes = HashSet::from([root_id]);
+
+        loop {
+            if !self.nodes[node_id.0].is_evaluated() {
+                if !self.in_flight_nodes.insert(node_id) {
+                    return Selection::Collision;
+                }
+                for &edge in &path {
+                    *self.in_flight_edges.entry(edge).or_default() += 1;
+                }
+                self.started += 1;
+                return Selection::Evaluate(Box::new(Leaf {
+                    node_id,
+                    path,
+                    engine,
+                }));
+            }
+
+            if self.nodes[node_id.0].edges.is_empty() {
+                self.diagnostics.terminal_visits += 1;
+                return self.finish_without_evaluation(&path);
+            }
+
+            let Some(edge_index) = self.select_edge(node_id, &active_nodes) else {
+                let edge_index = self.select_edge_allowing_cycle(node_id);
+                path.push((node_id, edge_index));
+                self.diagnostics.cycle_visits += 1;
+                return self.finish_without_evaluation(&path);
+            };
+
+            if edge_needs_catch_up(&self.nodes, node_id, edge_index) {
+                path.push((node_id, edge_index));
+                self.diagnostics.catch_up_visits += 1;
+                return self.finish_without_evaluation(&path);
+            }
+
+            let action = self.nodes[node_id.0].edges[edge_index].action;
+            let child = self.nodes[node_id.0].edges[edge_index].child;
+
+            if apply_action(&mut engine, action).is_err() {
+                self.diagnostics.invalid_action_visits += 1;
+                self.nodes[node_id.0].set_raw_value(-1.0);
+                recompute_node(&mut self.nodes, node_id);
+                return self.finish_without_evaluation(&path);
+            }
+
+            path.push((node_id, edge_index));
+            node_id = child;
+            if !active_nodes.insert(node_id) {
+                self.diagnostics.cycle_visits += 1;
+                return self.finish_without_evaluation(&path);
+            }
+        }
+    }
+
+    fn finish_without_evaluation(&mut self, path: &[(NodeId, usize)]) -> Selection {
+        self.backup_path(path);
+        self.started += 1;
+        self.completed += 1;
+        Selection::Finished
+    }
+
+    fn finish(&mut self, root: &Engine, leaf: Leaf, evaluation: Evaluation) {
+        self.in_flight_nodes.remove(&leaf.node_id);
+        for edge in &leaf.path {
+            if let Some(count) = self.in_flight_edges.get_mut(edge) {
+                *count -= 1;
+                if *count == 0 {
+                    self.in_flight_edges.remove(edge);
+                }
+            }
+        }
+
+        self.outcomes.add(
+            root,
+            leaf.engine.current_turn_stone(),
+            evaluation.ownership.as_deref(),
+            evaluation.score_lead,
+        );
+        self.nodes[leaf.node_id.0].set_raw_value(evaluation.value);
+        self.expand_node(leaf.node_id, &leaf.engine, evaluation.priors);
+        recompute_node(&mut self.nodes, leaf.node_id);
+        self.backup_path(&leaf.path);
+        self.completed += 1;
+    }
+
+    fn expand_node(&mut self, node_id: NodeId, engine: &Engine, priors: Vec<ActionPrior>) {
+        let mut edges = Vec::with_capacity(priors.len());
+
+        for prior in priors {
+            let mut child_engine = engine.clone();
+            if apply_action(&mut child_engine, prior.action).is_err() {
+                continue;
+            }
+            let child = self.intern_node(&child_engine);
+            edges.push(EdgeStats::new(prior.action, child, prior.prior.max(0.0)));
+        }
+
+        self.nodes[node_id.0].edges = edges;
+    }
+
+    fn select_edge(&self, node_id: NodeId, active_nodes: &HashSet<NodeId>) -> Option<usize> {
+        self.best_edge(node_id, |edge| !active_nodes.contains(&edge.child))
+    }
+
+    fn select_edge_allowing_cycle(&self, node_id: NodeId) -> usize {
+        self.best_edge(node_id, |_| true)
+            .expect("expanded graph node has at least one edge")
+    }
+
+    /// PUCT as in the serial search, with each evaluation in flight through
+    /// an edge counted as `virtual_loss` lost visits.
+    fn best_edge(
+        &self,
+        node_id: NodeId,
+        mut include: impl FnMut(&EdgeStats) -> bool,
+    ) -> Option<usize> {
+        let node = &self.nodes[node_id.0];
+        let parent_visits = node.visits.max(1) as f32;
+        let mut best_index = None;
+        let mut best_score = f32::NEG_INFINITY;
+
+        for (index, edge) in node.edges.iter().enumerate() {
+            if !include(edge) {
+                continue;
+            }
+
+            let virtual_visits = self
+                .in_flight_edges
+                .get(&(node_id, index))
+                .map_or(0, |count| count * self.config.virtual_loss);
+            let visits = edge.visits + virtual_visits;
+            let q = if virtual_visits == 0 {
+                self.edge_selection_value(node, edge)
+            } else {
+                (edge.value * edge.visits as f32 - virtual_visits as f32) / visits as f32
+            };
+            let u =
+                self.config.search.cpuct * edge.prior * parent_visits.sqrt() / (1 + visits) as f32;
+            let score = q + u;
+
+            if score > best_score {
+                best_index = Some(index);
+                best_score = score;
+            }
+        }
+
+        best_index
+    }
+
+    fn edge_selection_value(&self, node: &GraphNode, edge: &EdgeStats) -> f32 {
+        if edge.visits() > 0 {
+            return edge.mean_value();
+        }
+
+        (node.mean_value() - self.config.search.fpu_reduction).clamp(-1.0, 1.0)
+    }
+
+    fn backup_path(&mut self, path: &[(NodeId, usize)]) {
+        for &(node_id, edge_index) in path.iter().rev() {
+            self.nodes[node_id.0].edges[edge_index].increment_visit();
+            recompute_node(&mut self.nodes, node_id);
+        }
+    }
+
+    fn intern_node(&mut self, engine: &Engine) -> NodeId {
+        let key = PositionKey::from_engine(engine);
+        if let Some(id) = self.node_by_key.get(&key) {
+            return *id;
+        }
+
+        let id = NodeId(self.nodes.len());
+        self.nodes.push(GraphNode::new(key));
+        self.node_by_key.insert(key, id);
+        id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Stone;
+    use crate::mcts::{
+        RandomRolloutEvaluator, RolloutConfig, legal_actions, search, uniform_priors,
+    };
+
+    fn rollout_evaluator(seed: u64) -> RandomRolloutEvaluator {
+        RandomRolloutEvaluator::new(
+            RolloutConfig {
+                seed,
+                ..RolloutConfig::default()
+            },
+            0.5,
+        )
+    }
+
+    /// Uniform priors and an even value, slow enough that every thread has
+    /// an evaluation in flight at once.
+    struct SlowEvaluator;
+
+    impl MctsEvaluator for SlowEvaluator {
+        fn evaluate(&mut self, engine: &Engine, to_play: Stone) -> Evaluation {
+            thread::sleep(Duration::from_millis(20));
+            Evaluation {
+                value: 0.0,
+                priors: uniform_priors(legal_actions(engine, to_play)),
+                ownership: None,
+                score_lead: None,
+            }
+        }
+    }
+
+    #[test]
+    fn one_thread_matches_the_serial_search() {
+        let engine = Engine::new(5, 5);
+        let config = MctsConfig {
+            visits: 48,
+            ..MctsConfig::default()
+        };
+        let serial = search(&engine, config, &mut rollout_evaluator(7));
+        let parallel = parallel_search(
+            &engine,
+            ParallelMctsConfig {
+                search: config,
+                threads: 1,
+                virtual_loss: 1,
+            },
+            |_| rollout_evaluator(7),
+        );
+
+        assert_eq!(parallel.root_edges, serial.root_edges);
+        assert_eq!(parallel.principal_variation, serial.principal_variation);
+        assert_eq!(parallel.diagnostics.thread_collisions, vec![0]);
+    }
+
+    #[test]
+    fn threads_share_the_visit_budget() {
+        let summary = parallel_search(
+            &Engine::new(5, 5),
+            ParallelMctsConfig {
+                search: MctsConfig {
+                    visits: 200,
+                    ..MctsConfig::default()
+                },
+                threads: 4,
+                virtual_loss: 1,
+            },
+            |index| rollout_evaluator(index as u64),
+        );
+
+        assert_eq!(summary.visits, 200);
+        assert_eq!(summary.stop_reason, Some(StopReason::Visits));
+        assert_eq!(summary.diagnostics.thread_collisions.len(), 4);
+        assert!(summary.best_move.is_some());
+    }
+
+    #[test]
+    fn virtual_loss_sends_threads_to_different_moves() {
+        let summary = parallel_search(
+            &Engine::new(3, 3),
+            ParallelMctsConfig {
+                search: MctsConfig {
+                    visits: 5,
+                    ..MctsConfig::default()
+                },
+                threads: 4,
+                virtual_loss: 1,
+            },
+            |_| SlowEvaluator,
+        );
+
+        // The root, then one distinct reply per thread.
+        assert_eq!(summary.visits, 5);
+        assert_eq!(summary.diagnostics.visited_root_moves, 4);
+        assert!(
+            summary.root_edges[..4]
+                .iter()
+                .all(|edge| edge.visits() == 1)
+        );
+        // The other threads waited for the root evaluation.
+        assert!(summary.diagnostics.thread_collisions.iter().sum::<u32>() >= 1);
+    }
+}
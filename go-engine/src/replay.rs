@@ -1,6 +1,11 @@
 use crate::Point;
+use crate::annotation::Annotation;
 use crate::engine::Engine;
 use crate::game_tree::{GameTree, NodeId};
+use crate::handicap::HandicapPlacement;
+use crate::ruleset::Ruleset;
+use crate::setup::Setup;
+use crate::topology::Topology;
 use crate::turn::Turn;
 
 /// Engine wrapper with a game tree and a navigation cursor.
@@ -13,6 +18,10 @@ pub struct Replay {
     cols: u8,
     rows: u8,
     handicap: u8,
+    handicap_placement: HandicapPlacement,
+    ruleset: Ruleset,
+    topology: Topology,
+    capture_target: Option<u32>,
     tree: GameTree,
     current: Option<NodeId>,
     engine: Engine,
@@ -32,6 +41,10 @@ impl Replay {
             cols,
             rows,
             handicap: 0,
+            handicap_placement: HandicapPlacement::Fixed,
+            ruleset: Ruleset::default(),
+            topology: Topology::default(),
+            capture_target: None,
             tree: GameTree::new(),
             current: None,
             engine: Engine::new(cols, rows),
@@ -50,6 +63,10 @@ impl Replay {
             cols,
             rows,
             handicap: 0,
+            handicap_placement: HandicapPlacement::Fixed,
+            ruleset: Ruleset::default(),
+            topology: Topology::default(),
+            capture_target: None,
             tree,
             current,
             engine,
@@ -59,17 +76,61 @@ impl Replay {
         }
     }
 
+    /// Start from `setup` instead of an empty board.
+    pub fn with_setup(cols: u8, rows: u8, setup: Setup) -> Self {
+        let mut replay = Self::new(cols, rows);
+        replay.set_setup(setup);
+        replay
+    }
+
+    /// Replace the starting position, keeping the moves played from it.
+    pub fn set_setup(&mut self, setup: Setup) {
+        self.tree.set_setup(setup);
+        self.rebuild();
+    }
+
     pub fn set_handicap(&mut self, handicap: u8) {
         self.handicap = handicap;
         self.rebuild();
     }
 
+    pub fn set_handicap_placement(&mut self, placement: HandicapPlacement) {
+        self.handicap_placement = placement;
+        self.rebuild();
+    }
+
+    pub fn set_ruleset(&mut self, ruleset: Ruleset) {
+        self.ruleset = ruleset;
+        self.rebuild();
+    }
+
+    pub fn set_topology(&mut self, topology: Topology) {
+        self.topology = topology;
+        self.rebuild();
+    }
+
+    /// Play Capture Go with the given target, or regular Go with `None`.
+    pub fn set_capture_target(&mut self, target: Option<u32>) {
+        self.capture_target = target;
+        self.rebuild();
+    }
+
     fn rebuild(&mut self) {
         let moves = match self.current {
             Some(id) => self.tree.moves_to(id),
             None => Vec::new(),
         };
-        self.engine = Engine::with_handicap_and_moves(self.cols, self.rows, self.handicap, moves);
+        let engine = Engine::with_handicap(self.cols, self.rows, self.handicap)
+            .with_handicap_placement(self.handicap_placement)
+            .with_ruleset(self.ruleset)
+            .with_topology(self.topology)
+            .with_setups(self.tree.setups_to(self.current))
+            .replay(moves)
+            .expect("invalid move in replay");
+        self.engine = match self.capture_target {
+            Some(target) => engine.with_capture_target(target),
+            None => engine,
+        };
         self.history.clear();
     }
 
@@ -161,6 +222,30 @@ impl Replay {
         self.handicap
     }
 
+    pub fn handicap_placement(&self) -> HandicapPlacement {
+        self.handicap_placement
+    }
+
+    /// The free handicap stones at the start of the main line, if any.
+    pub fn free_handicap_stones(&self) -> Vec<Point> {
+        if self.handicap_placement == HandicapPlacement::Fixed {
+            return Vec::new();
+        }
+        Self::main_line_path_impl(&self.tree, None)
+            .into_iter()
+            .take(self.handicap as usize)
+            .filter_map(|id| self.tree.node(id).turn.pos)
+            .collect()
+    }
+
+    pub fn ruleset(&self) -> &Ruleset {
+        &self.ruleset
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
     pub fn engine(&self) -> &Engine {
         &self.engine
     }
@@ -232,6 +317,15 @@ impl Replay {
         self.current.map(|id| &self.tree.node(id).turn)
     }
 
+    /// Comment, markup and glyphs at the current position.
+    pub fn annotation(&self) -> &Annotation {
+        self.tree.annotation(self.current)
+    }
+
+    pub fn set_annotation(&mut self, annotation: Annotation) {
+        self.tree.set_annotation(self.current, annotation);
+    }
+
     /// The position of the last played stone at the current view, if any.
     pub fn last_play_pos(&self) -> Option<Point> {
         self.last_move().filter(|t| t.is_play()).and_then(|t| t.pos)
@@ -371,8 +465,10 @@ impl Replay {
             children[0]
         };
         self.history.push(self.engine.clone());
-        let turn = &self.tree.node(next).turn;
-        self.apply_turn(turn.clone());
+        let node = self.tree.node(next);
+        let (setup, turn) = (node.setup.clone(), node.turn.clone());
+        self.engine.edit_position(setup);
+        self.apply_turn(turn);
         self.current = Some(next);
         true
     }
@@ -413,17 +509,29 @@ impl Replay {
     /// Rebuilds the tree from the main-line turns and jumps to its tip.
     pub fn clear_variations(&mut self) {
         let main = self.main_line_path();
-        let turns: Vec<Turn> = main
-            .iter()
-            .map(|&id| self.tree.node(id).turn.clone())
-            .collect();
-        self.replace_moves(turns);
+        let mut tree = GameTree::with_setup(self.tree.setup().clone());
+        tree.set_annotation(None, self.tree.annotation(None).clone());
+        let mut parent = None;
+        for id in main {
+            let node = self.tree.node(id);
+            let new_id = tree.add_child_with_setup(parent, node.setup.clone(), node.turn.clone());
+            tree.set_annotation(Some(new_id), node.annotation.clone());
+            parent = Some(new_id);
+        }
+        self.tree = tree;
+        self.base_tip = None;
+        self.path = self.main_line_path();
+        self.current = self.path.last().copied();
+        self.base_tip = self.current;
+        self.rebuild();
     }
 
     /// Replace the full move history with a flat list.
-    /// Builds a fresh linear GameTree and sets current to latest.
+    /// Builds a fresh linear GameTree from the same starting position and sets current to latest.
     pub fn replace_moves(&mut self, moves: Vec<Turn>) {
+        let setup = self.tree.setup().clone();
         self.tree = GameTree::from_moves(&moves);
+        self.tree.set_setup(setup);
         self.path = self.main_line_path();
         self.current = self.path.last().copied();
         self.base_tip = self.current;
@@ -568,6 +676,66 @@ mod tests {
         assert_eq!(r.engine().board()[2], 0);
     }
 
+    #[test]
+    fn setup_position_survives_navigation() {
+        let setup = Setup::stones(vec![(2, 2)], vec![(6, 6)]).with_to_play(Stone::White);
+        let mut r = Replay::with_setup(9, 9, setup);
+        assert_eq!(r.engine().stone_at((2, 2)), Some(Stone::Black));
+        assert_eq!(r.engine().current_turn_stone(), Stone::White);
+
+        assert!(r.try_play(4, 4));
+        assert!(r.try_play(5, 5));
+        r.to_start();
+        assert_eq!(r.engine().stone_at((6, 6)), Some(Stone::White));
+        assert!(r.forward());
+        assert_eq!(r.engine().stone_at((4, 4)), Some(Stone::White));
+
+        r.clear_variations();
+        assert_eq!(r.engine().stone_at((2, 2)), Some(Stone::Black));
+        assert_eq!(r.total_moves(), 2);
+    }
+
+    #[test]
+    fn node_setup_applies_when_stepping_forward() {
+        let mut tree = GameTree::new();
+        let a = tree.add_child(None, Turn::play(Stone::Black, (0, 0)));
+        let cleared = Setup {
+            empty: vec![(0, 0)],
+            ..Default::default()
+        };
+        tree.add_child_with_setup(Some(a), cleared, Turn::play(Stone::White, (0, 0)));
+
+        let mut r = Replay::new(9, 9);
+        r.replace_tree(tree);
+        assert_eq!(r.engine().stone_at((0, 0)), Some(Stone::White));
+
+        r.to_start();
+        r.forward();
+        assert_eq!(r.engine().stone_at((0, 0)), Some(Stone::Black));
+        r.forward();
+        assert_eq!(r.engine().stone_at((0, 0)), Some(Stone::White));
+    }
+
+    #[test]
+    fn clear_variations_keeps_annotations() {
+        let mut r = Replay::new(9, 9);
+        let note = |text: &str| Annotation {
+            comment: Some(text.into()),
+            ..Default::default()
+        };
+        r.set_annotation(note("start"));
+        r.try_play(0, 0);
+        r.set_annotation(note("main"));
+        r.back();
+        r.try_play(1, 1);
+        r.set_annotation(note("variation"));
+
+        r.clear_variations();
+        assert_eq!(r.annotation(), &note("main"));
+        r.to_start();
+        assert_eq!(r.annotation(), &note("start"));
+    }
+
     #[test]
     fn clear_variations_empty_tree_is_noop() {
         let mut r = Replay::new(9, 9);
@@ -0,0 +1,205 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ko::KoRule;
+
+/// What counts toward a player's score at the end of the game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoringMethod {
+    /// Surrounded empty points plus prisoners (Japanese).
+    Territory,
+    /// Surrounded empty points plus stones on the board (Chinese).
+    Area,
+}
+
+/// Points White receives for Black's handicap stones under area scoring.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HandicapCompensation {
+    None,
+    /// One point per handicap stone.
+    Full,
+    /// One point per handicap stone after the first.
+    MinusOne,
+}
+
+/// A complete set of rules: how the game is scored and which moves are legal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Ruleset {
+    pub scoring: ScoringMethod,
+    pub ko_rule: KoRule,
+    /// Whether a move may leave its own chain without liberties (the chain is removed).
+    pub suicide_allowed: bool,
+    /// Whether passing hands the opponent a prisoner (AGA).
+    pub pass_stones: bool,
+    pub handicap_compensation: HandicapCompensation,
+}
+
+impl Ruleset {
+    pub const JAPANESE: Ruleset = Ruleset {
+        scoring: ScoringMethod::Territory,
+        ko_rule: KoRule::Simple,
+        suicide_allowed: false,
+        pass_stones: false,
+        handicap_compensation: HandicapCompensation::None,
+    };
+
+    pub const CHINESE: Ruleset = Ruleset {
+        scoring: ScoringMethod::Area,
+        ko_rule: KoRule::PositionalSuperko,
+        suicide_allowed: false,
+        pass_stones: false,
+        handicap_compensation: HandicapCompensation::Full,
+    };
+
+    pub const AGA: Ruleset = Ruleset {
+        scoring: ScoringMethod::Area,
+        ko_rule: KoRule::SituationalSuperko,
+        suicide_allowed: false,
+        pass_stones: true,
+        handicap_compensation: HandicapCompensation::MinusOne,
+    };
+
+    pub const NEW_ZEALAND: Ruleset = Ruleset {
+        scoring: ScoringMethod::Area,
+        ko_rule: KoRule::SituationalSuperko,
+        suicide_allowed: true,
+        pass_stones: false,
+        handicap_compensation: HandicapCompensation::None,
+    };
+
+    const PRESETS: [(&'static str, Ruleset); 4] = [
+        ("japanese", Ruleset::JAPANESE),
+        ("chinese", Ruleset::CHINESE),
+        ("aga", Ruleset::AGA),
+        ("new_zealand", Ruleset::NEW_ZEALAND),
+    ];
+
+    /// The preset name (`"japanese"`, `"chinese"`, `"aga"`, `"new_zealand"`),
+    /// or `None` for a custom combination.
+    pub fn name(&self) -> Option<&'static str> {
+        Self::PRESETS
+            .iter()
+            .find(|(_, rules)| rules == self)
+            .map(|&(name, _)| name)
+    }
+
+    /// Extra points White receives for `handicap` stones.
+    pub fn handicap_bonus(&self, handicap: u8) -> f64 {
+        if handicap < 2 || self.scoring == ScoringMethod::Territory {
+            return 0.0;
+        }
+        match self.handicap_compensation {
+            HandicapCompensation::None => 0.0,
+            HandicapCompensation::Full => handicap as f64,
+            HandicapCompensation::MinusOne => (handicap - 1) as f64,
+        }
+    }
+
+    /// Parse an SGF `RU[]` value. Unknown rule names yield `None`.
+    pub fn from_sgf(value: &str) -> Option<Ruleset> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "japanese" | "jp" => Some(Ruleset::JAPANESE),
+            "chinese" | "cn" => Some(Ruleset::CHINESE),
+            "aga" => Some(Ruleset::AGA),
+            "nz" | "new zealand" | "new_zealand" => Some(Ruleset::NEW_ZEALAND),
+            _ => None,
+        }
+    }
+
+    /// The SGF `RU[]` value for this ruleset, if it is a preset.
+    pub fn to_sgf(&self) -> Option<&'static str> {
+        self.name().map(|name| match name {
+            "japanese" => "Japanese",
+            "chinese" => "Chinese",
+            "aga" => "AGA",
+            _ => "NZ",
+        })
+    }
+}
+
+impl Default for Ruleset {
+    fn default() -> Self {
+        Ruleset::JAPANESE
+    }
+}
+
+impl fmt::Display for Ruleset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.name() {
+            Some(name) => write!(f, "{name}"),
+            None => write!(f, "custom"),
+        }
+    }
+}
+
+impl std::str::FromStr for Ruleset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::PRESETS
+            .iter()
+            .find(|(name, _)| *name == s)
+            .map(|&(_, rules)| rules)
+            .ok_or_else(|| format!("unknown ruleset: {s}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_round_trip_through_names() {
+        for name in ["japanese", "chinese", "aga", "new_zealand"] {
+            let rules: Ruleset = name.parse().unwrap();
+            assert_eq!(rules.name(), Some(name));
+            assert_eq!(rules.to_string(), name);
+        }
+    }
+
+    #[test]
+    fn unknown_name_is_rejected() {
+        assert!("ing".parse::<Ruleset>().is_err());
+    }
+
+    #[test]
+    fn custom_combination_has_no_name() {
+        let rules = Ruleset {
+            suicide_allowed: true,
+            ..Ruleset::JAPANESE
+        };
+        assert_eq!(rules.name(), None);
+        assert_eq!(rules.to_sgf(), None);
+        assert_eq!(rules.to_string(), "custom");
+    }
+
+    #[test]
+    fn sgf_rule_names() {
+        assert_eq!(Ruleset::from_sgf("Chinese"), Some(Ruleset::CHINESE));
+        assert_eq!(Ruleset::from_sgf("NZ"), Some(Ruleset::NEW_ZEALAND));
+        assert_eq!(Ruleset::from_sgf("Ing"), None);
+        assert_eq!(Ruleset::AGA.to_sgf(), Some("AGA"));
+        assert_eq!(Ruleset::JAPANESE.to_sgf(), Some("Japanese"));
+    }
+
+    #[test]
+    fn only_area_presets_use_superko() {
+        assert_eq!(Ruleset::JAPANESE.ko_rule, KoRule::Simple);
+        assert_eq!(Ruleset::default().ko_rule, KoRule::Simple);
+        assert_eq!(Ruleset::CHINESE.ko_rule, KoRule::PositionalSuperko);
+        assert_eq!(Ruleset::AGA.ko_rule, KoRule::SituationalSuperko);
+        assert_eq!(Ruleset::NEW_ZEALAND.ko_rule, KoRule::SituationalSuperko);
+    }
+
+    #[test]
+    fn handicap_bonus_follows_compensation() {
+        assert_eq!(Ruleset::CHINESE.handicap_bonus(4), 4.0);
+        assert_eq!(Ruleset::AGA.handicap_bonus(4), 3.0);
+        assert_eq!(Ruleset::NEW_ZEALAND.handicap_bonus(4), 0.0);
+        assert_eq!(Ruleset::JAPANESE.handicap_bonus(4), 0.0);
+        assert_eq!(Ruleset::CHINESE.handicap_bonus(0), 0.0);
+    }
+}
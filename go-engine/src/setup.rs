@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+
+use crate::Point;
+use crate::stone::Stone;
+
+/// Stones added or removed outside normal play, as in SGF `AB`/`AW`/`AE`/`PL`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Setup {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub black: Vec<Point>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub white: Vec<Point>,
+    /// Points cleared before `black` and `white` are placed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub empty: Vec<Point>,
+    /// Side to move next, overriding the usual alternation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_play: Option<Stone>,
+}
+
+impl Setup {
+    pub fn is_empty(&self) -> bool {
+        self.black.is_empty()
+            && self.white.is_empty()
+            && self.empty.is_empty()
+            && self.to_play.is_none()
+    }
+
+    pub fn stones(black: Vec<Point>, white: Vec<Point>) -> Self {
+        Setup {
+            black,
+            white,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_to_play(mut self, stone: Stone) -> Self {
+        self.to_play = Some(stone);
+        self
+    }
+
+    /// Fold a later edit into this one, so applying the result once has the
+    /// same effect as applying both in order.
+    pub fn merge(&mut self, other: Setup) {
+        for pt in other.empty {
+            self.black.retain(|&p| p != pt);
+            self.white.retain(|&p| p != pt);
+            self.empty.push(pt);
+        }
+        for pt in other.black {
+            self.white.retain(|&p| p != pt);
+            self.black.push(pt);
+        }
+        for pt in other.white {
+            self.black.retain(|&p| p != pt);
+            self.white.push(pt);
+        }
+        if other.to_play.is_some() {
+            self.to_play = other.to_play;
+        }
+    }
+
+    /// This edit with every point moved by `f`.
+    pub(crate) fn map_points(&self, f: &impl Fn(Point) -> Point) -> Self {
+        Setup {
+            black: self.black.iter().copied().map(f).collect(),
+            white: self.white.iter().copied().map(f).collect(),
+            empty: self.empty.iter().copied().map(f).collect(),
+            to_play: self.to_play,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_is_empty() {
+        assert!(Setup::default().is_empty());
+        assert!(!Setup::default().with_to_play(Stone::White).is_empty());
+        assert!(!Setup::stones(vec![(0, 0)], vec![]).is_empty());
+    }
+
+    #[test]
+    fn merge_keeps_the_later_edit() {
+        let mut setup = Setup::stones(vec![(0, 0), (1, 1)], vec![(2, 2)]);
+        setup.merge(Setup {
+            white: vec![(0, 0)],
+            empty: vec![(2, 2)],
+            to_play: Some(Stone::Black),
+            ..Default::default()
+        });
+        assert_eq!(setup.black, vec![(1, 1)]);
+        assert_eq!(setup.white, vec![(0, 0)]);
+        assert_eq!(setup.empty, vec![(2, 2)]);
+        assert_eq!(setup.to_play, Some(Stone::Black));
+    }
+
+    #[test]
+    fn serde_skips_empty_fields() {
+        let setup = Setup::stones(vec![(2, 2)], vec![]);
+        assert_eq!(
+            serde_json::to_string(&setup).unwrap(),
+            r#"{"black":[[2,2]]}"#
+        );
+        let back: Setup = serde_json::from_str("{}").unwrap();
+        assert!(back.is_empty());
+    }
+}
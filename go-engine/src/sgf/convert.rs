@@ -2,7 +2,10 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+use crate::Point;
+use crate::annotation::{Annotation, Glyph, GlyphKind};
 use crate::game_tree::{GameTree, NodeId};
+use crate::setup::Setup;
 use crate::stone::Stone;
 use crate::turn::Turn;
 
@@ -29,6 +32,13 @@ pub struct SgfMetadata {
     pub komi: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub handicap: Option<u8>,
+    /// Freely placed handicap stones, written as root `AB[]`. Read only when
+    /// `HA[]` is at least 2; otherwise root `AB[]` is plain setup.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub handicap_stones: Vec<Point>,
+    /// Raw `RU[]` value; see [`crate::Ruleset::from_sgf`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rules: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub black_name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -37,6 +47,9 @@ pub struct SgfMetadata {
     pub game_name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub result: Option<String>,
+    /// Raw `DT[]` value, usually `YYYY-MM-DD`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub time_limit_secs: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -60,16 +73,22 @@ fn extract_metadata(sgf_tree: &sgf::GameTree) -> SgfMetadata {
                 }
                 Property::Komi(k) => meta.komi = Some(*k),
                 Property::Handicap(h) => meta.handicap = Some(*h),
+                Property::AddBlack(pts) => meta.handicap_stones.extend(pts),
+                Property::Rules(s) => meta.rules = Some(s.clone()),
                 Property::BlackName(s) => meta.black_name = Some(s.clone()),
                 Property::WhiteName(s) => meta.white_name = Some(s.clone()),
                 Property::GameName(s) => meta.game_name = Some(s.clone()),
                 Property::Result(s) => meta.result = Some(s.clone()),
+                Property::Date(s) => meta.date = Some(s.clone()),
                 Property::TimeLimitSeconds(t) => meta.time_limit_secs = Some(*t),
                 Property::OvertimeDescription(s) => meta.overtime = Some(s.clone()),
                 _ => {}
             }
         }
     }
+    if meta.handicap.is_none_or(|h| h < 2) {
+        meta.handicap_stones.clear();
+    }
 
     meta
 }
@@ -88,6 +107,66 @@ fn node_to_turn(node: &sgf::Node) -> Option<Turn> {
     None
 }
 
+/// Extract `AB`/`AW`/`AE`/`PL` setup properties from an SGF node.
+fn node_to_setup(node: &sgf::Node) -> Setup {
+    let mut setup = Setup::default();
+    for prop in &node.properties {
+        match prop {
+            Property::AddBlack(pts) => setup.black.extend(pts),
+            Property::AddWhite(pts) => setup.white.extend(pts),
+            Property::AddEmpty(pts) => setup.empty.extend(pts),
+            Property::PlayerToPlay(stone) => setup.to_play = Some(*stone),
+            _ => {}
+        }
+    }
+    setup
+}
+
+/// Extract comment, markup and evaluation properties from an SGF node.
+fn node_to_annotation(node: &sgf::Node) -> Annotation {
+    let mut ann = Annotation::default();
+    let markup = &mut ann.markup;
+    for prop in &node.properties {
+        let glyph = |kind, d: &sgf::Double| Glyph {
+            kind,
+            emphasized: *d == sgf::Double::Emphasized,
+        };
+        match prop {
+            Property::NodeName(s) => ann.name = Some(s.clone()),
+            Property::Comment(s) => ann.comment = Some(s.clone()),
+            Property::NodeValue(v) => ann.value = Some(*v),
+            Property::Circles(pts) => markup.circles.extend(pts),
+            Property::Triangles(pts) => markup.triangles.extend(pts),
+            Property::Squares(pts) => markup.squares.extend(pts),
+            Property::XMarks(pts) => markup.crosses.extend(pts),
+            Property::SelectedPoints(pts) => markup.selected.extend(pts),
+            Property::DimPoints(pts) => markup.dimmed.extend(pts),
+            Property::Labels(labels) => markup
+                .labels
+                .extend(labels.iter().map(|l| (l.point, l.text.clone()))),
+            Property::Arrows(pairs) => markup.arrows.extend(pairs.iter().map(|p| (p.from, p.to))),
+            Property::Lines(pairs) => markup.lines.extend(pairs.iter().map(|p| (p.from, p.to))),
+            Property::GoodForBlack(d) => ann.glyphs.push(glyph(GlyphKind::GoodForBlack, d)),
+            Property::GoodForWhite(d) => ann.glyphs.push(glyph(GlyphKind::GoodForWhite, d)),
+            Property::EvenPosition(d) => ann.glyphs.push(glyph(GlyphKind::Even, d)),
+            Property::UnclearPosition(d) => ann.glyphs.push(glyph(GlyphKind::Unclear, d)),
+            Property::Hotspot(d) => ann.glyphs.push(glyph(GlyphKind::Hotspot, d)),
+            Property::BadMove(d) => ann.glyphs.push(glyph(GlyphKind::BadMove, d)),
+            Property::Tesuji(d) => ann.glyphs.push(glyph(GlyphKind::Tesuji, d)),
+            Property::DoubtfulMove => ann.glyphs.push(Glyph {
+                kind: GlyphKind::Doubtful,
+                emphasized: false,
+            }),
+            Property::InterestingMove => ann.glyphs.push(Glyph {
+                kind: GlyphKind::Interesting,
+                emphasized: false,
+            }),
+            _ => {}
+        }
+    }
+    ann
+}
+
 /// Extract per-move time data from an SGF node's properties.
 fn node_to_move_time(node: &sgf::Node) -> Option<MoveTime> {
     let mut mt = MoveTime::default();
@@ -123,16 +202,82 @@ pub struct SgfConversion {
     pub move_times: HashMap<NodeId, MoveTime>,
 }
 
+/// One game of a multi-game SGF collection, for choosing which one to load.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SgfGameSummary {
+    /// Position of the game in the collection.
+    pub index: usize,
+    #[serde(flatten)]
+    pub metadata: SgfMetadata,
+    /// Number of moves along the main line.
+    pub move_count: usize,
+}
+
+/// Summarize each game in a collection from its root properties and main line.
+pub fn summarize_collection(collection: &sgf::Collection) -> Vec<SgfGameSummary> {
+    collection
+        .iter()
+        .enumerate()
+        .map(|(index, sgf_tree)| SgfGameSummary {
+            index,
+            metadata: extract_metadata(sgf_tree),
+            move_count: main_line_move_count(sgf_tree),
+        })
+        .collect()
+}
+
+fn main_line_move_count(sgf_tree: &sgf::GameTree) -> usize {
+    let mut count = 0;
+    let mut current = Some(sgf_tree);
+    while let Some(t) = current {
+        count += t.nodes.iter().filter(|n| node_to_turn(n).is_some()).count();
+        current = t.variations.first();
+    }
+    count
+}
+
 /// Convert an SGF game tree into an engine GameTree + metadata + per-move times.
 ///
 /// Walks the SGF tree recursively, extracting B/W move properties as Turns.
-/// Non-move nodes (root properties, comments, etc.) are skipped.
+/// Setup properties before the first move become the tree's starting position;
+/// later ones attach to the next move. Setup with no move after it is dropped.
+/// Comments, markup and glyphs attach to the node's move, or to the position
+/// before it when the node has no move.
 pub fn sgf_to_game_tree(sgf_tree: &sgf::GameTree) -> SgfConversion {
     let metadata = extract_metadata(sgf_tree);
     let mut tree = GameTree::new();
     let mut move_times = HashMap::new();
 
-    walk_sgf_sequence(sgf_tree, &mut tree, None, &mut move_times);
+    // Setup in the leading move-less nodes is the starting position.
+    let lead = sgf_tree
+        .nodes
+        .iter()
+        .take_while(|node| node_to_turn(node).is_none())
+        .count();
+    let mut root_setup = Setup::default();
+    for node in &sgf_tree.nodes[..lead] {
+        root_setup.merge(node_to_setup(node));
+        annotate(&mut tree, None, node_to_annotation(node));
+    }
+    tree.set_setup(root_setup);
+
+    walk_sgf_sequence(
+        &sgf_tree.nodes[lead..],
+        &sgf_tree.variations,
+        &mut tree,
+        None,
+        Setup::default(),
+        &mut move_times,
+    );
+
+    // Handicap stones given as AB leave White to move unless PL says otherwise.
+    if metadata.handicap.is_some_and(|h| h >= 2)
+        && !tree.setup().black.is_empty()
+        && tree.setup().to_play.is_none()
+    {
+        let setup = tree.setup().clone().with_to_play(Stone::White);
+        tree.set_setup(setup);
+    }
 
     SgfConversion {
         tree,
@@ -141,30 +286,53 @@ pub fn sgf_to_game_tree(sgf_tree: &sgf::GameTree) -> SgfConversion {
     }
 }
 
+/// Merge an annotation into a tree node (None = the starting position).
+fn annotate(tree: &mut GameTree, node_id: Option<NodeId>, annotation: Annotation) {
+    if annotation.is_empty() {
+        return;
+    }
+    let mut merged = tree.annotation(node_id).clone();
+    merged.merge(annotation);
+    tree.set_annotation(node_id, merged);
+}
+
 /// Walk an SGF GameTree's node sequence, adding turns to the engine tree.
+/// Setup is carried in `pending` until the next move picks it up.
 /// Returns the last NodeId added (or the parent if no moves were added).
 fn walk_sgf_sequence(
-    sgf_tree: &sgf::GameTree,
+    nodes: &[sgf::Node],
+    variations: &[sgf::GameTree],
     tree: &mut GameTree,
     parent: Option<NodeId>,
+    mut pending: Setup,
     move_times: &mut HashMap<NodeId, MoveTime>,
 ) -> Option<NodeId> {
     let mut current = parent;
 
     // Process sequential nodes
-    for node in &sgf_tree.nodes {
+    for node in nodes {
+        pending.merge(node_to_setup(node));
         if let Some(turn) = node_to_turn(node) {
-            let id = tree.add_child(current, turn);
+            let setup = std::mem::take(&mut pending);
+            let id = tree.add_child_with_setup(current, setup, turn);
             if let Some(mt) = node_to_move_time(node) {
                 move_times.insert(id, mt);
             }
             current = Some(id);
         }
+        annotate(tree, current, node_to_annotation(node));
     }
 
     // Process variations: each is a sub-GameTree branching from `current`
-    for variation in &sgf_tree.variations {
-        walk_sgf_sequence(variation, tree, current, move_times);
+    for variation in variations {
+        walk_sgf_sequence(
+            &variation.nodes,
+            &variation.variations,
+            tree,
+            current,
+            pending.clone(),
+            move_times,
+        );
     }
 
     current
@@ -192,6 +360,24 @@ fn build_sgf_tree(tree: &GameTree, meta: &SgfMetadata) -> sgf::GameTree {
     {
         root_props.push(Property::Handicap(h));
     }
+    let setup = tree.setup();
+    let mut add_black = meta.handicap_stones.clone();
+    add_black.extend(
+        setup
+            .black
+            .iter()
+            .filter(|pt| !meta.handicap_stones.contains(pt)),
+    );
+    push_setup_props(
+        &mut root_props,
+        &Setup {
+            black: add_black,
+            ..setup.clone()
+        },
+    );
+    if let Some(ref s) = meta.rules {
+        root_props.push(Property::Rules(s.clone()));
+    }
     if let Some(ref s) = meta.black_name {
         root_props.push(Property::BlackName(s.clone()));
     }
@@ -204,18 +390,22 @@ fn build_sgf_tree(tree: &GameTree, meta: &SgfMetadata) -> sgf::GameTree {
     if let Some(ref s) = meta.result {
         root_props.push(Property::Result(s.clone()));
     }
+    if let Some(ref s) = meta.date {
+        root_props.push(Property::Date(s.clone()));
+    }
     if let Some(t) = meta.time_limit_secs {
         root_props.push(Property::TimeLimitSeconds(t));
     }
     if let Some(ref s) = meta.overtime {
         root_props.push(Property::OvertimeDescription(s.clone()));
     }
+    push_annotation_props(&mut root_props, tree.annotation(None));
 
     let root_node = sgf::Node {
         properties: root_props,
     };
 
-    let root_children = tree.root_children();
+    let root_children = skip_handicap_moves(tree, &meta.handicap_stones);
     if root_children.is_empty() {
         return sgf::GameTree {
             nodes: vec![root_node],
@@ -243,6 +433,107 @@ fn build_sgf_tree(tree: &GameTree, meta: &SgfMetadata) -> sgf::GameTree {
     }
 }
 
+/// Append `AB`/`AW`/`AE`/`PL` for a setup, skipping empty ones.
+fn push_setup_props(props: &mut Vec<Property>, setup: &Setup) {
+    if !setup.black.is_empty() {
+        props.push(Property::AddBlack(setup.black.clone()));
+    }
+    if !setup.white.is_empty() {
+        props.push(Property::AddWhite(setup.white.clone()));
+    }
+    if !setup.empty.is_empty() {
+        props.push(Property::AddEmpty(setup.empty.clone()));
+    }
+    if let Some(stone) = setup.to_play {
+        props.push(Property::PlayerToPlay(stone));
+    }
+}
+
+/// Append comment, markup and glyph properties for an annotation.
+fn push_annotation_props(props: &mut Vec<Property>, ann: &Annotation) {
+    if let Some(ref s) = ann.name {
+        props.push(Property::NodeName(s.clone()));
+    }
+    if let Some(ref s) = ann.comment {
+        props.push(Property::Comment(s.clone()));
+    }
+    if let Some(v) = ann.value {
+        props.push(Property::NodeValue(v));
+    }
+    let m = &ann.markup;
+    let point_lists = [
+        (&m.circles, Property::Circles as fn(_) -> _),
+        (&m.triangles, Property::Triangles),
+        (&m.squares, Property::Squares),
+        (&m.crosses, Property::XMarks),
+        (&m.selected, Property::SelectedPoints),
+        (&m.dimmed, Property::DimPoints),
+    ];
+    for (pts, prop) in point_lists {
+        if !pts.is_empty() {
+            props.push(prop(pts.clone()));
+        }
+    }
+    if !m.labels.is_empty() {
+        let labels = m
+            .labels
+            .iter()
+            .map(|(point, text)| sgf::Label {
+                point: *point,
+                text: text.clone(),
+            })
+            .collect();
+        props.push(Property::Labels(labels));
+    }
+    let pairs = |pairs: &[(Point, Point)]| {
+        pairs
+            .iter()
+            .map(|&(from, to)| sgf::PointPair { from, to })
+            .collect()
+    };
+    if !m.arrows.is_empty() {
+        props.push(Property::Arrows(pairs(&m.arrows)));
+    }
+    if !m.lines.is_empty() {
+        props.push(Property::Lines(pairs(&m.lines)));
+    }
+    for glyph in &ann.glyphs {
+        let d = if glyph.emphasized {
+            sgf::Double::Emphasized
+        } else {
+            sgf::Double::Normal
+        };
+        props.push(match glyph.kind {
+            GlyphKind::GoodForBlack => Property::GoodForBlack(d),
+            GlyphKind::GoodForWhite => Property::GoodForWhite(d),
+            GlyphKind::Even => Property::EvenPosition(d),
+            GlyphKind::Unclear => Property::UnclearPosition(d),
+            GlyphKind::Hotspot => Property::Hotspot(d),
+            GlyphKind::BadMove => Property::BadMove(d),
+            GlyphKind::Tesuji => Property::Tesuji(d),
+            GlyphKind::Doubtful => Property::DoubtfulMove,
+            GlyphKind::Interesting => Property::InterestingMove,
+        });
+    }
+}
+
+/// Free handicap stones are stored as Black's opening moves but exported as `AB`.
+/// Returns the children to export from, past the main-line moves that placed them.
+fn skip_handicap_moves<'a>(tree: &'a GameTree, handicap_stones: &[Point]) -> &'a [NodeId] {
+    let mut children = tree.root_children();
+    for &point in handicap_stones {
+        let Some(&first) = children.first() else {
+            break;
+        };
+        let turn = &tree.node(first).turn;
+        if turn.stone != Stone::Black || turn.pos != Some(point) {
+            break;
+        }
+        children = tree.children_of(Some(first));
+    }
+    children
+}
+
 /// Build a line of SGF nodes from a starting engine node, following children[0].
 /// Returns (nodes_in_sequence, variations_at_end).
 fn build_sgf_line(tree: &GameTree, start: NodeId) -> (Vec<sgf::Node>, Vec<sgf::GameTree>) {
@@ -251,7 +542,14 @@ fn build_sgf_line(tree: &GameTree, start: NodeId) -> (Vec<sgf::Node>, Vec<sgf::G
 
     loop {
         let node = tree.node(current);
-        if let Some(sgf_node) = turn_to_sgf_node(&node.turn) {
+        // SGF does not mix setup and move properties in one node.
+        if !node.setup.is_empty() {
+            let mut properties = Vec::new();
+            push_setup_props(&mut properties, &node.setup);
+            nodes.push(sgf::Node { properties });
+        }
+        if let Some(mut sgf_node) = turn_to_sgf_node(&node.turn) {
+            push_annotation_props(&mut sgf_node.properties, &node.annotation);
             nodes.push(sgf_node);
         }
 
@@ -357,7 +655,7 @@ mod tests {
 
     #[test]
     fn metadata_extraction() {
-        let input = "(;FF[4]GM[1]SZ[13]KM[0.5]HA[2]PB[Alice]PW[Bob]GN[Test]RE[B+2.5]TM[1800]OT[5x30 byo-yomi])";
+        let input = "(;FF[4]GM[1]SZ[13]KM[0.5]HA[2]RU[Chinese]PB[Alice]PW[Bob]GN[Test]RE[B+2.5]TM[1800]OT[5x30 byo-yomi])";
         let collection = sgf::parse(input).unwrap();
         let conv = sgf_to_game_tree(&collection[0]);
         let meta = &conv.metadata;
@@ -366,6 +664,7 @@ mod tests {
         assert_eq!(meta.rows, 13);
         assert_eq!(meta.komi, Some(0.5));
         assert_eq!(meta.handicap, Some(2));
+        assert_eq!(meta.rules.as_deref(), Some("Chinese"));
         assert_eq!(meta.black_name.as_deref(), Some("Alice"));
         assert_eq!(meta.white_name.as_deref(), Some("Bob"));
         assert_eq!(meta.game_name.as_deref(), Some("Test"));
@@ -374,6 +673,24 @@ mod tests {
         assert_eq!(meta.overtime.as_deref(), Some("5x30 byo-yomi"));
     }
 
+    #[test]
+    fn add_black_is_handicap_only_with_ha() {
+        let setup = sgf::parse("(;FF[4]GM[1]SZ[9]AB[cc][gg]AW[ee];B[dd])").unwrap();
+        let conv = sgf_to_game_tree(&setup[0]);
+        assert!(conv.metadata.handicap_stones.is_empty());
+        assert_eq!(conv.tree.setup().black, vec![(2, 2), (6, 6)]);
+
+        let handicap = sgf::parse("(;FF[4]GM[1]SZ[9]HA[2]AB[cc][gg];W[dd])").unwrap();
+        let conv = sgf_to_game_tree(&handicap[0]);
+        assert_eq!(conv.metadata.handicap_stones, vec![(2, 2), (6, 6)]);
+        assert!(
+            summarize_collection(&setup)[0]
+                .metadata
+                .handicap_stones
+                .is_empty()
+        );
+    }
+
     #[test]
     fn pass_moves() {
         let input = "(;FF[4]GM[1]SZ[19];B[dd];W[];B[])";
@@ -397,6 +714,19 @@ mod tests {
         assert!(output.contains("B[]"));
     }
 
+    #[test]
+    fn rectangular_board_round_trip() {
+        let input = "(;FF[4]GM[1]SZ[9:13];B[hl];W[ab])";
+        let collection = sgf::parse(input).unwrap();
+        let conv = sgf_to_game_tree(&collection[0]);
+        assert_eq!((conv.metadata.cols, conv.metadata.rows), (9, 13));
+        assert_eq!(conv.tree.moves_to(1)[0].pos, Some((7, 11)));
+
+        let output = game_tree_to_sgf(&conv.tree, &conv.metadata);
+        assert!(output.contains("SZ[9:13]"));
+        assert!(output.contains("B[hl]"));
+    }
+
     #[test]
     fn default_19x19_when_sz_absent() {
         let input = "(;FF[4]GM[1];B[pd])";
@@ -423,6 +753,138 @@ mod tests {
         assert!(re.tree.is_empty());
     }
 
+    #[test]
+    fn free_handicap_moves_export_as_add_black() {
+        let moves = vec![
+            Turn::play(Stone::Black, (2, 2)),
+            Turn::play(Stone::Black, (6, 6)),
+            Turn::play(Stone::White, (4, 4)),
+        ];
+        let tree = GameTree::from_moves(&moves);
+        let meta = SgfMetadata {
+            cols: 9,
+            rows: 9,
+            handicap: Some(2),
+            handicap_stones: vec![(2, 2), (6, 6)],
+            ..Default::default()
+        };
+        let output = game_tree_to_sgf(&tree, &meta);
+
+        assert!(output.contains("HA[2]"));
+        assert!(output.contains("AB[cc][gg]"));
+        assert!(!output.contains(";B[cc]"));
+        assert!(output.contains("W[ee]"));
+
+        let re = sgf_to_game_tree(&sgf::parse(&output).unwrap()[0]);
+        assert_eq!(re.metadata.handicap_stones, vec![(2, 2), (6, 6)]);
+        assert_eq!(re.tree.len(), 1);
+    }
+
+    #[test]
+    fn setup_properties_round_trip() {
+        let input = "(;FF[4]GM[1]SZ[9]AB[cc][dc]AW[cd]PL[W](;W[dd];AE[cc]AB[ee];W[cc])(;W[ed]))";
+        let conv = sgf_to_game_tree(&sgf::parse(input).unwrap()[0]);
+
+        let root = conv.tree.setup();
+        assert_eq!(root.black, vec![(2, 2), (3, 2)]);
+        assert_eq!(root.white, vec![(2, 3)]);
+        assert_eq!(root.to_play, Some(Stone::White));
+        assert_eq!(conv.tree.len(), 3);
+        let first = conv.tree.root_children()[0];
+        let edited = conv.tree.children_of(Some(first))[0];
+        assert_eq!(conv.tree.node(edited).setup.empty, vec![(2, 2)]);
+        assert_eq!(conv.tree.node(edited).setup.black, vec![(4, 4)]);
+
+        let output = game_tree_to_sgf(&conv.tree, &conv.metadata);
+        assert!(output.contains("AB[cc][dc]"));
+        assert!(output.contains("PL[W]"));
+        assert!(output.contains("AE[cc]"));
+
+        let re = sgf_to_game_tree(&sgf::parse(&output).unwrap()[0]);
+        assert_eq!(re.tree.setup(), conv.tree.setup());
+        assert_eq!(
+            re.tree.setups_to(Some(edited)),
+            conv.tree.setups_to(Some(edited))
+        );
+    }
+
+    #[test]
+    fn handicap_add_black_leaves_white_to_play() {
+        let input = "(;FF[4]GM[1]SZ[9]HA[2]AB[cg][gc];W[ee])";
+        let conv = sgf_to_game_tree(&sgf::parse(input).unwrap()[0]);
+        assert_eq!(conv.tree.setup().black, vec![(2, 6), (6, 2)]);
+        assert_eq!(conv.tree.setup().to_play, Some(Stone::White));
+
+        // Exporting with the stones also in the metadata writes them once.
+        let output = game_tree_to_sgf(&conv.tree, &conv.metadata);
+        assert_eq!(output.matches("[cg]").count(), 1);
+    }
+
+    #[test]
+    fn annotations_round_trip() {
+        let input = "(;FF[4]GM[1]SZ[9]C[Black to live]AB[cc]\
+            (;B[dd]N[Key move]C[Correct]TE[2]CR[ee]LB[ff:A]AR[aa:bb]\
+            ;W[de]GW[1];C[Trailing note])\
+            (;B[ed]BM[1]DO[]TR[cc]))";
+        let conv = sgf_to_game_tree(&sgf::parse(input).unwrap()[0]);
+
+        assert_eq!(
+            conv.tree.annotation(None).comment.as_deref(),
+            Some("Black to live")
+        );
+        let key = conv.tree.root_children()[0];
+        let ann = conv.tree.annotation(Some(key));
+        assert_eq!(ann.name.as_deref(), Some("Key move"));
+        assert_eq!(ann.comment.as_deref(), Some("Correct"));
+        assert_eq!(
+            ann.glyphs,
+            vec![Glyph {
+                kind: GlyphKind::Tesuji,
+                emphasized: true
+            }]
+        );
+        assert_eq!(ann.markup.circles, vec![(4, 4)]);
+        assert_eq!(ann.markup.labels, vec![((5, 5), "A".to_string())]);
+        assert_eq!(ann.markup.arrows, vec![((0, 0), (1, 1))]);
+
+        // A move-less node's comment lands on the move before it.
+        let reply = conv.tree.children_of(Some(key))[0];
+        let reply_ann = conv.tree.annotation(Some(reply));
+        assert_eq!(reply_ann.comment.as_deref(), Some("Trailing note"));
+        assert_eq!(reply_ann.glyphs[0].kind, GlyphKind::GoodForWhite);
+
+        let output = game_tree_to_sgf(&conv.tree, &conv.metadata);
+        let re = sgf_to_game_tree(&sgf::parse(&output).unwrap()[0]);
+        assert_eq!(re.tree.annotation(None), conv.tree.annotation(None));
+        for id in 0..conv.tree.len() {
+            assert_eq!(re.tree.annotation(Some(id)), conv.tree.annotation(Some(id)));
+        }
+        assert!(output.contains("DO[]"));
+    }
+
+    #[test]
+    fn summarize_multi_game_collection() {
+        let input = "(;GM[1]SZ[19]PB[Alice]PW[Bob]DT[2024-01-02]RE[B+R];B[pd];W[dp](;B[pp];W[dd])(;B[dd]))\
+                     (;GM[1]SZ[9]PB[Carol]PW[Dan];B[ee])";
+        let collection = sgf::parse(input).unwrap();
+        let games = summarize_collection(&collection);
+
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[0].index, 0);
+        assert_eq!(games[0].metadata.black_name.as_deref(), Some("Alice"));
+        assert_eq!(games[0].metadata.date.as_deref(), Some("2024-01-02"));
+        assert_eq!(games[0].metadata.result.as_deref(), Some("B+R"));
+        assert_eq!(games[0].move_count, 4);
+        assert_eq!(games[1].index, 1);
+        assert_eq!(games[1].metadata.cols, 9);
+        assert_eq!(games[1].metadata.white_name.as_deref(), Some("Dan"));
+        assert_eq!(games[1].move_count, 1);
+
+        let json = serde_json::to_value(&games[1]).unwrap();
+        assert_eq!(json["cols"], 9);
+        assert_eq!(json["move_count"], 1);
+    }
+
     #[test]
     fn metadata_round_trip() {
         let meta = SgfMetadata {
@@ -430,10 +892,13 @@ mod tests {
             rows: 19,
             komi: Some(6.5),
             handicap: Some(3),
+            handicap_stones: Vec::new(),
+            rules: Some("AGA".into()),
             black_name: Some("Alice".into()),
             white_name: Some("Bob".into()),
             game_name: Some("Game 1".into()),
             result: Some("W+R".into()),
+            date: Some("2024-03-09".into()),
             time_limit_secs: Some(1800.0),
             overtime: Some("5x30 byo-yomi".into()),
         };
@@ -444,10 +909,12 @@ mod tests {
 
         assert_eq!(re.metadata.komi, meta.komi);
         assert_eq!(re.metadata.handicap, meta.handicap);
+        assert_eq!(re.metadata.rules, meta.rules);
         assert_eq!(re.metadata.black_name, meta.black_name);
         assert_eq!(re.metadata.white_name, meta.white_name);
         assert_eq!(re.metadata.game_name, meta.game_name);
         assert_eq!(re.metadata.result, meta.result);
+        assert_eq!(re.metadata.date, meta.date);
         assert_eq!(re.metadata.time_limit_secs, meta.time_limit_secs);
         assert_eq!(re.metadata.overtime, meta.overtime);
     }
@@ -0,0 +1,301 @@
+use crate::engine::{Engine, GameState};
+use crate::game_tree::GameTree;
+use crate::goban::Goban;
+use crate::ko::Ko;
+use crate::stone::Stone;
+use crate::turn::Turn;
+use crate::{Point, zobrist};
+
+/// One of the eight rotations and reflections of a board.
+///
+/// Quarter turns and the diagonal flips swap the board's sides, so on a
+/// rectangular board they produce a board of the transposed shape.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Symmetry {
+    Identity,
+    /// A quarter turn clockwise.
+    Rotate90,
+    Rotate180,
+    /// A quarter turn counterclockwise.
+    Rotate270,
+    /// Mirror left to right.
+    FlipHorizontal,
+    /// Mirror top to bottom.
+    FlipVertical,
+    /// Mirror across the top-left to bottom-right diagonal.
+    Transpose,
+    /// Mirror across the top-right to bottom-left diagonal.
+    AntiTranspose,
+}
+
+impl Symmetry {
+    pub const ALL: [Symmetry; 8] = [
+        Symmetry::Identity,
+        Symmetry::Rotate90,
+        Symmetry::Rotate180,
+        Symmetry::Rotate270,
+        Symmetry::FlipHorizontal,
+        Symmetry::FlipVertical,
+        Symmetry::Transpose,
+        Symmetry::AntiTranspose,
+    ];
+
+    pub fn swaps_axes(self) -> bool {
+        matches!(
+            self,
+            Symmetry::Rotate90
+                | Symmetry::Rotate270
+                | Symmetry::Transpose
+                | Symmetry::AntiTranspose
+        )
+    }
+
+    /// Whether a `cols` x `rows` board keeps its shape under this symmetry.
+    pub fn preserves_shape(self, cols: u8, rows: u8) -> bool {
+        cols == rows || !self.swaps_axes()
+    }
+
+    /// The symmetry that undoes this one.
+    pub fn inverse(self) -> Symmetry {
+        match self {
+            Symmetry::Rotate90 => Symmetry::Rotate270,
+            Symmetry::Rotate270 => Symmetry::Rotate90,
+            other => other,
+        }
+    }
+
+    /// Dimensions of a `cols` x `rows` board after the transform.
+    pub fn dimensions(self, cols: u8, rows: u8) -> (u8, u8) {
+        if self.swaps_axes() {
+            (rows, cols)
+        } else {
+            (cols, rows)
+        }
+    }
+
+    /// Where `point` on a `cols` x `rows` board lands.
+    pub fn point(self, (col, row): Point, cols: u8, rows: u8) -> Point {
+        let last_col = cols - 1;
+        let last_row = rows - 1;
+        match self {
+            Symmetry::Identity => (col, row),
+            Symmetry::Rotate90 => (last_row - row, col),
+            Symmetry::Rotate180 => (last_col - col, last_row - row),
+            Symmetry::Rotate270 => (row, last_col - col),
+            Symmetry::FlipHorizontal => (last_col - col, row),
+            Symmetry::FlipVertical => (col, last_row - row),
+            Symmetry::Transpose => (row, col),
+            Symmetry::AntiTranspose => (last_row - row, last_col - col),
+        }
+    }
+
+    /// Rearrange a row-major per-point array, such as a board or policy
+    /// logits. Entries past `cols * rows`, like a trailing pass logit, stay
+    /// where they are.
+    pub fn plane<T: Copy>(self, values: &[T], cols: u8, rows: u8) -> Vec<T> {
+        let (out_cols, _) = self.dimensions(cols, rows);
+        let area = cols as usize * rows as usize;
+        let mut out = values.to_vec();
+        for (i, &value) in values.iter().take(area).enumerate() {
+            let point = ((i % cols as usize) as u8, (i / cols as usize) as u8);
+            let (col, row) = self.point(point, cols, rows);
+            out[row as usize * out_cols as usize + col as usize] = value;
+        }
+        out
+    }
+
+    pub fn turn(self, turn: &Turn, cols: u8, rows: u8) -> Turn {
+        turn.map_points(&|point| self.point(point, cols, rows))
+    }
+
+    /// Transform every move, setup and markup point of a tree recorded on a
+    /// `cols` x `rows` board.
+    pub fn game_tree(self, tree: &GameTree, cols: u8, rows: u8) -> GameTree {
+        tree.map_points(&|point| self.point(point, cols, rows))
+    }
+
+    /// Transform the stones, captures and ko point, keeping the ruleset and
+    /// topology. The superko history holds hashes rather than boards, so it
+    /// cannot be carried over and the result starts a fresh one.
+    pub fn goban(self, goban: &Goban) -> Goban {
+        let (cols, rows) = (goban.cols(), goban.rows());
+        let (out_cols, out_rows) = self.dimensions(cols, rows);
+        let ko = goban.ko().as_ref().map(|ko| {
+            let (col, row) = self.point((ko.pos.0 as u8, ko.pos.1 as u8), cols, rows);
+            Ko {
+                pos: (col as i8, row as i8),
+                illegal: ko.illegal,
+            }
+        });
+        Goban::from_state(GameState {
+            board: self.plane(goban.board(), cols, rows),
+            cols: out_cols,
+            rows: out_rows,
+            captures: goban.captures().clone(),
+            ko,
+            last_move: None,
+        })
+        .with_ruleset(*goban.ruleset())
+        .with_topology(goban.topology())
+    }
+
+    /// [`Engine::position_hash`] of the engine's position after this
+    /// transform, computed without building the transformed board.
+    pub fn position_hash(self, engine: &Engine) -> u64 {
+        let (cols, rows) = (engine.cols(), engine.rows());
+        let (out_cols, out_rows) = self.dimensions(cols, rows);
+        let index = |point: Point| {
+            let (col, row) = self.point(point, cols, rows);
+            row as usize * out_cols as usize + col as usize
+        };
+
+        let mut hash = zobrist::board_key(out_cols, out_rows, engine.topology());
+        for (i, &value) in engine.board().iter().enumerate() {
+            if let Some(stone) = Stone::from_int(value) {
+                let point = ((i % cols as usize) as u8, (i / cols as usize) as u8);
+                hash ^= zobrist::stone_key(index(point), stone);
+            }
+        }
+        if engine.current_turn_stone() == Stone::White {
+            hash ^= zobrist::white_to_play_key();
+        }
+        if let Some(ko) = engine.ko() {
+            hash ^= zobrist::ko_key(index((ko.pos.0 as u8, ko.pos.1 as u8)));
+        }
+        hash
+    }
+}
+
+/// Orientation-independent position hash: the smallest
+/// [`Symmetry::position_hash`] among the symmetries that keep the board's
+/// shape, with the symmetry that produced it. Positions that are rotations
+/// or reflections of each other share the hash.
+pub fn canonical_position_hash(engine: &Engine) -> (u64, Symmetry) {
+    Symmetry::ALL
+        .into_iter()
+        .filter(|symmetry| symmetry.preserves_shape(engine.cols(), engine.rows()))
+        .map(|symmetry| (symmetry.position_hash(engine), symmetry))
+        .min_by_key(|&(hash, _)| hash)
+        .expect("identity always preserves the shape")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::annotation::{Annotation, Markup};
+    use crate::setup::Setup;
+
+    fn engine_with(cols: u8, rows: u8, moves: &[Turn]) -> Engine {
+        Engine::with_moves(cols, rows, moves.to_vec())
+    }
+
+    #[test]
+    fn inverse_undoes_every_symmetry() {
+        for symmetry in Symmetry::ALL {
+            let (cols, rows) = symmetry.dimensions(9, 13);
+            for point in [(0, 0), (8, 0), (3, 12), (5, 7)] {
+                let moved = symmetry.point(point, 9, 13);
+                assert!(moved.0 < cols && moved.1 < rows, "{symmetry:?}");
+                assert_eq!(symmetry.inverse().point(moved, cols, rows), point);
+            }
+        }
+    }
+
+    #[test]
+    fn quarter_turn_moves_corners_clockwise() {
+        assert_eq!(Symmetry::Rotate90.point((0, 0), 19, 19), (18, 0));
+        assert_eq!(Symmetry::Rotate90.point((18, 0), 19, 19), (18, 18));
+        assert_eq!(Symmetry::Rotate90.point((2, 0), 9, 13), (12, 2));
+    }
+
+    #[test]
+    fn plane_keeps_trailing_entries() {
+        let logits = [0, 1, 2, 3, 4, 5, 6];
+        assert_eq!(
+            Symmetry::Transpose.plane(&logits, 3, 2),
+            vec![0, 3, 1, 4, 2, 5, 6]
+        );
+    }
+
+    #[test]
+    fn goban_transform_matches_transformed_moves() {
+        let moves = [
+            Turn::play(Stone::Black, (2, 3)),
+            Turn::play(Stone::White, (4, 1)),
+            Turn::play(Stone::Black, (0, 6)),
+        ];
+        for symmetry in Symmetry::ALL {
+            let (cols, rows) = symmetry.dimensions(7, 9);
+            let turned: Vec<Turn> = moves.iter().map(|t| symmetry.turn(t, 7, 9)).collect();
+            let expected = Goban::with_moves(cols, rows, &turned).unwrap();
+            let actual = symmetry.goban(&Goban::with_moves(7, 9, &moves).unwrap());
+            assert_eq!(actual.board(), expected.board(), "{symmetry:?}");
+            assert_eq!((actual.cols(), actual.rows()), (cols, rows));
+        }
+    }
+
+    #[test]
+    fn position_hash_matches_the_transformed_engine() {
+        let moves = [
+            Turn::play(Stone::Black, (2, 2)),
+            Turn::play(Stone::White, (6, 3)),
+            Turn::play(Stone::Black, (3, 7)),
+        ];
+        let engine = engine_with(9, 9, &moves);
+        assert_eq!(
+            Symmetry::Identity.position_hash(&engine),
+            engine.position_hash()
+        );
+        for symmetry in Symmetry::ALL {
+            let turned: Vec<Turn> = moves.iter().map(|t| symmetry.turn(t, 9, 9)).collect();
+            let transformed = engine_with(9, 9, &turned);
+            assert_eq!(
+                symmetry.position_hash(&engine),
+                transformed.position_hash(),
+                "{symmetry:?}"
+            );
+            assert_eq!(
+                canonical_position_hash(&engine).0,
+                canonical_position_hash(&transformed).0
+            );
+        }
+    }
+
+    #[test]
+    fn canonical_hash_keeps_rectangular_shape() {
+        let engine = engine_with(9, 13, &[Turn::play(Stone::Black, (1, 2))]);
+        let (_, symmetry) = canonical_position_hash(&engine);
+        assert!(!symmetry.swaps_axes());
+
+        let other = engine_with(9, 13, &[Turn::play(Stone::Black, (1, 3))]);
+        assert_ne!(
+            canonical_position_hash(&engine).0,
+            canonical_position_hash(&other).0
+        );
+    }
+
+    #[test]
+    fn game_tree_transform_moves_setup_and_markup() {
+        let mut tree = GameTree::with_setup(Setup::stones(vec![(0, 0)], vec![(1, 0)]));
+        let node = tree.add_child(None, Turn::play(Stone::Black, (2, 1)));
+        tree.set_annotation(
+            Some(node),
+            Annotation {
+                markup: Markup {
+                    arrows: vec![((0, 0), (2, 2))],
+                    labels: vec![((1, 2), "A".into())],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+
+        let flipped = Symmetry::FlipHorizontal.game_tree(&tree, 3, 3);
+        assert_eq!(flipped.setup().black, vec![(2, 0)]);
+        assert_eq!(flipped.setup().white, vec![(1, 0)]);
+        assert_eq!(flipped.node(node).turn.pos, Some((0, 1)));
+        let markup = &flipped.annotation(Some(node)).markup;
+        assert_eq!(markup.arrows, vec![((2, 0), (0, 2))]);
+        assert_eq!(markup.labels, vec![((1, 2), "A".to_string())]);
+    }
+}
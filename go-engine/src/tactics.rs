@@ -0,0 +1,272 @@
+use crate::Point;
+use crate::goban::Goban;
+use crate::stone::Stone;
+
+/// Bounds on a tactical read. A read that runs out of budget is treated as
+/// an escape, so a reported capture is always one the search proved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadingLimits {
+    /// Positions visited before giving up.
+    pub max_nodes: u32,
+    /// Moves deep a single line may go.
+    pub max_depth: u8,
+    /// Attacking moves per line that do not give atari. Zero reads ladders only.
+    pub max_loose_moves: u8,
+}
+
+impl Default for ReadingLimits {
+    fn default() -> Self {
+        Self {
+            max_nodes: 4000,
+            max_depth: 100,
+            max_loose_moves: 2,
+        }
+    }
+}
+
+/// The attacking move that captures the chain at `point` in a ladder, with
+/// the attacker to play. Every attacking move must give atari; the defender
+/// may extend, capture an adjacent chain in atari, or tenuki while it still
+/// has two liberties.
+pub fn ladder_capture(goban: &Goban, point: Point, limits: ReadingLimits) -> Option<Point> {
+    Reader::new(goban, point, limits)?.attack(0, 0)
+}
+
+/// Like [`ladder_capture`], but the attacker may also play up to
+/// `limits.max_loose_moves` quiet moves next to the chain's liberties,
+/// which catches nets (geta) that a ladder alone misses.
+pub fn net_capture(goban: &Goban, point: Point, limits: ReadingLimits) -> Option<Point> {
+    Reader::new(goban, point, limits)?.attack(0, limits.max_loose_moves)
+}
+
+/// Whether the chain at `point` survives a ladder or net attack when its
+/// owner moves first. An empty point has nothing to save and returns false.
+pub fn can_escape(goban: &Goban, point: Point, limits: ReadingLimits) -> bool {
+    Reader::new(goban, point, limits)
+        .is_some_and(|mut reader| reader.defend(0, limits.max_loose_moves))
+}
+
+/// Whether `stone` playing at `point` puts an adjacent chain in atari that
+/// cannot escape. Illegal moves and moves that give no atari do not work.
+pub fn atari_works(goban: &Goban, point: Point, stone: Stone, limits: ReadingLimits) -> bool {
+    let mut goban = goban.clone();
+    if goban.play_in_place(point, stone).is_err() {
+        return false;
+    }
+    goban.neighbors(point).into_iter().any(|n| {
+        goban.stone_at(n) == Some(stone.opp())
+            && goban.liberty_count(n) == 1
+            && !can_escape(&goban, n, limits)
+    })
+}
+
+/// Depth-first capture search on a private copy of the board. Moves are
+/// made and taken back in place, so a read allocates little beyond the
+/// candidate lists.
+struct Reader {
+    goban: Goban,
+    target: Point,
+    defender: Stone,
+    limits: ReadingLimits,
+    nodes: u32,
+}
+
+impl Reader {
+    fn new(goban: &Goban, target: Point, limits: ReadingLimits) -> Option<Self> {
+        let defender = goban.stone_at(target)?;
+        Some(Self {
+            goban: goban.clone(),
+            target,
+            defender,
+            limits,
+            nodes: 0,
+        })
+    }
+
+    /// The capturing move with the attacker to play, if any.
+    fn attack(&mut self, depth: u8, loose_moves: u8) -> Option<Point> {
+        let attacker = self.defender.opp();
+        let liberties = self.goban.liberties(self.target);
+        if let [last] = liberties[..] {
+            return self.goban.is_legal_move(last, attacker).then_some(last);
+        }
+        if liberties.len() > 2 || !self.visit(depth) {
+            return None;
+        }
+
+        let mut moves = liberties.clone();
+        if loose_moves > 0 {
+            for &liberty in &liberties {
+                for n in self.goban.neighbors(liberty) {
+                    if self.goban.stone_at(n).is_none() && !moves.contains(&n) {
+                        moves.push(n);
+                    }
+                }
+            }
+        }
+
+        for point in moves {
+            let Ok(undo) = self.goban.make_move(point, attacker) else {
+                continue;
+            };
+            let remaining = loose_moves - u8::from(!liberties.contains(&point));
+            let escaped = self.defend(depth + 1, remaining);
+            self.goban.unmake_move(undo);
+            if !escaped {
+                return Some(point);
+            }
+        }
+        None
+    }
+
+    /// Whether the target lives with the defender to play.
+    fn defend(&mut self, depth: u8, loose_moves: u8) -> bool {
+        let liberties = self.goban.liberties(self.target);
+        if liberties.len() > 2 || !self.visit(depth) {
+            return true;
+        }
+
+        let mut moves = self.counter_moves(liberties.len() == 2);
+        for &liberty in &liberties {
+            if !moves.contains(&liberty) {
+                moves.push(liberty);
+            }
+        }
+
+        for point in moves {
+            let Ok(undo) = self.goban.make_move(point, self.defender) else {
+                continue;
+            };
+            // Suicide rules may let the defender take its own chain off.
+            let escaped = self.goban.stone_at(self.target).is_some()
+                && self.attack(depth + 1, loose_moves).is_none();
+            self.goban.unmake_move(undo);
+            if escaped {
+                return true;
+            }
+        }
+
+        // Extending can be self-atari in a seki, so with two liberties the
+        // defender may also leave the chain alone.
+        if liberties.len() < 2 {
+            return false;
+        }
+        let undo = self.goban.make_pass(self.defender);
+        let escaped = self.attack(depth + 1, loose_moves).is_none();
+        self.goban.unmake_move(undo);
+        escaped
+    }
+
+    /// Captures of attacking chains next to the target, plus counter-ataris
+    /// on two-liberty attackers when `ataris` is set.
+    fn counter_moves(&self, ataris: bool) -> Vec<Point> {
+        let attacker = self.defender.opp();
+        let mut visited = vec![false; self.goban.board().len()];
+        let mut moves = Vec::new();
+        for stone in self.goban.chain(self.target) {
+            for n in self.goban.neighbors(stone) {
+                if self.goban.stone_at(n) != Some(attacker) {
+                    continue;
+                }
+                let chain = self.goban.chain_from(n, &mut visited);
+                if chain.is_empty() {
+                    continue;
+                }
+                let liberties = self.goban.chain_liberties(&chain);
+                if liberties.len() == 1 || (ataris && liberties.len() == 2) {
+                    for liberty in liberties {
+                        if !moves.contains(&liberty) {
+                            moves.push(liberty);
+                        }
+                    }
+                }
+            }
+        }
+        moves
+    }
+
+    fn visit(&mut self, depth: u8) -> bool {
+        self.nodes += 1;
+        self.nodes <= self.limits.max_nodes && depth < self.limits.max_depth
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::goban_from_layout;
+
+    const LADDER: [&str; 9] = [
+        "+++++++++",
+        "+++++++++",
+        "+++++++++",
+        "+++++++++",
+        "+++B+++++",
+        "+++BW++++",
+        "++++B++++",
+        "+++++++++",
+        "+++++++++",
+    ];
+
+    fn with_stone(layout: &[&str], (col, row): Point, stone: char) -> Goban {
+        let mut rows: Vec<String> = layout.iter().map(|row| row.to_string()).collect();
+        rows[row as usize].replace_range(col as usize..col as usize + 1, &stone.to_string());
+        let rows: Vec<&str> = rows.iter().map(String::as_str).collect();
+        goban_from_layout(&rows)
+    }
+
+    #[test]
+    fn ladder_runs_to_the_edge() {
+        let goban = goban_from_layout(&LADDER);
+        let limits = ReadingLimits::default();
+        assert_eq!(ladder_capture(&goban, (4, 5), limits), Some((5, 5)));
+        assert!(atari_works(&goban, (5, 5), Stone::Black, limits));
+        assert!(!atari_works(&goban, (4, 4), Stone::Black, limits));
+        // Moving first, White gets a third liberty straight away.
+        assert!(can_escape(&goban, (4, 5), limits));
+    }
+
+    #[test]
+    fn ladder_breaker_lets_the_chain_run() {
+        let goban = with_stone(&LADDER, (7, 1), 'W');
+        let limits = ReadingLimits::default();
+        assert_eq!(ladder_capture(&goban, (4, 5), limits), None);
+        assert!(!atari_works(&goban, (5, 5), Stone::Black, limits));
+    }
+
+    #[test]
+    fn net_catches_what_a_ladder_misses() {
+        // White stones in every direction break the ladder.
+        let goban = goban_from_layout(&[
+            "+++++++++",
+            "+W+++++W+",
+            "+++++++++",
+            "+++B+++++",
+            "+++BW++++",
+            "++++BB+++",
+            "+++++++++",
+            "+W+++++W+",
+            "+++++++++",
+        ]);
+        let limits = ReadingLimits::default();
+        assert_eq!(ladder_capture(&goban, (4, 4), limits), None);
+        assert_eq!(net_capture(&goban, (4, 4), limits), Some((5, 3)));
+        let ladders_only = ReadingLimits {
+            max_loose_moves: 0,
+            ..limits
+        };
+        assert_eq!(net_capture(&goban, (4, 4), ladders_only), None);
+        assert!(can_escape(&goban, (4, 4), limits));
+    }
+
+    #[test]
+    fn capturing_an_attacker_gets_out_of_atari() {
+        let goban = goban_from_layout(&["WB+++", "B++++", "+++++", "+++++", "+++++"]);
+        let limits = ReadingLimits::default();
+        // The corner stone is in atari, but so is the Black stone at (1, 0).
+        assert!(!can_escape(&goban, (0, 0), limits));
+        let goban = goban_from_layout(&["WB+++", "BW+++", "W++++", "+++++", "+++++"]);
+        assert!(can_escape(&goban, (1, 1), limits));
+        assert!(!can_escape(&goban, (4, 4), limits));
+    }
+}
@@ -1,7 +1,9 @@
 use std::collections::HashSet;
 
+use crate::board::Board;
 use crate::goban::Goban;
 use crate::stone::Stone;
+use crate::tactics::{self, ReadingLimits};
 use crate::{GameState, Point};
 
 use super::alive::find_unconditionally_alive;
@@ -53,145 +55,52 @@ impl Rng {
 // PlayoutBoard — lightweight mutable board for fast random playouts
 // ---------------------------------------------------------------------------
 
+/// Random-playout view of the incremental board: moves are checked against
+/// the chain counters and applied in place, with no ko or superko history.
 struct PlayoutBoard {
-    data: Vec<i8>,
-    cols: usize,
-    rows: usize,
+    board: Board,
 }
 
 impl PlayoutBoard {
     fn from_goban(goban: &Goban) -> Self {
         Self {
-            data: goban.board().to_vec(),
-            cols: goban.cols() as usize,
-            rows: goban.rows() as usize,
+            board: goban.core().clone(),
         }
     }
 
     #[inline]
     fn size(&self) -> usize {
-        self.cols * self.rows
+        self.board.len()
     }
 
     #[inline]
     fn get(&self, v: usize) -> i8 {
-        self.data[v]
+        self.board.get(v)
     }
 
-    /// 4-connected neighbors as flat indices.
+    /// 4-connected neighbors as flat indices, wrapping around on a torus.
     fn neighbors(&self, v: usize) -> arrayvec::ArrayVec<usize, 4> {
-        let mut result = arrayvec::ArrayVec::new();
-        let x = v % self.cols;
-        let y = v / self.cols;
-        if x > 0 {
-            result.push(v - 1);
-        }
-        if x + 1 < self.cols {
-            result.push(v + 1);
-        }
-        if y > 0 {
-            result.push(v - self.cols);
-        }
-        if y + 1 < self.rows {
-            result.push(v + self.cols);
-        }
-        result
-    }
-
-    /// Does the chain containing `v` have at least one liberty? Early-exit DFS.
-    fn has_liberties(&self, v: usize, visited: &mut [bool]) -> bool {
-        let sign = self.data[v];
-        let mut stack = vec![v];
-        while let Some(u) = stack.pop() {
-            if visited[u] {
-                continue;
-            }
-            visited[u] = true;
-            for n in self.neighbors(u) {
-                if self.data[n] == 0 {
-                    return true;
-                }
-                if self.data[n] == sign && !visited[n] {
-                    stack.push(n);
-                }
-            }
-        }
-        false
-    }
-
-    /// Flood-fill chain from `v`.
-    fn get_chain(&self, v: usize) -> Vec<usize> {
-        let sign = self.data[v];
-        let mut visited = vec![false; self.size()];
-        let mut chain = Vec::new();
-        let mut stack = vec![v];
-        while let Some(u) = stack.pop() {
-            if visited[u] {
-                continue;
-            }
-            visited[u] = true;
-            chain.push(u);
-            for n in self.neighbors(u) {
-                if self.data[n] == sign && !visited[n] {
-                    stack.push(n);
-                }
-            }
-        }
-        chain
+        self.board.neighbors(v)
     }
 
-    /// Try to make a pseudo-legal move. Returns Some(captured_vertices) on success,
-    /// None if the move is rejected (eye fill, suicide, ko-like recapture).
-    fn make_pseudo_move(&mut self, sign: i8, v: usize) -> Option<Vec<usize>> {
+    /// Try to make a pseudo-legal move. Returns false if the move is rejected
+    /// (eye fill, suicide, ko-like recapture).
+    fn make_pseudo_move(&mut self, sign: i8, v: usize) -> bool {
         // Reject eye fills: all neighbors are same color or off-board
         let neighbors = self.neighbors(v);
-        let all_friendly = neighbors.iter().all(|&n| self.data[n] == sign);
-        if all_friendly {
-            return None;
+        if neighbors.iter().all(|&n| self.get(n) == sign) {
+            return false;
         }
 
-        // Place stone
-        self.data[v] = sign;
-
-        // Capture opponent chains with 0 liberties
-        let opp = -sign;
-        let mut captured = Vec::new();
-        for &n in &neighbors {
-            if self.data[n] == opp {
-                let mut vis = vec![false; self.size()];
-                if !self.has_liberties(n, &mut vis) {
-                    let chain = self.get_chain(n);
-                    for &c in &chain {
-                        self.data[c] = 0;
-                    }
-                    captured.extend(chain);
-                }
-            }
-        }
-
-        // Reject suicide (placed stone has no liberties and captured nothing)
-        if captured.is_empty() {
-            let mut vis = vec![false; self.size()];
-            if !self.has_liberties(v, &mut vis) {
-                self.data[v] = 0;
-                return None;
-            }
+        let stone = Stone::from_int(sign).expect("playout sign must be a stone");
+        let placement = self.board.analyze(v, stone);
+        // Reject suicide, and single-stone recaptures that would start a ko fight.
+        if placement.suicide || placement.is_ko_shape() {
+            return false;
         }
 
-        // Reject ko-like recaptures: single stone captures single stone AND
-        // the capturing stone has exactly 1 liberty (the captured position).
-        if captured.len() == 1 {
-            let nbrs = self.neighbors(v);
-            let is_single = nbrs.iter().all(|&n| self.data[n] != sign);
-            let lib_count = nbrs.iter().filter(|&&n| self.data[n] == 0).count();
-            if is_single && lib_count == 1 {
-                self.data[v] = 0;
-                self.data[captured[0]] = opp;
-                return None;
-            }
-        }
-
-        Some(captured)
+        self.board.place(v, stone, &placement, None);
+        true
     }
 }
 
@@ -226,7 +135,7 @@ fn play_till_end(goban: &Goban, starting_sign: i8, rng: &mut Rng) -> Vec<i8> {
                 continue;
             }
 
-            if let Some(_captured) = board.make_pseudo_move(sign, v) {
+            if board.make_pseudo_move(sign, v) {
                 empty.swap_remove(idx);
                 played = true;
                 break;
@@ -244,19 +153,20 @@ fn play_till_end(goban: &Goban, starting_sign: i8, rng: &mut Rng) -> Vec<i8> {
     }
 
     // Patch remaining empty points with neighbor color
+    let mut data = board.board.cells().to_vec();
     for i in 0..size {
-        if board.get(i) == 0 {
+        if data[i] == 0 {
             for n in board.neighbors(i) {
-                let ns = board.data[n]; // direct access for speed
+                let ns = data[n]; // direct access for speed
                 if ns != 0 {
-                    board.data[i] = ns;
+                    data[i] = ns;
                     break;
                 }
             }
         }
     }
 
-    board.data
+    data
 }
 
 /// Run multiple random playouts and return per-vertex ownership probability.
@@ -289,7 +199,8 @@ fn get_probability_map(goban: &Goban, iterations: usize) -> Vec<f64> {
 /// Phase 2 — Monte Carlo: runs random playouts and checks the average ownership
 /// probability of each non-alive chain's liberties. If the liberties are firmly
 /// controlled by the opponent, the chain is dead. Chains with zero liberties
-/// (already captured in practice) are also marked dead.
+/// (already captured in practice) are also marked dead, as are chains with
+/// at most two liberties that a tactical read shows cannot escape.
 ///
 /// Benson-alive stones are never marked dead.
 pub fn detect_dead_stones(goban: &Goban) -> HashSet<Point> {
@@ -318,7 +229,8 @@ pub fn detect_dead_stones(goban: &Goban) -> HashSet<Point> {
         captures: Default::default(),
         ko: None,
         last_move: None,
-    });
+    })
+    .with_topology(goban.topology());
     let ownership = estimate_territory(&simplified_goban, &HashSet::new());
 
     let mut dead = HashSet::new();
@@ -381,6 +293,15 @@ pub fn detect_dead_stones(goban: &Goban) -> HashSet<Point> {
                 continue;
             }
 
+            // Short of liberties and caught in a ladder or net even when
+            // moving first → dead. Random playouts often miss these.
+            if lib_count <= 2 && !tactics::can_escape(goban, (x, y), ReadingLimits::default()) {
+                for &pt in &chain {
+                    dead.insert(pt);
+                }
+                continue;
+            }
+
             // Average liberty probability opposes stone color → dead
             let stone_liberty_score = (stone.to_int() as f64) * liberty_score;
             let liberty_regions = chain_liberty_region_count(goban, &chain);
@@ -545,6 +466,7 @@ mod tests {
     use super::*;
     use crate::Engine;
     use crate::territory::score;
+    use crate::topology::Topology;
 
     fn play(engine: &mut Engine, stone: Stone, point: Point) {
         engine.try_play(stone, point).expect("legal move");
@@ -795,7 +717,14 @@ mod tests {
         assert_eq!(dead, expected_dead_black);
 
         let ownership = estimate_territory(engine.goban(), &dead);
-        let final_score = score(engine.goban(), &ownership, &dead, 6.5);
+        let final_score = score(
+            engine.goban(),
+            &ownership,
+            &dead,
+            6.5,
+            engine.handicap(),
+            engine.ruleset(),
+        );
 
         assert_eq!(final_score.black.territory, 3);
     }
@@ -824,7 +753,14 @@ mod tests {
         }
 
         let ownership = estimate_territory(engine.goban(), &dead);
-        let final_score = score(engine.goban(), &ownership, &dead, 6.5);
+        let final_score = score(
+            engine.goban(),
+            &ownership,
+            &dead,
+            6.5,
+            engine.handicap(),
+            engine.ruleset(),
+        );
         assert!(final_score.white_total() > final_score.black_total());
     }
 
@@ -844,4 +780,33 @@ mod tests {
             assert!(!dead.contains(&point), "expected {point:?} to be alive");
         }
     }
+
+    #[test]
+    fn chain_caught_in_a_ladder_is_dead() {
+        let goban = crate::test_utils::goban_from_layout(&[
+            "+++++++++",
+            "+++++++++",
+            "+++++++++",
+            "+++++++++",
+            "+++B+++++",
+            "+++BWB+++",
+            "++++B++++",
+            "+++++++++",
+            "+++++++++",
+        ]);
+        let dead = detect_dead_stones(&goban);
+        assert!(dead.contains(&(4, 5)));
+    }
+
+    #[test]
+    fn playout_neighbors_wrap_on_torus() {
+        let goban = Goban::with_dimensions(3, 3).with_topology(Topology::Torus);
+        let board = PlayoutBoard::from_goban(&goban);
+        let mut neighbors = board.neighbors(0).to_vec();
+        neighbors.sort();
+        assert_eq!(neighbors, vec![1, 2, 3, 6]);
+
+        let planar = PlayoutBoard::from_goban(&Goban::with_dimensions(3, 3));
+        assert_eq!(planar.neighbors(0).len(), 2);
+    }
 }
@@ -3,6 +3,7 @@ mod dead_stones;
 mod pass_alive;
 mod scoring;
 
+pub(crate) use alive::find_unconditionally_alive;
 pub use dead_stones::detect_dead_stones;
 pub use pass_alive::{
     AreaOptions, IndependentLifeArea, calculate_area, calculate_independent_life_area,
@@ -227,7 +227,7 @@ fn is_seki_seed(goban: &Goban, basic_area: &[i8], point: Point) -> bool {
     let idx = point_idx(goban, point);
     let owner = basic_area[idx];
 
-    if goban.board()[idx].signum() == owner && goban.liberties(point).len() == 1 {
+    if goban.board()[idx].signum() == owner && goban.liberty_count(point) == 1 {
         return true;
     }
 
@@ -4,6 +4,7 @@ use serde::Serialize;
 
 use crate::Point;
 use crate::goban::Goban;
+use crate::ruleset::{Ruleset, ScoringMethod};
 use crate::stone::Stone;
 
 /// Estimate territory ownership for each point on the board.
@@ -109,16 +110,18 @@ pub fn toggle_dead_chain(goban: &Goban, dead_stones: &mut HashSet<Point>, point:
     }
 }
 
-/// Per-color score breakdown: territory (empty points) and captures (prisoners + dead stones).
+/// Per-color score breakdown: territory (empty points), captures (prisoners + dead stones,
+/// territory scoring only) and stones (living stones on the board, area scoring only).
 #[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub struct PlayerPoints {
     pub territory: u32,
     pub captures: u32,
+    pub stones: u32,
 }
 
 impl PlayerPoints {
     pub fn total(&self) -> u32 {
-        self.territory + self.captures
+        self.territory + self.captures + self.stones
     }
 }
 
@@ -128,6 +131,8 @@ pub struct GameScore {
     pub black: PlayerPoints,
     pub white: PlayerPoints,
     pub komi: f64,
+    /// Points White receives for Black's handicap stones (see [`Ruleset::handicap_bonus`]).
+    pub handicap_bonus: f64,
 }
 
 impl GameScore {
@@ -136,7 +141,7 @@ impl GameScore {
     }
 
     pub fn white_total(&self) -> f64 {
-        self.white.total() as f64 + self.komi
+        self.white.total() as f64 + self.komi + self.handicap_bonus
     }
 
     pub fn result(&self) -> String {
@@ -144,16 +149,26 @@ impl GameScore {
     }
 }
 
-/// Calculate final scores with full breakdown.
-///
-/// Uses Japanese-style scoring:
-/// score = territory + captures (including dead opponent stones) + komi (White only)
+/// Calculate final scores with full breakdown under `ruleset` for a game
+/// played with `handicap` stones.
 pub fn score(
     goban: &Goban,
     ownership: &[i8],
     dead_stones: &HashSet<Point>,
     komi: f64,
+    handicap: u8,
+    ruleset: &Ruleset,
 ) -> GameScore {
+    let mut score = match ruleset.scoring {
+        ScoringMethod::Territory => territory_score(goban, ownership, dead_stones, komi),
+        ScoringMethod::Area => area_score(goban, ownership, dead_stones, komi),
+    };
+    score.handicap_bonus = ruleset.handicap_bonus(handicap);
+    score
+}
+
+/// Count (Black, White) owned points in an ownership map.
+fn count_territory(ownership: &[i8]) -> (u32, u32) {
     let mut black_territory: u32 = 0;
     let mut white_territory: u32 = 0;
 
@@ -165,6 +180,19 @@ pub fn score(
         }
     }
 
+    (black_territory, white_territory)
+}
+
+/// Japanese-style scoring:
+/// score = territory + captures (including dead opponent stones) + komi (White only)
+fn territory_score(
+    goban: &Goban,
+    ownership: &[i8],
+    dead_stones: &HashSet<Point>,
+    komi: f64,
+) -> GameScore {
+    let (black_territory, white_territory) = count_territory(ownership);
+
     let mut dead_black: u32 = 0;
     let mut dead_white: u32 = 0;
 
@@ -180,12 +208,60 @@ pub fn score(
         black: PlayerPoints {
             territory: black_territory,
             captures: goban.captures().get(Stone::Black) + dead_white,
+            stones: 0,
         },
         white: PlayerPoints {
             territory: white_territory,
             captures: goban.captures().get(Stone::White) + dead_black,
+            stones: 0,
         },
         komi,
+        handicap_bonus: 0.0,
+    }
+}
+
+/// Chinese-style scoring:
+/// score = territory + living stones on the board + komi (White only)
+///
+/// Prisoners are ignored. Dead stones are removed before counting, and the
+/// points they occupied already belong to the surrounding territory in `ownership`.
+fn area_score(
+    goban: &Goban,
+    ownership: &[i8],
+    dead_stones: &HashSet<Point>,
+    komi: f64,
+) -> GameScore {
+    let (black_territory, white_territory) = count_territory(ownership);
+
+    let mut black_stones: u32 = 0;
+    let mut white_stones: u32 = 0;
+
+    for row in 0..goban.rows() {
+        for col in 0..goban.cols() {
+            if dead_stones.contains(&(col, row)) {
+                continue;
+            }
+            match goban.stone_at((col, row)) {
+                Some(Stone::Black) => black_stones += 1,
+                Some(Stone::White) => white_stones += 1,
+                None => {}
+            }
+        }
+    }
+
+    GameScore {
+        black: PlayerPoints {
+            territory: black_territory,
+            captures: 0,
+            stones: black_stones,
+        },
+        white: PlayerPoints {
+            territory: white_territory,
+            captures: 0,
+            stones: white_stones,
+        },
+        komi,
+        handicap_bonus: 0.0,
     }
 }
 
@@ -202,3 +278,91 @@ pub fn format_result(black_score: f64, white_score: f64) -> String {
         "Draw".to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::topology::Topology;
+
+    /// Two columns of Black and White stones with an empty column behind each,
+    /// plus a dead White stone inside Black's area.
+    fn split_board() -> (Goban, HashSet<Point>) {
+        let goban = Goban::new(vec![vec![-1, 1, -1, 0], vec![0, 1, -1, 0]]);
+        (goban, HashSet::from([(0, 0)]))
+    }
+
+    #[test]
+    fn territory_scoring_counts_dead_stones_as_captures() {
+        let (goban, dead) = split_board();
+        let ownership = estimate_territory(&goban, &dead);
+        let gs = score(&goban, &ownership, &dead, 0.5, 0, &Ruleset::JAPANESE);
+
+        assert_eq!(
+            gs.black,
+            PlayerPoints {
+                territory: 2,
+                captures: 1,
+                stones: 0,
+            }
+        );
+        assert_eq!(gs.white.total(), 2);
+        assert_eq!(gs.result(), "B+0.5");
+    }
+
+    #[test]
+    fn area_scoring_counts_living_stones_and_ignores_prisoners() {
+        let (goban, dead) = split_board();
+        let ownership = estimate_territory(&goban, &dead);
+        let gs = score(&goban, &ownership, &dead, 0.5, 0, &Ruleset::CHINESE);
+
+        assert_eq!(
+            gs.black,
+            PlayerPoints {
+                territory: 2,
+                captures: 0,
+                stones: 2,
+            }
+        );
+        assert_eq!(
+            gs.white,
+            PlayerPoints {
+                territory: 2,
+                captures: 0,
+                stones: 2,
+            }
+        );
+        assert_eq!(gs.result(), "W+0.5");
+    }
+
+    #[test]
+    fn area_scoring_compensates_white_for_handicap_stones() {
+        let (goban, dead) = split_board();
+        let ownership = estimate_territory(&goban, &dead);
+
+        let chinese = score(&goban, &ownership, &dead, 0.5, 4, &Ruleset::CHINESE);
+        assert_eq!(chinese.handicap_bonus, 4.0);
+        assert_eq!(chinese.white_total(), 8.5);
+        assert_eq!(chinese.result(), "W+4.5");
+
+        let aga = score(&goban, &ownership, &dead, 0.5, 4, &Ruleset::AGA);
+        assert_eq!(aga.handicap_bonus, 3.0);
+        assert_eq!(aga.result(), "W+3.5");
+
+        let japanese = score(&goban, &ownership, &dead, 0.5, 4, &Ruleset::JAPANESE);
+        assert_eq!(japanese.handicap_bonus, 0.0);
+        assert_eq!(japanese.result(), "B+0.5");
+    }
+
+    #[test]
+    fn torus_territory_reaches_around_the_edge() {
+        let board = vec![vec![0, 1, 0, -1, 0]; 3];
+        let planar = Goban::new(board.clone());
+        let ownership = estimate_territory(&planar, &HashSet::new());
+        assert_eq!(&ownership[..5], &[1, 0, 0, 0, -1]);
+
+        // Columns 0 and 4 join across the edge, touching both colors.
+        let torus = Goban::new(board).with_topology(Topology::Torus);
+        let ownership = estimate_territory(&torus, &HashSet::new());
+        assert_eq!(&ownership[..5], &[0, 0, 0, 0, 0]);
+    }
+}
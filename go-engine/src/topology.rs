@@ -0,0 +1,73 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// How the edges of the board connect.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Topology {
+    /// The usual board: edge points have fewer than four neighbors.
+    #[default]
+    Planar,
+    /// Edgeless: the left edge touches the right and the top touches the bottom.
+    Torus,
+}
+
+impl Topology {
+    pub fn is_planar(&self) -> bool {
+        *self == Topology::Planar
+    }
+
+    /// The point `delta` steps from `pos` along a side of length `len`, or
+    /// `None` when that walks off a planar board.
+    pub(crate) fn step(&self, pos: u8, delta: i8, len: u8) -> Option<u8> {
+        let next = pos as i16 + delta as i16;
+        match self {
+            Topology::Planar => (0..len as i16).contains(&next).then_some(next as u8),
+            Topology::Torus => Some(next.rem_euclid(len as i16) as u8),
+        }
+    }
+}
+
+impl fmt::Display for Topology {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Topology::Planar => write!(f, "planar"),
+            Topology::Torus => write!(f, "torus"),
+        }
+    }
+}
+
+impl std::str::FromStr for Topology {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "planar" => Ok(Topology::Planar),
+            "torus" => Ok(Topology::Torus),
+            _ => Err(format!("unknown topology: {s}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn step_wraps_only_on_torus() {
+        assert_eq!(Topology::Planar.step(0, -1, 9), None);
+        assert_eq!(Topology::Planar.step(8, 1, 9), None);
+        assert_eq!(Topology::Planar.step(4, 1, 9), Some(5));
+        assert_eq!(Topology::Torus.step(0, -1, 9), Some(8));
+        assert_eq!(Topology::Torus.step(8, 1, 9), Some(0));
+    }
+
+    #[test]
+    fn round_trips_through_strings() {
+        for topology in [Topology::Planar, Topology::Torus] {
+            assert_eq!(topology.to_string().parse::<Topology>(), Ok(topology));
+        }
+        assert!("klein".parse::<Topology>().is_err());
+    }
+}
@@ -0,0 +1,416 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::game_tree::{GameTree, NodeId};
+use crate::goban::{Goban, Undo};
+use crate::setup::Setup;
+use crate::stone::Stone;
+use crate::territory::find_unconditionally_alive;
+use crate::turn::Turn;
+use crate::{Point, zobrist};
+
+/// The fate of a problem's target group under best play.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    /// The group makes life, either unconditionally or in seki.
+    Lives,
+    Dies,
+    /// The search ran out of nodes or depth before deciding.
+    Unknown,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Lives => write!(f, "lives"),
+            Outcome::Dies => write!(f, "dies"),
+            Outcome::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl std::str::FromStr for Outcome {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lives" => Ok(Outcome::Lives),
+            "dies" => Ok(Outcome::Dies),
+            "unknown" => Ok(Outcome::Unknown),
+            _ => Err(format!("unknown outcome: {s}")),
+        }
+    }
+}
+
+/// A life-and-death question: does the group through `target` live when
+/// `to_play` moves first and play is confined to `region`?
+#[derive(Debug, Clone)]
+pub struct Problem {
+    pub goban: Goban,
+    pub target: Point,
+    /// Points either side may play on. Passing is always allowed.
+    pub region: Vec<Point>,
+    pub to_play: Stone,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SolverLimits {
+    /// Positions searched before giving up.
+    pub max_nodes: u32,
+    /// Moves deep a single line may go.
+    pub max_depth: u8,
+    /// Cap on the solution tree's size; branches past it are left out.
+    pub max_tree_nodes: usize,
+}
+
+impl Default for SolverLimits {
+    fn default() -> Self {
+        Self {
+            max_nodes: 200_000,
+            max_depth: 40,
+            max_tree_nodes: 500,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Solution {
+    pub outcome: Outcome,
+    /// Proof of the outcome: the winning side's move at each of its turns
+    /// and every reply of the losing side, so each wrong try comes with its
+    /// refutation. The tree's setup holds the problem position. Empty when
+    /// the outcome is unknown or already settled.
+    pub tree: GameTree,
+    /// Positions searched.
+    pub nodes: u32,
+}
+
+/// Solve `problem` by depth-first AND/OR search with a transposition table.
+///
+/// The target dies once it is captured. It lives once Benson's algorithm
+/// finds it unconditionally alive, or when both sides pass in a row, which
+/// covers seki. An empty target has nothing to save and is reported dead.
+pub fn solve(problem: &Problem, limits: SolverLimits) -> Solution {
+    let Some(defender) = problem.goban.stone_at(problem.target) else {
+        return Solution {
+            outcome: Outcome::Dies,
+            tree: GameTree::new(),
+            nodes: 0,
+        };
+    };
+
+    let mut solver = Solver {
+        goban: problem.goban.clone(),
+        problem,
+        defender,
+        limits,
+        nodes: 0,
+        table: HashMap::new(),
+    };
+    let outcome = solver.search(problem.to_play, 0, 0);
+
+    let mut tree = GameTree::with_setup(problem_setup(problem));
+    if outcome != Outcome::Unknown {
+        solver.build_tree(&mut tree, None, problem.to_play, 0);
+    }
+
+    Solution {
+        outcome,
+        tree,
+        nodes: solver.nodes,
+    }
+}
+
+fn problem_setup(problem: &Problem) -> Setup {
+    let goban = &problem.goban;
+    let mut black = Vec::new();
+    let mut white = Vec::new();
+    for row in 0..goban.rows() {
+        for col in 0..goban.cols() {
+            match goban.stone_at((col, row)) {
+                Some(Stone::Black) => black.push((col, row)),
+                Some(Stone::White) => white.push((col, row)),
+                None => {}
+            }
+        }
+    }
+    Setup::stones(black, white).with_to_play(problem.to_play)
+}
+
+/// A move in the search: a point, or `None` to pass.
+type Play = Option<Point>;
+
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    outcome: Outcome,
+    /// The winning move when the side to move wins.
+    best: Play,
+}
+
+struct Solver<'a> {
+    goban: Goban,
+    problem: &'a Problem,
+    defender: Stone,
+    limits: SolverLimits,
+    nodes: u32,
+    /// Settled positions, keyed by position hash and whether the last move
+    /// was a pass. Unknown results depend on the remaining depth and are not
+    /// stored.
+    table: HashMap<(u64, bool), Entry>,
+}
+
+impl Solver<'_> {
+    fn search(&mut self, to_play: Stone, passes: u8, depth: u8) -> Outcome {
+        if let Some(outcome) = self.settled(passes) {
+            return outcome;
+        }
+        let key = self.key(to_play, passes);
+        if let Some(entry) = self.table.get(&key) {
+            return entry.outcome;
+        }
+        self.nodes += 1;
+        if self.nodes > self.limits.max_nodes || depth >= self.limits.max_depth {
+            return Outcome::Unknown;
+        }
+
+        let (wanted, otherwise) = if to_play == self.defender {
+            (Outcome::Lives, Outcome::Dies)
+        } else {
+            (Outcome::Dies, Outcome::Lives)
+        };
+        let mut result = otherwise;
+        for play in self.candidates(to_play, true) {
+            let Some(undo) = self.make(play, to_play) else {
+                continue;
+            };
+            let next_passes = if play.is_none() { passes + 1 } else { 0 };
+            let outcome = self.search(to_play.opp(), next_passes, depth + 1);
+            self.goban.unmake_move(undo);
+
+            if outcome == wanted {
+                self.table.insert(
+                    key,
+                    Entry {
+                        outcome,
+                        best: play,
+                    },
+                );
+                return outcome;
+            }
+            if outcome == Outcome::Unknown {
+                result = Outcome::Unknown;
+            }
+        }
+
+        if result != Outcome::Unknown {
+            self.table.insert(
+                key,
+                Entry {
+                    outcome: result,
+                    best: None,
+                },
+            );
+        }
+        result
+    }
+
+    /// Walk the settled table into `tree`: one move where the winner is to
+    /// play, every move (bar passing) where the loser is.
+    fn build_tree(
+        &mut self,
+        tree: &mut GameTree,
+        parent: Option<NodeId>,
+        to_play: Stone,
+        passes: u8,
+    ) {
+        if self.settled(passes).is_some() {
+            return;
+        }
+        let Some(entry) = self.table.get(&self.key(to_play, passes)).copied() else {
+            return;
+        };
+        let winner = match entry.outcome {
+            Outcome::Lives => self.defender,
+            _ => self.defender.opp(),
+        };
+        let plays = if to_play == winner {
+            vec![entry.best]
+        } else {
+            self.candidates(to_play, false)
+        };
+
+        for play in plays {
+            if tree.len() >= self.limits.max_tree_nodes {
+                return;
+            }
+            let Some(undo) = self.make(play, to_play) else {
+                continue;
+            };
+            let turn = match play {
+                Some(point) => Turn::play(to_play, point),
+                None => Turn::pass(to_play),
+            };
+            let node = tree.add_child(parent, turn);
+            let next_passes = if play.is_none() { passes + 1 } else { 0 };
+            self.build_tree(tree, Some(node), to_play.opp(), next_passes);
+            self.goban.unmake_move(undo);
+        }
+    }
+
+    /// The outcome if the position needs no more reading.
+    fn settled(&self, passes: u8) -> Option<Outcome> {
+        if self.goban.stone_at(self.problem.target) != Some(self.defender) {
+            return Some(Outcome::Dies);
+        }
+        if passes >= 2
+            || find_unconditionally_alive(&self.goban, self.defender).contains(&self.problem.target)
+        {
+            return Some(Outcome::Lives);
+        }
+        None
+    }
+
+    /// Legal region moves, the target's liberties first, then optionally a pass.
+    fn candidates(&self, to_play: Stone, pass: bool) -> Vec<Play> {
+        let liberties = self.goban.liberties(self.problem.target);
+        let mut plays: Vec<Play> = liberties
+            .iter()
+            .chain(&self.problem.region)
+            .copied()
+            .filter(|&point| {
+                self.problem.region.contains(&point) && self.goban.is_legal_move(point, to_play)
+            })
+            .map(Some)
+            .collect();
+        let mut seen = Vec::with_capacity(plays.len());
+        plays.retain(|play| {
+            let fresh = !seen.contains(play);
+            seen.push(*play);
+            fresh
+        });
+        if pass {
+            plays.push(None);
+        }
+        plays
+    }
+
+    fn make(&mut self, play: Play, stone: Stone) -> Option<Undo> {
+        match play {
+            Some(point) => self.goban.make_move(point, stone).ok(),
+            None => Some(self.goban.make_pass(stone)),
+        }
+    }
+
+    fn key(&self, to_play: Stone, passes: u8) -> (u64, bool) {
+        let mut hash = self.goban.hash();
+        if to_play == Stone::White {
+            hash ^= zobrist::white_to_play_key();
+        }
+        if let Some(ko) = self.goban.ko() {
+            let index = ko.pos.1 as usize * self.goban.cols() as usize + ko.pos.0 as usize;
+            hash ^= zobrist::ko_key(index);
+        }
+        (hash, passes > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::goban_from_layout;
+
+    // Black's corner group has a straight three along the top edge.
+    const STRAIGHT_THREE: [&str; 7] = [
+        "+++BW++", "BBBBW++", "WWWWW++", "+++++++", "+++++++", "+++++++", "+++++++",
+    ];
+
+    fn problem(to_play: Stone) -> Problem {
+        Problem {
+            goban: goban_from_layout(&STRAIGHT_THREE),
+            target: (0, 1),
+            region: vec![(0, 0), (1, 0), (2, 0)],
+            to_play,
+        }
+    }
+
+    #[test]
+    fn straight_three_lives_on_the_vital_point() {
+        let solution = solve(&problem(Stone::Black), SolverLimits::default());
+        assert_eq!(solution.outcome, Outcome::Lives);
+
+        let tree = &solution.tree;
+        assert_eq!(tree.root_children().len(), 1);
+        let first = tree.node(tree.root_children()[0]);
+        assert_eq!(first.turn, Turn::play(Stone::Black, (1, 0)));
+        assert_eq!(tree.setup().to_play, Some(Stone::Black));
+        assert_eq!(tree.setup().black.len(), 5);
+    }
+
+    #[test]
+    fn straight_three_dies_when_the_attacker_moves_first() {
+        let solution = solve(&problem(Stone::White), SolverLimits::default());
+        assert_eq!(solution.outcome, Outcome::Dies);
+
+        // White's placement, then each Black try with White's answer.
+        let tree = &solution.tree;
+        let first = tree.root_children()[0];
+        assert_eq!(tree.node(first).turn, Turn::play(Stone::White, (1, 0)));
+        let replies = tree.children_of(Some(first));
+        assert_eq!(replies.len(), 2);
+        for &reply in replies {
+            assert_eq!(tree.node(reply).turn.stone, Stone::Black);
+            assert_eq!(tree.children_of(Some(reply)).len(), 1);
+        }
+    }
+
+    #[test]
+    fn straight_four_lives_whoever_moves_first() {
+        let goban = goban_from_layout(&[
+            "++++BW+", "BBBBBW+", "WWWWWW+", "+++++++", "+++++++", "+++++++", "+++++++",
+        ]);
+        let problem = Problem {
+            goban,
+            target: (0, 1),
+            region: vec![(0, 0), (1, 0), (2, 0), (3, 0)],
+            to_play: Stone::White,
+        };
+        let solution = solve(&problem, SolverLimits::default());
+        assert_eq!(solution.outcome, Outcome::Lives);
+        // Every White placement is in the tree, each answered by Black.
+        let tree = &solution.tree;
+        assert_eq!(tree.root_children().len(), 4);
+        for &attack in tree.root_children() {
+            assert_eq!(tree.children_of(Some(attack)).len(), 1);
+        }
+    }
+
+    #[test]
+    fn unconditionally_alive_group_needs_no_moves() {
+        let mut problem = problem(Stone::White);
+        problem.goban.set_stone((1, 0), Stone::Black);
+        let solution = solve(&problem, SolverLimits::default());
+        assert_eq!(solution.outcome, Outcome::Lives);
+        assert!(solution.tree.is_empty());
+    }
+
+    #[test]
+    fn exhausted_budget_is_unknown() {
+        let limits = SolverLimits {
+            max_nodes: 1,
+            ..SolverLimits::default()
+        };
+        let solution = solve(&problem(Stone::White), limits);
+        assert_eq!(solution.outcome, Outcome::Unknown);
+        assert!(solution.tree.is_empty());
+    }
+
+    #[test]
+    fn outcome_round_trips_through_strings() {
+        for outcome in [Outcome::Lives, Outcome::Dies, Outcome::Unknown] {
+            assert_eq!(outcome.to_string().parse::<Outcome>(), Ok(outcome));
+        }
+    }
+}
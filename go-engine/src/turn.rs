@@ -79,6 +79,14 @@ impl Turn {
     pub fn is_resign(&self) -> bool {
         self.kind == Move::Resign
     }
+
+    /// This turn with its point moved by `f`.
+    pub(crate) fn map_points(&self, f: &impl Fn(Point) -> Point) -> Self {
+        Turn {
+            pos: self.pos.map(f),
+            ..self.clone()
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,106 @@
+//! Zobrist keys for board positions.
+//!
+//! Keys are derived from the point index with splitmix64 rather than stored in
+//! a table, so boards of any size hash without allocation and the values are
+//! stable across processes (they may be persisted alongside cached state).
+
+use crate::stone::Stone;
+use crate::topology::Topology;
+
+const SEED: u64 = 0x5E4B_1C0D_EB0A_2D17;
+// Stone keys use small indices, so these tags keep the other keys apart.
+const TO_PLAY_TAG: u64 = 1 << 63;
+const KO_TAG: u64 = 1 << 62;
+const BOARD_TAG: u64 = 1 << 61;
+
+fn splitmix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+/// Key for `stone` sitting on the flat board index `index`.
+pub fn stone_key(index: usize, stone: Stone) -> u64 {
+    let color = match stone {
+        Stone::Black => 0,
+        Stone::White => 1,
+    };
+    splitmix64(SEED ^ (((index as u64) << 1) | color))
+}
+
+/// Key mixed into a position hash when White is on move.
+pub fn white_to_play_key() -> u64 {
+    splitmix64(SEED ^ TO_PLAY_TAG)
+}
+
+/// Key mixed into a position hash for a ko point at the flat index `index`.
+pub fn ko_key(index: usize) -> u64 {
+    splitmix64(SEED ^ KO_TAG ^ index as u64)
+}
+
+/// Key mixed into a position hash for the board's shape, so that equal flat
+/// indices on different boards never collide.
+pub fn board_key(cols: u8, rows: u8, topology: Topology) -> u64 {
+    let topology = match topology {
+        Topology::Planar => 0,
+        Topology::Torus => 1,
+    };
+    splitmix64(SEED ^ BOARD_TAG ^ ((cols as u64) << 16) ^ ((rows as u64) << 8) ^ topology)
+}
+
+/// Hash of a whole flat board (`0` = empty, otherwise a stone value).
+pub fn board_hash(board: &[i8]) -> u64 {
+    board
+        .iter()
+        .enumerate()
+        .filter_map(|(i, &v)| Stone::from_int(v).map(|s| stone_key(i, s)))
+        .fold(0, |acc, key| acc ^ key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_board_hashes_to_zero() {
+        assert_eq!(board_hash(&[0; 81]), 0);
+    }
+
+    #[test]
+    fn keys_differ_by_color_and_point() {
+        assert_ne!(stone_key(0, Stone::Black), stone_key(0, Stone::White));
+        assert_ne!(stone_key(0, Stone::Black), stone_key(1, Stone::Black));
+    }
+
+    #[test]
+    fn position_keys_differ_from_stone_keys() {
+        assert_ne!(white_to_play_key(), stone_key(0, Stone::Black));
+        assert_ne!(ko_key(0), stone_key(0, Stone::Black));
+        assert_ne!(ko_key(0), white_to_play_key());
+        assert_ne!(board_key(9, 9, Topology::Planar), white_to_play_key());
+    }
+
+    #[test]
+    fn board_keys_differ_by_shape_and_topology() {
+        let planar = board_key(9, 9, Topology::Planar);
+        assert_ne!(planar, board_key(19, 19, Topology::Planar));
+        assert_ne!(planar, board_key(9, 13, Topology::Planar));
+        assert_ne!(
+            board_key(9, 13, Topology::Planar),
+            board_key(13, 9, Topology::Planar)
+        );
+        assert_ne!(planar, board_key(9, 9, Topology::Torus));
+    }
+
+    #[test]
+    fn board_hash_is_xor_of_stone_keys() {
+        let mut board = [0i8; 9];
+        board[4] = Stone::Black.to_int();
+        board[0] = Stone::White.to_int();
+        assert_eq!(
+            board_hash(&board),
+            stone_key(4, Stone::Black) ^ stone_key(0, Stone::White)
+        );
+    }
+}
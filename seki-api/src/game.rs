@@ -19,6 +19,18 @@ pub struct GameSettings {
     pub cols: i32,
     pub rows: i32,
     pub handicap: i32,
+    /// Preset ruleset name: `japanese`, `chinese`, `aga` or `new_zealand`.
+    #[serde(default = "default_ruleset")]
+    pub ruleset: String,
+    /// How handicap stones are placed: `fixed` (star points) or `free`.
+    #[serde(default = "default_handicap_placement")]
+    pub handicap_placement: String,
+    /// Board topology: `planar` or `torus` (edges wrap around).
+    #[serde(default = "default_topology")]
+    pub topology: String,
+    /// Capture Go: the first player to capture this many stones wins.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capture_target: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_rating_difference_lower: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -45,6 +57,18 @@ pub struct GameSettings {
     pub calibration_policy_version: Option<String>,
 }
 
+fn default_ruleset() -> String {
+    "japanese".to_string()
+}
+
+fn default_handicap_placement() -> String {
+    "fixed".to_string()
+}
+
+fn default_topology() -> String {
+    "planar".to_string()
+}
+
 /// Per-player clock period within the in-game clock state.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
@@ -100,6 +124,9 @@ pub struct TerritoryState {
 pub struct TerritoryScore {
     pub black: TerritorySide,
     pub white: TerritorySide,
+    /// Points White receives for Black's handicap stones; only under area scoring.
+    #[serde(default)]
+    pub handicap_bonus: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -107,6 +134,9 @@ pub struct TerritoryScore {
 pub struct TerritorySide {
     pub territory: u32,
     pub captures: u32,
+    /// Living stones on the board; only counted under area scoring.
+    #[serde(default)]
+    pub stones: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -121,6 +151,8 @@ pub struct SettledTerritoryData {
 #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct PregameSettingsData {
     pub handicap: i32,
+    #[serde(default = "default_handicap_placement")]
+    pub handicap_placement: String,
     pub komi: f64,
     pub color: String,
     pub creator_approved: bool,
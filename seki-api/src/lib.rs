@@ -1,3 +1,4 @@
 pub mod game;
+pub mod scoring;
 pub mod user;
 pub mod ws;
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+/// A finished position for a scoring worker (`seki-gtp --score-listen`).
+/// Each request is one TCP connection carrying a single JSON line, answered
+/// by one [`ScoreResponse`] line. Points are `[col, row]`, row 0 at the top.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoreRequest {
+    pub game_id: i64,
+    pub cols: u8,
+    pub rows: u8,
+    pub komi: f64,
+    pub black: Vec<[u8; 2]>,
+    pub white: Vec<[u8; 2]>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScoreResponse {
+    #[serde(default)]
+    pub dead_stones: Vec<[u8; 2]>,
+    /// The engine's own count, positive when Black is ahead.
+    #[serde(default)]
+    pub score: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
@@ -89,6 +89,9 @@ pub enum ClientMsg {
         handicap: i32,
         komi: f64,
         color: String,
+        /// `fixed` or `free`; omitted keeps the current placement.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        handicap_placement: Option<String>,
     },
     AcceptPregameSettings {
         game_id: i64,
@@ -10,6 +10,11 @@ fn deserialize_live_item_with_ranked_present() {
     let json = r#"{"id":1,"creator_id":1,"stage":"unstarted","black":null,"white":null,"settings":{"cols":19,"rows":19,"handicap":0,"max_rating_difference_lower":null,"max_rating_difference_higher":null,"rating_difference_lower_unlimited":true,"rating_difference_higher_unlimited":true,"rating_range_mode":"absolute","time_control":"fischer","main_time_secs":null,"increment_secs":null,"byoyomi_time_secs":null,"byoyomi_periods":null,"is_private":false,"ranked":false,"rating_status":"unranked","color_reason":null,"calibration_policy_version":null},"move_count":null,"ranked":false,"derived_handicap":null,"derived_komi":null,"derived_color_reason":null}"#;
     let item: LiveGameItem = serde_json::from_str(json).unwrap();
     assert!(!item.ranked);
+    // Payloads from before rulesets default to Japanese.
+    assert_eq!(item.settings.ruleset, "japanese");
+    assert_eq!(item.settings.handicap_placement, "fixed");
+    assert_eq!(item.settings.topology, "planar");
+    assert_eq!(item.settings.capture_target, None);
 }
 
 #[test]
@@ -29,6 +34,10 @@ fn time_control_serialization_round_trip() {
         cols: 19,
         rows: 19,
         handicap: 0,
+        ruleset: "chinese".into(),
+        handicap_placement: "free".into(),
+        topology: "torus".into(),
+        capture_target: Some(3),
         max_rating_difference_lower: None,
         max_rating_difference_higher: None,
         rating_difference_lower_unlimited: true,
@@ -51,6 +60,10 @@ fn time_control_serialization_round_trip() {
 
     let round_tripped: GameSettings = serde_json::from_str(&json).unwrap();
     assert_eq!(round_tripped.time_control, TimeControl::Fischer);
+    assert_eq!(round_tripped.ruleset, "chinese");
+    assert_eq!(round_tripped.handicap_placement, "free");
+    assert_eq!(round_tripped.topology, "torus");
+    assert_eq!(round_tripped.capture_target, Some(3));
 }
 
 #[test]
@@ -1,14 +1,18 @@
 use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 
-use go_engine::{Stone, Turn};
+use go_engine::{HandicapPlacement, Ruleset, Stone, Turn};
+use seki_api::game::InGameClock;
 use seki_api::ws::{ClientMsg, LiveGameItem, ServerMsg};
 use tokio::sync::mpsc;
 use tracing::{error, info, warn};
 
+use crate::clock::{MoveClock, TimeSettings};
 use crate::config::Config;
-use crate::engine::{EngineHandle, MoveResult};
+use crate::engine::{BoardSync, EngineHandle, GameSetup, MoveResult};
+use crate::pool::EnginePool;
 
 #[derive(Debug, Clone, PartialEq)]
 enum GameStage {
@@ -20,6 +24,19 @@ enum GameStage {
     Finished,
 }
 
+/// What the engine needs to reproduce a game before `genmove`.
+#[derive(Clone, Copy)]
+struct Position<'a> {
+    cols: u8,
+    rows: u8,
+    komi: f64,
+    moves: &'a [Turn],
+    handicap: u8,
+    handicap_placement: HandicapPlacement,
+    ruleset: Ruleset,
+    time_settings: TimeSettings,
+}
+
 struct GameState {
     stage: GameStage,
     our_stone: Option<Stone>,
@@ -27,14 +44,19 @@ struct GameState {
     rows: u8,
     komi: f64,
     handicap: u8,
+    handicap_placement: HandicapPlacement,
+    ruleset: Ruleset,
     moves_known: usize,
     pregame_accepted: bool,
     territory_approved: bool,
+    time_settings: TimeSettings,
+    /// Our side of the clock from the latest `state`, if the game is timed.
+    clock: Option<MoveClock>,
 }
 
 pub struct Bot {
     config: Config,
-    engine: EngineHandle,
+    engines: EnginePool,
     ws_tx: mpsc::UnboundedSender<String>,
     games: HashMap<i64, GameState>,
     joined_games: Vec<i64>,
@@ -50,7 +72,7 @@ fn send_json(tx: &mpsc::UnboundedSender<String>, msg: &ClientMsg) {
 }
 
 impl Bot {
-    pub async fn run(config: Config, engine: EngineHandle, user_id: i64) -> Result<(), String> {
+    pub async fn run(config: Config, engines: EnginePool, user_id: i64) -> Result<(), String> {
         info!("Bot user_id={user_id} starting");
 
         let mut ws_handle =
@@ -59,7 +81,7 @@ impl Bot {
 
         let mut bot = Bot {
             config,
-            engine,
+            engines,
             ws_tx,
             games: HashMap::new(),
             joined_games: Vec::new(),
@@ -113,6 +135,7 @@ impl Bot {
                 negotiations,
                 territory,
                 settings,
+                clock,
                 hydrate_only,
                 ..
             } => {
@@ -130,8 +153,16 @@ impl Bot {
                     &negotiations,
                     &territory,
                     settings.settings.handicap as u8,
+                    settings
+                        .settings
+                        .handicap_placement
+                        .parse()
+                        .unwrap_or_default(),
+                    settings.settings.ruleset.parse().unwrap_or_default(),
                     settings.settings.cols as u8,
                     settings.settings.rows as u8,
+                    TimeSettings::from_game_settings(&settings.settings),
+                    &clock,
                     hydrate_only,
                 )
                 .await;
@@ -140,9 +171,10 @@ impl Bot {
                 game_id,
                 current_turn_stone,
                 ref moves,
+                ref clock,
                 ..
             } => {
-                self.handle_undo_accepted(game_id, current_turn_stone, moves)
+                self.handle_undo_accepted(game_id, current_turn_stone, moves, clock)
                     .await;
             }
             ServerMsg::UndoRequestSent { game_id } => {
@@ -210,9 +242,17 @@ impl Bot {
                             rows: game.settings.rows as u8,
                             komi: game.derived_komi.unwrap_or(6.5),
                             handicap: game.settings.handicap as u8,
+                            handicap_placement: game
+                                .settings
+                                .handicap_placement
+                                .parse()
+                                .unwrap_or_default(),
+                            ruleset: game.settings.ruleset.parse().unwrap_or_default(),
                             moves_known: game.move_count.unwrap_or(0),
                             pregame_accepted: false,
                             territory_approved: false,
+                            time_settings: TimeSettings::None,
+                            clock: None,
                         });
                     }
                     _ => {}
@@ -256,6 +296,7 @@ impl Bot {
     async fn handle_game_removed(&mut self, game_id: i64) {
         self.games.remove(&game_id);
         self.game_gens.remove(&game_id);
+        self.engines.release(game_id);
         self.joined_games.retain(|&id| id != game_id);
         self.process_challenge_queue().await;
     }
@@ -295,9 +336,13 @@ impl Bot {
                     rows: 19,
                     komi: 6.5,
                     handicap: 0,
+                    handicap_placement: HandicapPlacement::Fixed,
+                    ruleset: Ruleset::default(),
                     moves_known: 0,
                     pregame_accepted: false,
                     territory_approved: false,
+                    time_settings: TimeSettings::None,
+                    clock: None,
                 },
             );
             self.joined_games.push(game_id);
@@ -347,9 +392,13 @@ impl Bot {
                     rows: 19,
                     komi: 6.5,
                     handicap: 0,
+                    handicap_placement: HandicapPlacement::Fixed,
+                    ruleset: Ruleset::default(),
                     moves_known: 0,
                     pregame_accepted: false,
                     territory_approved: false,
+                    time_settings: TimeSettings::None,
+                    clock: None,
                 },
             );
             self.joined_games.push(game_id);
@@ -380,8 +429,12 @@ impl Bot {
         negotiations: &Option<seki_api::game::Negotiations>,
         territory: &Option<seki_api::game::TerritoryState>,
         handicap: u8,
+        handicap_placement: HandicapPlacement,
+        ruleset: Ruleset,
         cols: u8,
         rows: u8,
+        time_settings: TimeSettings,
+        clock: &Option<InGameClock>,
         _hydrate_only: bool,
     ) {
         let tx = self.ws_tx.clone();
@@ -394,13 +447,19 @@ impl Bot {
                 rows,
                 komi: 6.5,
                 handicap: 0,
+                handicap_placement: HandicapPlacement::Fixed,
+                ruleset: Ruleset::default(),
                 moves_known: 0,
                 pregame_accepted: false,
                 territory_approved: false,
+                time_settings: TimeSettings::None,
+                clock: None,
             });
 
             gs.cols = cols;
             gs.rows = rows;
+            gs.handicap_placement = handicap_placement;
+            gs.ruleset = ruleset;
 
             if gs.our_stone.is_none() {
                 gs.our_stone = if black.as_ref().is_some_and(|u| u.id == self.user_id) {
@@ -412,6 +471,8 @@ impl Bot {
                 };
             }
             gs.komi = komi;
+            gs.time_settings = time_settings;
+            gs.clock = read_clock(time_settings, clock, gs.our_stone);
             if handicap > 0 {
                 gs.handicap = handicap;
             }
@@ -457,7 +518,7 @@ impl Bot {
                         }
                     }
                 }
-                "black_to_play" | "white_to_play" => {
+                "handicap_placement" | "black_to_play" | "white_to_play" => {
                     let transitioning_to_play = gs.stage != GameStage::Playing;
                     gs.stage = GameStage::Playing;
 
@@ -472,12 +533,16 @@ impl Bot {
                         && (moves.len() > gs.moves_known || transitioning_to_play)
                     {
                         let tx = self.ws_tx.clone();
-                        let engine = self.engine.clone();
+                        let engine = self.engines.engine_for(game_id);
                         let cfg = self.config.clone();
                         let gs_cols = gs.cols;
                         let gs_rows = gs.rows;
                         let gs_komi = gs.komi;
                         let gs_handicap = gs.handicap;
+                        let gs_placement = gs.handicap_placement;
+                        let gs_ruleset = gs.ruleset;
+                        let gs_time_settings = gs.time_settings;
+                        let gs_clock = gs.clock;
                         let moves_vec = moves.to_vec();
 
                         let generation = self
@@ -500,6 +565,10 @@ impl Bot {
                                 &moves_vec,
                                 our_stone,
                                 gs_handicap,
+                                gs_placement,
+                                gs_ruleset,
+                                gs_time_settings,
+                                gs_clock,
                                 &cfg,
                                 generation,
                                 cancel_token,
@@ -540,6 +609,7 @@ impl Bot {
         if finished {
             self.games.remove(&game_id);
             self.game_gens.remove(&game_id);
+            self.engines.release(game_id);
             self.joined_games.retain(|&id| id != game_id);
             self.process_challenge_queue().await;
         }
@@ -550,6 +620,7 @@ impl Bot {
         game_id: i64,
         current_turn_stone: i32,
         moves: &[Turn],
+        clock: &Option<InGameClock>,
     ) {
         let our_stone = self.games.get(&game_id).and_then(|g| g.our_stone);
         let expected_current = match our_stone {
@@ -563,6 +634,7 @@ impl Bot {
 
         if let Some(gs) = self.games.get_mut(&game_id) {
             gs.moves_known = moves.len();
+            gs.clock = read_clock(gs.time_settings, clock, gs.our_stone);
         }
 
         let generation = self
@@ -575,17 +647,25 @@ impl Bot {
         if current_turn_stone == expected_current {
             info!("It is our turn after undo, playing game={game_id}");
             let tx = self.ws_tx.clone();
-            let engine = self.engine.clone();
+            let engine = self.engines.engine_for(game_id);
             let cfg = self.config.clone();
             let gs_cols;
             let gs_rows;
             let gs_komi;
             let gs_handicap;
+            let gs_placement;
+            let gs_ruleset;
+            let gs_time_settings;
+            let gs_clock;
             if let Some(gs) = self.games.get(&game_id) {
                 gs_cols = gs.cols;
                 gs_rows = gs.rows;
                 gs_komi = gs.komi;
                 gs_handicap = gs.handicap;
+                gs_placement = gs.handicap_placement;
+                gs_ruleset = gs.ruleset;
+                gs_time_settings = gs.time_settings;
+                gs_clock = gs.clock;
             } else {
                 return;
             }
@@ -605,6 +685,10 @@ impl Bot {
                     &moves_vec,
                     our_stone,
                     gs_handicap,
+                    gs_placement,
+                    gs_ruleset,
+                    gs_time_settings,
+                    gs_clock,
                     &cfg,
                     generation,
                     cancel_token,
@@ -630,6 +714,10 @@ impl Bot {
         moves: &[Turn],
         our_stone: Option<Stone>,
         handicap: u8,
+        handicap_placement: HandicapPlacement,
+        ruleset: Ruleset,
+        time_settings: TimeSettings,
+        clock: Option<MoveClock>,
         config: &Config,
         generation: Arc<AtomicU64>,
         cancel_token: u64,
@@ -647,89 +735,228 @@ impl Bot {
             return;
         }
 
-        if let Err(e) = engine.setup_position(cols, rows, komi).await {
-            error!("Game {game_id}: setup failed - {e}. Falling back to pass.");
+        let _turn = engine.reserve().await;
+        if !engine.is_alive().await
+            && let Err(e) = engine.restart().await
+        {
+            error!("Game {game_id}: engine respawn failed - {e}. Falling back to pass.");
             send_json(&tx, &ClientMsg::pass(game_id));
             return;
         }
 
-        if generation.load(Ordering::SeqCst) != cancel_token {
-            info!("Game {game_id}: stale think_and_play after setup, skipping");
-            return;
+        let position = Position {
+            cols,
+            rows,
+            komi,
+            moves,
+            handicap,
+            handicap_placement,
+            ruleset,
+            time_settings,
+        };
+        let mut result = Bot::generate_move(
+            &engine,
+            &tx,
+            game_id,
+            &position,
+            stone,
+            clock,
+            config,
+            &generation,
+            cancel_token,
+        )
+        .await;
+        if let Err(e) = &result
+            && !engine.is_alive().await
+        {
+            warn!("Game {game_id}: engine died ({e}), respawning and retrying");
+            result = match engine.restart().await {
+                Ok(()) => {
+                    Bot::generate_move(
+                        &engine,
+                        &tx,
+                        game_id,
+                        &position,
+                        stone,
+                        clock,
+                        config,
+                        &generation,
+                        cancel_token,
+                    )
+                    .await
+                }
+                Err(e) => Err(format!("respawn failed - {e}")),
+            };
         }
 
-        if handicap >= 2
-            && let Some(pts) = go_engine::handicap::handicap_points(cols, rows, handicap)
-        {
-            let gtp_coords: Vec<String> = pts
-                .iter()
-                .map(|&(c, r)| crate::gtp::seki_to_gtp(c, r, cols))
-                .collect();
-            if let Err(e) = engine.set_free_handicap(&gtp_coords).await {
-                error!("Game {game_id}: handicap placement failed - {e}. Falling back to pass.");
+        match result {
+            Ok(Some(MoveResult::Coord { col, row })) => {
+                info!("Game {game_id}: bot playing ({col},{row})");
+                send_json(&tx, &ClientMsg::play(game_id, col as i32, row as i32));
+            }
+            Ok(Some(MoveResult::Pass)) => {
+                info!("Game {game_id}: bot passing");
+                send_json(&tx, &ClientMsg::pass(game_id));
+            }
+            Ok(Some(MoveResult::Resign)) => {
+                info!("Game {game_id}: bot resigning");
+                send_json(&tx, &ClientMsg::Resign { game_id });
+            }
+            Ok(None) => {}
+            Err(_) if generation.load(Ordering::SeqCst) != cancel_token => {
+                info!("Game {game_id}: stale think_and_play after failure, skipping");
+            }
+            Err(e) => {
+                error!("Game {game_id}: {e}. Falling back to pass.");
                 send_json(&tx, &ClientMsg::pass(game_id));
-                return;
             }
         }
+    }
 
-        if generation.load(Ordering::SeqCst) != cancel_token {
-            info!("Game {game_id}: stale think_and_play after handicap, skipping");
-            return;
+    /// Load the position into the engine and ask it for a move. `Ok(None)`
+    /// means there is nothing to send: the request went stale or a handicap
+    /// stone was placed instead.
+    #[allow(clippy::too_many_arguments)]
+    async fn generate_move(
+        engine: &EngineHandle,
+        tx: &mpsc::UnboundedSender<String>,
+        game_id: i64,
+        position: &Position<'_>,
+        stone: Stone,
+        clock: Option<MoveClock>,
+        config: &Config,
+        generation: &AtomicU64,
+        cancel_token: u64,
+    ) -> Result<Option<MoveResult>, String> {
+        let Position {
+            cols,
+            rows,
+            komi,
+            moves,
+            handicap,
+            handicap_placement,
+            ruleset,
+            time_settings,
+        } = *position;
+
+        // The engine picks free handicap stones on an empty board.
+        if handicap >= 2
+            && handicap_placement == HandicapPlacement::Free
+            && moves.len() < handicap as usize
+        {
+            engine
+                .setup_position(cols, rows, komi)
+                .await
+                .map_err(|e| format!("setup failed - {e}"))?;
+            if generation.load(Ordering::SeqCst) != cancel_token {
+                info!("Game {game_id}: stale think_and_play after setup, skipping");
+                return Ok(None);
+            }
+            Bot::place_next_handicap_stone(engine, tx, game_id, (cols, rows), handicap, moves)
+                .await;
+            return Ok(None);
         }
 
-        if let Err(e) = engine.replay_moves(moves).await {
-            error!("Game {game_id}: replay failed - {e}. Falling back to pass.");
-            send_json(&tx, &ClientMsg::pass(game_id));
-            return;
+        let setup = GameSetup {
+            game_id,
+            cols,
+            rows,
+            komi,
+            handicap,
+            handicap_placement,
+            ruleset,
+        };
+        if engine.load_game(&setup, moves).await? == BoardSync::Replayed {
+            info!(
+                "Game {game_id}: loaded {} moves into the engine",
+                moves.len()
+            );
+        }
+        if let Err(e) = engine.time_settings(time_settings).await {
+            warn!("Game {game_id}: engine did not take the time settings - {e}");
         }
 
         if generation.load(Ordering::SeqCst) != cancel_token {
-            info!("Game {game_id}: stale think_and_play after replay, skipping");
-            return;
+            info!("Game {game_id}: stale think_and_play after loading the game, skipping");
+            return Ok(None);
         }
 
-        let move_result = tokio::time::timeout(
-            std::time::Duration::from_millis(config.time.engine_timeout_ms),
-            engine.genmove(stone),
-        )
-        .await;
-
-        if generation.load(Ordering::SeqCst) != cancel_token {
-            info!("Game {game_id}: stale think_and_play after genmove, skipping");
-            return;
+        let margin = Duration::from_millis(config.time.safety_margin_ms);
+        let mut timeout = Duration::from_millis(config.time.engine_timeout_ms);
+        if let Some(clock) = clock {
+            let now = Instant::now();
+            if let Err(e) = engine.time_left(stone, clock.time_left(now, margin)).await {
+                warn!("Game {game_id}: time_left failed - {e}");
+            }
+            timeout = timeout.min(clock.budget(now, margin));
         }
 
+        let move_result = tokio::time::timeout(timeout, engine.genmove(stone)).await;
+
         let move_result = match move_result {
-            Ok(Ok(r)) => r,
-            Ok(Err(e)) => {
-                error!("Game {game_id}: genmove failed - {e}. Falling back to pass.");
-                send_json(&tx, &ClientMsg::pass(game_id));
-                return;
-            }
+            Ok(result) => result.map_err(|e| format!("genmove failed - {e}"))?,
             Err(_) => {
-                warn!("Game {game_id}: engine timeout. Falling back to pass.");
-                send_json(&tx, &ClientMsg::pass(game_id));
-                return;
+                // The late reply would be read as the answer to the next
+                // command, so the process cannot be reused.
+                warn!("Game {game_id}: engine timeout, respawning it");
+                if let Err(e) = engine.restart().await {
+                    error!("Game {game_id}: engine respawn failed - {e}");
+                }
+                return Err("engine timeout".to_string());
             }
         };
 
-        match move_result {
-            MoveResult::Coord { col, row } => {
-                info!("Game {game_id}: bot playing ({col},{row})");
-                send_json(&tx, &ClientMsg::play(game_id, col as i32, row as i32));
-            }
-            MoveResult::Pass => {
-                info!("Game {game_id}: bot passing");
-                send_json(&tx, &ClientMsg::pass(game_id));
+        if generation.load(Ordering::SeqCst) != cancel_token {
+            info!("Game {game_id}: stale think_and_play after genmove, skipping");
+            return Ok(None);
+        }
+
+        Ok(Some(move_result))
+    }
+
+    /// Ask the engine where the free handicap stones go and play the first
+    /// one that is not on the board yet. Engines that cannot choose get the
+    /// fixed star points.
+    async fn place_next_handicap_stone(
+        engine: &EngineHandle,
+        tx: &mpsc::UnboundedSender<String>,
+        game_id: i64,
+        (cols, rows): (u8, u8),
+        handicap: u8,
+        placed: &[Turn],
+    ) {
+        let points = if engine.supports("place_free_handicap").await {
+            match engine.place_free_handicap(handicap).await {
+                Ok(points) => points,
+                Err(e) => {
+                    error!("Game {game_id}: place_free_handicap failed - {e}");
+                    return;
+                }
             }
-            MoveResult::Resign => {
-                info!("Game {game_id}: bot resigning");
-                send_json(&tx, &ClientMsg::Resign { game_id });
+        } else {
+            go_engine::handicap::handicap_points(cols, rows, handicap).unwrap_or_default()
+        };
+        match points
+            .into_iter()
+            .find(|&pt| !placed.iter().any(|t| t.pos == Some(pt)))
+        {
+            Some((col, row)) => {
+                info!("Game {game_id}: bot placing handicap stone ({col},{row})");
+                send_json(tx, &ClientMsg::play(game_id, col as i32, row as i32));
             }
+            None => error!("Game {game_id}: engine chose no new handicap point"),
         }
     }
 }
 
+fn read_clock(
+    time_settings: TimeSettings,
+    clock: &Option<InGameClock>,
+    our_stone: Option<Stone>,
+) -> Option<MoveClock> {
+    MoveClock::read(time_settings, clock.as_ref()?, our_stone?, Instant::now())
+}
+
 fn opponent_accepted_pregame_settings(
     user_id: i64,
     creator: &Option<seki_api::user::UserData>,
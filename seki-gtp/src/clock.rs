@@ -0,0 +1,230 @@
+use std::time::{Duration, Instant};
+
+use go_engine::Stone;
+use seki_api::game::{GameSettings, InGameClock, TimeControl};
+
+/// A game's time control, as forwarded to the engine.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimeSettings {
+    /// No clock, or one too slow (correspondence) to matter to an engine.
+    None,
+    Fischer {
+        main_secs: u32,
+        increment_secs: u32,
+    },
+    ByoYomi {
+        main_secs: u32,
+        period_secs: u32,
+        periods: u32,
+    },
+}
+
+impl TimeSettings {
+    /// Missing values fall back to the server's defaults.
+    pub fn from_game_settings(settings: &GameSettings) -> Self {
+        let secs = |value: Option<i32>, default: u32| value.map_or(default, |v| v.max(0) as u32);
+        match settings.time_control {
+            TimeControl::Fischer => TimeSettings::Fischer {
+                main_secs: secs(settings.main_time_secs, 600),
+                increment_secs: secs(settings.increment_secs, 5),
+            },
+            TimeControl::Byoyomi => TimeSettings::ByoYomi {
+                main_secs: secs(settings.main_time_secs, 1200),
+                period_secs: secs(settings.byoyomi_time_secs, 30),
+                periods: secs(settings.byoyomi_periods, 3),
+            },
+            TimeControl::None | TimeControl::Correspondence => TimeSettings::None,
+        }
+    }
+
+    /// The `kgs-time_settings` command, which can express every system.
+    pub fn kgs_command(&self) -> String {
+        match *self {
+            TimeSettings::None => "kgs-time_settings none".to_string(),
+            TimeSettings::Fischer {
+                main_secs,
+                increment_secs,
+            } => format!("kgs-time_settings fischer {main_secs} {increment_secs}"),
+            TimeSettings::ByoYomi {
+                main_secs,
+                period_secs,
+                periods,
+            } => format!("kgs-time_settings byoyomi {main_secs} {period_secs} {periods}"),
+        }
+    }
+
+    /// The closest standard `time_settings` command. Standard GTP only knows
+    /// Canadian byo-yomi, so Fischer becomes absolute time and each byo-yomi
+    /// period becomes a one-stone Canadian period.
+    pub fn gtp_command(&self) -> String {
+        match *self {
+            TimeSettings::None => "time_settings 0 1 0".to_string(),
+            TimeSettings::Fischer { main_secs, .. } => format!("time_settings {main_secs} 0 0"),
+            TimeSettings::ByoYomi {
+                main_secs,
+                period_secs,
+                ..
+            } => format!("time_settings {main_secs} {period_secs} 1"),
+        }
+    }
+}
+
+/// Our side's time for `time_left`, in whole seconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimeLeft {
+    Main { secs: u32 },
+    ByoYomi { secs: u32, periods: u32 },
+}
+
+/// Our side of the clock, as last reported by the server.
+#[derive(Debug, Clone, Copy)]
+pub struct MoveClock {
+    pub settings: TimeSettings,
+    pub remaining_ms: i64,
+    pub periods: i32,
+    pub read_at: Instant,
+}
+
+impl MoveClock {
+    /// `None` when the game is untimed.
+    pub fn read(
+        settings: TimeSettings,
+        clock: &InGameClock,
+        stone: Stone,
+        read_at: Instant,
+    ) -> Option<Self> {
+        if settings == TimeSettings::None {
+            return None;
+        }
+        let side = match stone {
+            Stone::Black => &clock.black,
+            Stone::White => &clock.white,
+        };
+        Some(MoveClock {
+            settings,
+            remaining_ms: side.remaining_ms,
+            periods: side.periods,
+            read_at,
+        })
+    }
+
+    /// The clock now, less `margin` to cover the trip back to the server.
+    fn remaining_now(&self, now: Instant, margin: Duration) -> i64 {
+        let spent = now.saturating_duration_since(self.read_at) + margin;
+        self.remaining_ms - spent.as_millis() as i64
+    }
+
+    /// What to report with `time_left`. The server does not say which phase
+    /// the clock is in, so anything at or below one period counts as byo-yomi,
+    /// with the server's reserve periods plus the one being played.
+    pub fn time_left(&self, now: Instant, margin: Duration) -> TimeLeft {
+        let secs = (self.remaining_now(now, margin).max(0) / 1000) as u32;
+        match self.settings {
+            TimeSettings::ByoYomi { period_secs, .. }
+                if self.remaining_ms <= i64::from(period_secs) * 1000 =>
+            {
+                TimeLeft::ByoYomi {
+                    secs,
+                    periods: self.periods.max(0) as u32 + 1,
+                }
+            }
+            _ => TimeLeft::Main { secs },
+        }
+    }
+
+    /// How long `genmove` may run before we would lose on time.
+    pub fn budget(&self, now: Instant, margin: Duration) -> Duration {
+        let reserve = match self.settings {
+            TimeSettings::ByoYomi { period_secs, .. } => {
+                i64::from(period_secs) * 1000 * i64::from(self.periods.max(0))
+            }
+            _ => 0,
+        };
+        let ms = self.remaining_now(now, margin) + reserve;
+        Duration::from_millis(ms.max(0) as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use seki_api::game::ClockPlayerState;
+
+    fn in_game_clock(black_ms: i64, black_periods: i32) -> InGameClock {
+        InGameClock {
+            clock_type: "byoyomi".to_string(),
+            black: ClockPlayerState {
+                remaining_ms: black_ms,
+                periods: black_periods,
+            },
+            white: ClockPlayerState {
+                remaining_ms: 0,
+                periods: 0,
+            },
+            active_stone: Some(1),
+            server_now_ms: 0,
+        }
+    }
+
+    const BYO_YOMI: TimeSettings = TimeSettings::ByoYomi {
+        main_secs: 600,
+        period_secs: 30,
+        periods: 3,
+    };
+
+    #[test]
+    fn commands_spell_out_each_time_system() {
+        let fischer = TimeSettings::Fischer {
+            main_secs: 300,
+            increment_secs: 10,
+        };
+        assert_eq!(fischer.kgs_command(), "kgs-time_settings fischer 300 10");
+        assert_eq!(fischer.gtp_command(), "time_settings 300 0 0");
+        assert_eq!(BYO_YOMI.kgs_command(), "kgs-time_settings byoyomi 600 30 3");
+        assert_eq!(BYO_YOMI.gtp_command(), "time_settings 600 30 1");
+        assert_eq!(TimeSettings::None.gtp_command(), "time_settings 0 1 0");
+    }
+
+    #[test]
+    fn untimed_games_have_no_move_clock() {
+        let clock = in_game_clock(60_000, 0);
+        let now = Instant::now();
+        assert!(MoveClock::read(TimeSettings::None, &clock, Stone::Black, now).is_none());
+    }
+
+    #[test]
+    fn time_left_subtracts_the_margin_and_time_since_the_reading() {
+        let read_at = Instant::now();
+        let clock =
+            MoveClock::read(BYO_YOMI, &in_game_clock(100_000, 3), Stone::Black, read_at).unwrap();
+
+        let later = read_at + Duration::from_secs(5);
+        assert_eq!(
+            clock.time_left(later, Duration::from_secs(2)),
+            TimeLeft::Main { secs: 93 }
+        );
+        assert_eq!(
+            clock.budget(later, Duration::from_secs(2)),
+            Duration::from_millis(93_000 + 90_000)
+        );
+    }
+
+    #[test]
+    fn byo_yomi_counts_the_current_period() {
+        let read_at = Instant::now();
+        let clock =
+            MoveClock::read(BYO_YOMI, &in_game_clock(20_000, 1), Stone::Black, read_at).unwrap();
+
+        assert_eq!(
+            clock.time_left(read_at, Duration::from_secs(1)),
+            TimeLeft::ByoYomi {
+                secs: 19,
+                periods: 2
+            }
+        );
+        assert_eq!(
+            clock.budget(read_at, Duration::from_secs(1)),
+            Duration::from_millis(19_000 + 30_000)
+        );
+    }
+}
@@ -14,6 +14,8 @@ pub struct Config {
     pub engine: Option<EngineConfig>,
     #[serde(default)]
     pub time: TimeConfig,
+    #[serde(default)]
+    pub scoring: Option<ScoringConfig>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -21,18 +23,102 @@ pub struct EngineConfig {
     pub command: String,
     #[serde(default)]
     pub args: Vec<String>,
+    /// Number of engine processes to run. Each game sticks to one process.
+    #[serde(default = "default_processes")]
+    pub processes: usize,
+    #[serde(default = "default_health_check_interval_s")]
+    pub health_check_interval_s: u64,
+    #[serde(default)]
+    pub profile: EngineProfile,
+}
+
+/// Engine-specific knowledge that `list_commands` cannot tell us.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum EngineProfile {
+    #[default]
+    Generic,
+    Gnugo,
+    Katago,
+    LeelaZero,
+    Pachi,
+}
+
+impl EngineProfile {
+    /// The configured arguments plus whatever the engine needs to speak GTP.
+    pub fn args(self, configured: &[String]) -> Vec<String> {
+        let has = |flag: &str| configured.iter().any(|a| a == flag);
+        let mut args = configured.to_vec();
+        match self {
+            EngineProfile::Gnugo if !has("--mode") => {
+                args.extend(["--mode".to_string(), "gtp".to_string()]);
+            }
+            // `gtp` is a subcommand and must come first.
+            EngineProfile::Katago if !has("gtp") => args.insert(0, "gtp".to_string()),
+            EngineProfile::LeelaZero if !has("--gtp") && !has("-g") => {
+                args.push("--gtp".to_string());
+            }
+            _ => {}
+        }
+        args
+    }
+
+    /// The `genmove` variant that also streams analysis, if the engine has one.
+    pub fn genmove_analyze(self) -> Option<&'static str> {
+        match self {
+            EngineProfile::Katago => Some("kata-genmove_analyze"),
+            EngineProfile::LeelaZero => Some("lz-genmove_analyze"),
+            _ => None,
+        }
+    }
+}
+
+impl Default for EngineConfig {
+    fn default() -> Self {
+        EngineConfig {
+            command: String::new(),
+            args: Vec::new(),
+            processes: default_processes(),
+            health_check_interval_s: default_health_check_interval_s(),
+            profile: EngineProfile::default(),
+        }
+    }
+}
+
+/// Serve territory scoring requests from seki-web with the engine pool.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScoringConfig {
+    /// Address to listen on, e.g. `127.0.0.1:7781`.
+    pub listen: String,
+    /// How long `kata-analyze` runs when the engine lacks `final_status_list`.
+    #[serde(default = "default_analyze_ms")]
+    pub analyze_ms: u64,
+}
+
+impl ScoringConfig {
+    pub fn new(listen: String) -> Self {
+        ScoringConfig {
+            listen,
+            analyze_ms: default_analyze_ms(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct TimeConfig {
     #[serde(default = "default_engine_timeout_ms")]
     pub engine_timeout_ms: u64,
+    /// Held back from the real clock when reporting `time_left` and bounding
+    /// `genmove`, to cover the round trip to the server.
+    #[serde(default = "default_safety_margin_ms")]
+    pub safety_margin_ms: u64,
 }
 
 impl Default for TimeConfig {
     fn default() -> Self {
         TimeConfig {
             engine_timeout_ms: default_engine_timeout_ms(),
+            safety_margin_ms: default_safety_margin_ms(),
         }
     }
 }
@@ -49,13 +135,65 @@ fn default_accept_delay_s() -> u64 {
     10
 }
 
+fn default_processes() -> usize {
+    1
+}
+
+fn default_health_check_interval_s() -> u64 {
+    30
+}
+
+fn default_analyze_ms() -> u64 {
+    2_000
+}
+
 fn default_engine_timeout_ms() -> u64 {
     60_000
 }
 
+fn default_safety_margin_ms() -> u64 {
+    1_000
+}
+
 impl Config {
     pub fn load(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
         let content = std::fs::read_to_string(path)?;
         Ok(toml::from_str(&content)?)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn profiles_add_their_gtp_mode_once() {
+        let katago = EngineProfile::Katago.args(&args(&["-model", "m.bin.gz"]));
+        assert_eq!(katago, args(&["gtp", "-model", "m.bin.gz"]));
+        assert_eq!(EngineProfile::Katago.args(&katago), katago);
+
+        assert_eq!(
+            EngineProfile::Gnugo.args(&args(&["--level", "10"])),
+            args(&["--level", "10", "--mode", "gtp"])
+        );
+        assert_eq!(
+            EngineProfile::LeelaZero.args(&args(&["-g", "-w", "net.gz"])),
+            args(&["-g", "-w", "net.gz"])
+        );
+        assert_eq!(
+            EngineProfile::Pachi.args(&args(&["-t", "=5000"])),
+            args(&["-t", "=5000"])
+        );
+    }
+
+    #[test]
+    fn profile_names_are_kebab_case() {
+        let config: EngineConfig =
+            toml::from_str("command = \"leelaz\"\nprofile = \"leela-zero\"").unwrap();
+        assert_eq!(config.profile, EngineProfile::LeelaZero);
+    }
+}
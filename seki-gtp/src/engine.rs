@@ -1,11 +1,14 @@
+use std::collections::HashSet;
 use std::sync::Arc;
 
-use go_engine::{Stone, Turn};
+use go_engine::{GoError, HandicapPlacement, Move, Point, Ruleset, Stone, Turn};
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::process::{Child, ChildStdin, ChildStdout, Command};
-use tokio::sync::Mutex;
-use tracing::{debug, info};
+use tokio::sync::{Mutex, OwnedMutexGuard};
+use tracing::{debug, info, warn};
 
+use crate::clock::{TimeLeft, TimeSettings};
+use crate::config::{EngineConfig, EngineProfile};
 use crate::gtp::{seki_to_gtp, stone_to_gtp};
 
 #[derive(Debug)]
@@ -18,49 +21,259 @@ pub enum MoveResult {
 #[derive(Clone)]
 pub struct EngineHandle {
     inner: Arc<Mutex<EngineInner>>,
+    turn: Arc<Mutex<()>>,
+}
+
+/// The commands an engine reported in `list_commands`.
+#[derive(Debug, Clone, Default)]
+pub struct Capabilities {
+    commands: Option<HashSet<String>>,
+}
+
+impl Capabilities {
+    pub fn from_list(response: &str) -> Self {
+        let list = response.strip_prefix('=').unwrap_or(response);
+        Capabilities {
+            commands: Some(list.split_whitespace().map(str::to_string).collect()),
+        }
+    }
+
+    /// Until the engine has been probed, or if it cannot list its commands,
+    /// everything is assumed to be there and failures surface as GTP errors.
+    pub fn supports(&self, command: &str) -> bool {
+        self.commands
+            .as_ref()
+            .is_none_or(|commands| commands.contains(command))
+    }
+}
+
+/// A game as laid out on the engine's board.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GameSetup {
+    pub game_id: i64,
+    pub cols: u8,
+    pub rows: u8,
+    pub komi: f64,
+    pub handicap: u8,
+    pub handicap_placement: HandicapPlacement,
+    /// The game's rules, so the mirror accepts exactly the moves the server did.
+    pub ruleset: Ruleset,
+}
+
+impl GameSetup {
+    /// Handicap stones, loaded with `set_free_handicap`, and the moves to
+    /// `play` after them. Free handicap stones are Black's opening moves;
+    /// fixed ones sit on the star points.
+    fn split<'a>(&self, moves: &'a [Turn]) -> (Vec<Point>, &'a [Turn]) {
+        if self.handicap < 2 {
+            (Vec::new(), moves)
+        } else if self.handicap_placement == HandicapPlacement::Free {
+            let placed = moves.len().min(self.handicap as usize);
+            let stones = moves[..placed].iter().filter_map(|t| t.pos).collect();
+            (stones, &moves[placed..])
+        } else {
+            let stones = go_engine::handicap::handicap_points(self.cols, self.rows, self.handicap)
+                .unwrap_or_default();
+            (stones, moves)
+        }
+    }
+
+    /// The position on a `go_engine::Engine`, or `None` if a move does not
+    /// replay there.
+    fn mirror(&self, moves: &[Turn]) -> Option<go_engine::Engine> {
+        let mut board = go_engine::Engine::with_handicap(self.cols, self.rows, self.handicap)
+            .with_handicap_placement(self.handicap_placement)
+            .with_ruleset(self.ruleset);
+        for turn in moves {
+            mirror_turn(&mut board, turn).ok()?;
+        }
+        Some(board)
+    }
+}
+
+fn mirror_turn(board: &mut go_engine::Engine, turn: &Turn) -> Result<(), GoError> {
+    match (turn.kind, turn.pos) {
+        (Move::Play, Some(point)) => board.try_play(turn.stone, point).map(|_| ()),
+        (Move::Play | Move::Pass, _) => board.try_pass(turn.stone).map(|_| ()),
+        (Move::Resign, _) => Ok(()),
+    }
+}
+
+/// The game the engine's board holds, mirrored on a `go_engine::Engine` so
+/// the next position can be reached by sending only what changed.
+struct LoadedGame {
+    setup: GameSetup,
+    board: go_engine::Engine,
+}
+
+/// How [`EngineHandle::load_game`] brought the engine's board up to date.
+#[derive(Debug, PartialEq)]
+pub enum BoardSync {
+    Replayed,
+    Incremental { undone: usize, played: usize },
 }
 
 struct EngineInner {
+    command: String,
+    args: Vec<String>,
+    profile: EngineProfile,
+    capabilities: Capabilities,
     stdin: ChildStdin,
     reader: BufReader<ChildStdout>,
-    _child: Child,
+    child: Child,
     boardsize: Option<(u8, u8)>,
+    time_settings: Option<TimeSettings>,
+    kgs_time: bool,
+    loaded: Option<LoadedGame>,
+    alive: bool,
+    restarts: u64,
+}
+
+fn start_process(
+    command: &str,
+    args: &[String],
+) -> Result<(Child, ChildStdin, BufReader<ChildStdout>), String> {
+    let mut child = Command::new(command)
+        .args(args)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::inherit())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Failed to spawn engine: {e}"))?;
+
+    let stdin = child
+        .stdin
+        .take()
+        .ok_or_else(|| "Failed to open engine stdin".to_string())?;
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| "Failed to open engine stdout".to_string())?;
+
+    Ok((child, stdin, BufReader::new(stdout)))
 }
 
 impl EngineHandle {
     pub fn spawn(command: &str, args: &[String]) -> Result<Self, String> {
-        let mut child = Command::new(command)
-            .args(args)
-            .stdin(std::process::Stdio::piped())
-            .stdout(std::process::Stdio::piped())
-            .stderr(std::process::Stdio::inherit())
-            .spawn()
-            .map_err(|e| format!("Failed to spawn engine: {e}"))?;
-
-        let stdin = child
-            .stdin
-            .take()
-            .ok_or_else(|| "Failed to open engine stdin".to_string())?;
-        let stdout = child
-            .stdout
-            .take()
-            .ok_or_else(|| "Failed to open engine stdout".to_string())?;
+        Self::spawn_with_profile(command, args, EngineProfile::Generic)
+    }
 
-        let reader = BufReader::new(stdout);
+    pub fn spawn_with_profile(
+        command: &str,
+        args: &[String],
+        profile: EngineProfile,
+    ) -> Result<Self, String> {
+        let (child, stdin, reader) = start_process(command, args)?;
 
         Ok(EngineHandle {
             inner: Arc::new(Mutex::new(EngineInner {
+                command: command.to_string(),
+                args: args.to_vec(),
+                profile,
+                capabilities: Capabilities::default(),
                 stdin,
                 reader,
-                _child: child,
+                child,
                 boardsize: None,
+                time_settings: None,
+                kgs_time: false,
+                loaded: None,
+                alive: true,
+                restarts: 0,
             })),
+            turn: Arc::new(Mutex::new(())),
         })
     }
 
+    pub fn same_process(&self, other: &EngineHandle) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+
+    /// Take exclusive use of the engine for a whole setup, replay and genmove
+    /// sequence, so games sharing a process never interleave their commands.
+    pub async fn reserve(&self) -> OwnedMutexGuard<()> {
+        self.turn.clone().lock_owned().await
+    }
+
+    /// Like [`reserve`](Self::reserve), but gives up if a game is using the engine.
+    pub fn try_reserve(&self) -> Option<OwnedMutexGuard<()>> {
+        self.turn.clone().try_lock_owned().ok()
+    }
+
+    /// False once the process has exited or its pipes have broken.
+    pub async fn is_alive(&self) -> bool {
+        let mut inner = self.inner.lock().await;
+        if inner.alive && !matches!(inner.child.try_wait(), Ok(None)) {
+            inner.alive = false;
+        }
+        inner.alive
+    }
+
+    /// How many times the process has been respawned. Any board state the
+    /// engine held before a restart is gone.
+    pub async fn restarts(&self) -> u64 {
+        self.inner.lock().await.restarts
+    }
+
+    /// Kill the current process and start a fresh one with the same command.
+    pub async fn restart(&self) -> Result<(), String> {
+        {
+            let mut inner = self.inner.lock().await;
+            let _ = inner.child.start_kill();
+            let (child, stdin, reader) = start_process(&inner.command, &inner.args)?;
+            inner.child = child;
+            inner.stdin = stdin;
+            inner.reader = reader;
+            inner.boardsize = None;
+            inner.time_settings = None;
+            inner.loaded = None;
+            inner.capabilities = Capabilities::default();
+            inner.alive = true;
+            inner.restarts += 1;
+            info!(
+                "[engine] respawned {} (restart #{})",
+                inner.command, inner.restarts
+            );
+        }
+        self.name().await?;
+        self.probe().await;
+        Ok(())
+    }
+
+    /// Ask the engine which commands it knows. Engines that cannot answer
+    /// keep the assume-everything default.
+    pub async fn probe(&self) -> Capabilities {
+        let capabilities = match self.send_command("list_commands").await {
+            Ok(resp) if resp.starts_with('=') => Capabilities::from_list(&resp),
+            _ => Capabilities::default(),
+        };
+        self.inner.lock().await.capabilities = capabilities.clone();
+        capabilities
+    }
+
+    pub async fn supports(&self, command: &str) -> bool {
+        self.inner.lock().await.capabilities.supports(command)
+    }
+
     pub async fn send_command(&self, cmd: &str) -> Result<String, String> {
         let mut inner = self.inner.lock().await;
+        if !inner.alive {
+            return Err("Engine is not running".to_string());
+        }
+        let result = Self::exchange(&mut inner, cmd).await;
+        if result.is_err() {
+            inner.alive = false;
+        }
+        result
+    }
 
+    async fn exchange(inner: &mut EngineInner, cmd: &str) -> Result<String, String> {
+        Self::write_command(inner, cmd).await?;
+        Self::read_response(inner).await
+    }
+
+    async fn write_command(inner: &mut EngineInner, cmd: &str) -> Result<(), String> {
         let line = format!("{cmd}\n");
         debug!("GTP >>> {line}");
         inner
@@ -72,8 +285,10 @@ impl EngineHandle {
             .stdin
             .flush()
             .await
-            .map_err(|e| format!("Failed to flush engine: {e}"))?;
+            .map_err(|e| format!("Failed to flush engine: {e}"))
+    }
 
+    async fn read_response(inner: &mut EngineInner) -> Result<String, String> {
         let mut response = String::new();
         loop {
             let mut line_buf = String::new();
@@ -118,8 +333,20 @@ impl EngineHandle {
         }
     }
 
+    /// Set the board size. Rectangular boards are sent as `boardsize cols rows`,
+    /// falling back to KataGo's `rectangular_boardsize` if the engine rejects it.
     pub async fn boardsize(&self, cols: u8, rows: u8) -> Result<(), String> {
-        let resp = self.send_command(&format!("boardsize {cols}")).await?;
+        let mut resp = if cols == rows {
+            self.send_command(&format!("boardsize {cols}")).await?
+        } else {
+            self.send_command(&format!("boardsize {cols} {rows}"))
+                .await?
+        };
+        if !resp.starts_with('=') && cols != rows && self.supports("rectangular_boardsize").await {
+            resp = self
+                .send_command(&format!("rectangular_boardsize {cols} {rows}"))
+                .await?;
+        }
         if resp.starts_with('=') {
             let mut inner = self.inner.lock().await;
             inner.boardsize = Some((cols, rows));
@@ -129,7 +356,13 @@ impl EngineHandle {
         }
     }
 
+    async fn board_dims(&self) -> (u8, u8) {
+        let inner = self.inner.lock().await;
+        inner.boardsize.unwrap_or((19, 19))
+    }
+
     pub async fn clear_board(&self) -> Result<(), String> {
+        self.inner.lock().await.loaded = None;
         let resp = self.send_command("clear_board").await?;
         if resp.starts_with('=') {
             Ok(())
@@ -147,6 +380,63 @@ impl EngineHandle {
         }
     }
 
+    /// Tell the engine the game's time control, preferring `kgs-time_settings`
+    /// and falling back to standard `time_settings`. Skipped if the engine
+    /// already has these settings or knows neither command.
+    pub async fn time_settings(&self, settings: TimeSettings) -> Result<(), String> {
+        let (kgs_known, gtp_known) = {
+            let inner = self.inner.lock().await;
+            if inner.time_settings == Some(settings) {
+                return Ok(());
+            }
+            (
+                inner.capabilities.supports("kgs-time_settings"),
+                inner.capabilities.supports("time_settings"),
+            )
+        };
+        let mut kgs = kgs_known;
+        let mut resp = String::new();
+        if kgs_known {
+            resp = self.send_command(&settings.kgs_command()).await?;
+        }
+        if !resp.starts_with('=') {
+            if !gtp_known {
+                return Ok(());
+            }
+            kgs = false;
+            resp = self.send_command(&settings.gtp_command()).await?;
+        }
+        if resp.starts_with('=') {
+            let mut inner = self.inner.lock().await;
+            inner.time_settings = Some(settings);
+            inner.kgs_time = kgs;
+            Ok(())
+        } else {
+            Err(format!("'time_settings' failed: {resp}"))
+        }
+    }
+
+    /// Report our remaining time. Under `kgs-time_settings` byo-yomi the stone
+    /// count is the number of periods left; under the Canadian fallback each
+    /// period is a single stone.
+    pub async fn time_left(&self, stone: Stone, left: TimeLeft) -> Result<(), String> {
+        let kgs = {
+            let inner = self.inner.lock().await;
+            if inner.time_settings.is_none() || !inner.capabilities.supports("time_left") {
+                return Ok(());
+            }
+            inner.kgs_time
+        };
+        let resp = self
+            .send_command(&time_left_command(stone, left, kgs))
+            .await?;
+        if resp.starts_with('=') {
+            Ok(())
+        } else {
+            Err(format!("'time_left' failed: {resp}"))
+        }
+    }
+
     pub async fn play(&self, stone: Stone, gtp_coord: &str) -> Result<(), String> {
         let color = stone_to_gtp(stone);
         let resp = self
@@ -159,10 +449,26 @@ impl EngineHandle {
         }
     }
 
+    pub async fn undo(&self) -> Result<(), String> {
+        let resp = self.send_command("undo").await?;
+        if resp.starts_with('=') {
+            Ok(())
+        } else {
+            Err(format!("'undo' failed: {resp}"))
+        }
+    }
+
+    /// Engines without `set_free_handicap` get the stones as Black moves.
     pub async fn set_free_handicap(&self, coords: &[String]) -> Result<(), String> {
         if coords.is_empty() {
             return Ok(());
         }
+        if !self.supports("set_free_handicap").await {
+            for coord in coords {
+                self.play(Stone::Black, coord).await?;
+            }
+            return Ok(());
+        }
         let cmd = format!("set_free_handicap {}", coords.join(" "));
         let resp = self.send_command(&cmd).await?;
         if resp.starts_with('=') {
@@ -172,29 +478,190 @@ impl EngineHandle {
         }
     }
 
+    /// Let the engine choose `count` handicap points. The stones are placed on
+    /// the engine's board as a side effect.
+    pub async fn place_free_handicap(&self, count: u8) -> Result<Vec<(u8, u8)>, String> {
+        let resp = self
+            .send_command(&format!("place_free_handicap {count}"))
+            .await?;
+        if !resp.starts_with('=') {
+            return Err(format!("'place_free_handicap' failed: {resp}"));
+        }
+        let (cols, rows) = self.board_dims().await;
+        resp[1..]
+            .split_whitespace()
+            .map(|coord| {
+                crate::gtp::gtp_to_seki(coord, cols, rows)
+                    .ok_or_else(|| format!("Engine returned invalid coordinate: {coord}"))
+            })
+            .collect()
+    }
+
+    /// Uses the profile's analysing `genmove` where the engine has it, which
+    /// also reports the engine's view of the score.
     pub async fn genmove(&self, stone: Stone) -> Result<MoveResult, String> {
         let color = stone_to_gtp(stone);
-        let resp = self.send_command(&format!("genmove {color}")).await?;
+        let analyze = {
+            let inner = self.inner.lock().await;
+            inner
+                .profile
+                .genmove_analyze()
+                .filter(|cmd| inner.capabilities.supports(cmd))
+        };
+        let cmd = match analyze {
+            Some(cmd) => format!("{cmd} {color} 100"),
+            None => format!("genmove {color}"),
+        };
+        let resp = self.send_command(&cmd).await?;
 
         if !resp.starts_with('=') {
             return Err(format!("'genmove' failed: {resp}"));
         }
 
-        let result = resp[1..].trim().to_uppercase();
+        let vertex = if analyze.is_some() {
+            let mut vertex = None;
+            for line in resp[1..].lines().map(str::trim) {
+                if let Some(played) = line.strip_prefix("play ") {
+                    vertex = Some(played);
+                } else if line.starts_with("info")
+                    && let (_, Some(lead)) = parse_analysis(line)
+                {
+                    debug!("[engine] {color} leads by {lead:.1}");
+                }
+            }
+            vertex.ok_or_else(|| format!("'genmove' returned no move: {resp}"))?
+        } else {
+            &resp[1..]
+        };
 
-        match result.as_str() {
-            "PASS" => Ok(MoveResult::Pass),
-            "RESIGN" => Ok(MoveResult::Resign),
+        let result = vertex.trim().to_uppercase();
+
+        let result = match result.as_str() {
+            "PASS" => MoveResult::Pass,
+            "RESIGN" => MoveResult::Resign,
             coord => {
-                let board_size = {
-                    let inner = self.inner.lock().await;
-                    inner.boardsize.unwrap_or((19, 19)).0
-                };
-                let (col, row) = crate::gtp::gtp_to_seki(coord, board_size)
+                let (cols, rows) = self.board_dims().await;
+                let (col, row) = crate::gtp::gtp_to_seki(coord, cols, rows)
                     .ok_or_else(|| format!("Engine returned invalid coordinate: {coord}"))?;
-                Ok(MoveResult::Coord { col, row })
+                MoveResult::Coord { col, row }
+            }
+        };
+        self.record_genmove(stone, &result).await;
+        Ok(result)
+    }
+
+    /// `genmove` plays the move on the engine's board, so the mirror follows.
+    async fn record_genmove(&self, stone: Stone, result: &MoveResult) {
+        let mut inner = self.inner.lock().await;
+        let Some(loaded) = inner.loaded.as_mut() else {
+            return;
+        };
+        let mirrored = match *result {
+            MoveResult::Coord { col, row } => loaded.board.try_play(stone, (col, row)).is_ok(),
+            MoveResult::Pass => loaded.board.try_pass(stone).is_ok(),
+            MoveResult::Resign => true,
+        };
+        if !mirrored {
+            inner.loaded = None;
+        }
+    }
+
+    /// Stones the engine considers dead in the current position.
+    pub async fn final_status_dead(&self) -> Result<Vec<(u8, u8)>, String> {
+        let resp = self.send_command("final_status_list dead").await?;
+        if !resp.starts_with('=') {
+            return Err(format!("'final_status_list' failed: {resp}"));
+        }
+        let (cols, rows) = self.board_dims().await;
+        resp[1..]
+            .split_whitespace()
+            .map(|coord| {
+                crate::gtp::gtp_to_seki(coord, cols, rows)
+                    .ok_or_else(|| format!("Engine returned invalid coordinate: {coord}"))
+            })
+            .collect()
+    }
+
+    /// The engine's count, positive when Black is ahead.
+    pub async fn final_score(&self) -> Result<f64, String> {
+        let resp = self.send_command("final_score").await?;
+        if !resp.starts_with('=') {
+            return Err(format!("'final_score' failed: {resp}"));
+        }
+        let result = resp[1..].trim();
+        let parsed = match result.split_once('+') {
+            Some(("B", margin)) => margin.parse::<f64>().ok(),
+            Some(("W", margin)) => margin.parse::<f64>().ok().map(|m| -m),
+            _ if result == "0" => Some(0.0),
+            _ => None,
+        };
+        parsed.ok_or_else(|| format!("Engine returned invalid score: {result}"))
+    }
+
+    /// Run `kata-analyze` for `duration` and return the latest ownership map
+    /// (row-major from the top-left, Black positive) and Black's score lead.
+    /// KataGo reports both from the side to move, which is `to_play` here.
+    pub async fn kata_ownership(
+        &self,
+        to_play: Stone,
+        duration: std::time::Duration,
+    ) -> Result<(Vec<f32>, Option<f64>), String> {
+        let mut inner = self.inner.lock().await;
+        if !inner.alive {
+            return Err("Engine is not running".to_string());
+        }
+        let result = Self::analyze(&mut inner, to_play, duration).await;
+        if result.is_err() {
+            inner.alive = false;
+        }
+        let (ownership, lead) = result?.ok_or("'kata-analyze' failed")?;
+        let sign = match to_play {
+            Stone::Black => 1.0,
+            Stone::White => -1.0,
+        };
+        Ok((
+            ownership.into_iter().map(|v| v * sign as f32).collect(),
+            lead.map(|l| l * sign),
+        ))
+    }
+
+    /// `Ok(None)` means the engine rejected the command; I/O errors leave the
+    /// process unusable.
+    async fn analyze(
+        inner: &mut EngineInner,
+        to_play: Stone,
+        duration: std::time::Duration,
+    ) -> Result<Option<(Vec<f32>, Option<f64>)>, String> {
+        let color = stone_to_gtp(to_play);
+        Self::write_command(inner, &format!("kata-analyze {color} 10 ownership true")).await?;
+
+        let deadline = tokio::time::Instant::now() + duration;
+        let mut latest = None;
+        let mut rejected = false;
+        loop {
+            let mut line = String::new();
+            match tokio::time::timeout_at(deadline, inner.reader.read_line(&mut line)).await {
+                Err(_) => break,
+                Ok(Ok(0)) => return Err("Engine closed stdin".to_string()),
+                Ok(Ok(_)) => {}
+                Ok(Err(e)) => return Err(format!("Failed to read from engine: {e}")),
+            }
+            let line = line.trim();
+            if line.starts_with('?') {
+                rejected = true;
+            } else if line.is_empty() && rejected {
+                return Ok(None);
+            } else if line.starts_with("info") {
+                latest = Some(line.to_string());
             }
         }
+
+        // Any command ends the analysis, whose output closes with a blank
+        // line before the command's own response.
+        Self::write_command(inner, "name").await?;
+        Self::read_response(inner).await?;
+        Self::read_response(inner).await?;
+        Ok(latest.map(|line| parse_analysis(&line)))
     }
 
     pub async fn setup_position(&self, cols: u8, rows: u8, komi: f64) -> Result<(), String> {
@@ -206,32 +673,413 @@ impl EngineHandle {
 
     pub async fn replay_moves(&self, moves: &[Turn]) -> Result<(), String> {
         for turn in moves {
-            if turn.kind == go_engine::Move::Play {
-                if let Some((col, row)) = turn.pos {
-                    let board_size = {
-                        let inner = self.inner.lock().await;
-                        inner.boardsize.unwrap_or((19, 19)).0
-                    };
-                    let gtp_coord = seki_to_gtp(col, row, board_size);
-                    self.play(turn.stone, &gtp_coord).await?;
-                } else {
-                    self.play(turn.stone, "pass").await?;
+            self.play_turn(turn).await?;
+        }
+        Ok(())
+    }
+
+    async fn play_turn(&self, turn: &Turn) -> Result<(), String> {
+        match (turn.kind, turn.pos) {
+            (Move::Play, Some((col, row))) => {
+                let (cols, rows) = self.board_dims().await;
+                self.play(turn.stone, &seki_to_gtp(col, row, cols, rows))
+                    .await
+            }
+            (Move::Play | Move::Pass, _) => self.play(turn.stone, "pass").await,
+            (Move::Resign, _) => Ok(()),
+        }
+    }
+
+    /// Bring the engine's board to `moves` of the given game. If it already
+    /// holds an earlier position of that game, only the difference is sent:
+    /// `undo` for moves taken back, then `play` for new ones. Otherwise, or
+    /// if the engine's board has diverged, the game is loaded from scratch.
+    pub async fn load_game(&self, setup: &GameSetup, moves: &[Turn]) -> Result<BoardSync, String> {
+        let loaded = self.inner.lock().await.loaded.take();
+        if let Some(loaded) = loaded.filter(|loaded| loaded.setup == *setup) {
+            match self.catch_up(loaded.board, moves).await {
+                Ok((board, sync)) => {
+                    self.inner.lock().await.loaded = Some(LoadedGame {
+                        setup: *setup,
+                        board,
+                    });
+                    return Ok(sync);
                 }
-            } else if turn.kind == go_engine::Move::Pass {
-                self.play(turn.stone, "pass").await?;
+                Err(e) if !self.is_alive().await => return Err(e),
+                Err(e) => info!("[engine] game={} {e}, replaying", setup.game_id),
             }
         }
-        Ok(())
+
+        self.setup_position(setup.cols, setup.rows, setup.komi)
+            .await
+            .map_err(|e| format!("setup failed - {e}"))?;
+        let (handicap_stones, rest) = setup.split(moves);
+        let gtp_coords: Vec<String> = handicap_stones
+            .iter()
+            .map(|&(c, r)| seki_to_gtp(c, r, setup.cols, setup.rows))
+            .collect();
+        self.set_free_handicap(&gtp_coords)
+            .await
+            .map_err(|e| format!("handicap placement failed - {e}"))?;
+        self.replay_moves(rest)
+            .await
+            .map_err(|e| format!("replay failed - {e}"))?;
+
+        // A game the mirror cannot follow is replayed in full every time.
+        let mirror = setup.mirror(moves);
+        if mirror.is_none() {
+            warn!(
+                "Game {}: moves do not replay on the mirror, syncing by full replay",
+                setup.game_id
+            );
+        }
+        self.inner.lock().await.loaded = mirror.map(|board| LoadedGame {
+            setup: *setup,
+            board,
+        });
+        Ok(BoardSync::Replayed)
+    }
+
+    /// Take `board`, the engine's position, to `moves`. Fails if the two
+    /// differ before the end of the handicap, if moves were taken back and
+    /// the engine cannot `undo`, or if a new move is illegal on the mirror.
+    async fn catch_up(
+        &self,
+        mut board: go_engine::Engine,
+        moves: &[Turn],
+    ) -> Result<(go_engine::Engine, BoardSync), String> {
+        let known = board.moves();
+        let common = known.iter().zip(moves).take_while(|(a, b)| a == b).count();
+        let undone = known.len() - common;
+        let free_handicap =
+            if board.handicap() >= 2 && board.handicap_placement() == HandicapPlacement::Free {
+                board.handicap() as usize
+            } else {
+                0
+            };
+        if undone > 0 && common < free_handicap {
+            return Err("diverged inside the handicap".to_string());
+        }
+        if undone > 0 && !self.supports("undo").await {
+            return Err(format!("took back {undone} moves without undo"));
+        }
+
+        for _ in 0..undone {
+            self.undo().await?;
+            board.pop_move();
+        }
+        for turn in &moves[common..] {
+            mirror_turn(&mut board, turn)
+                .map_err(|e| format!("diverged at move {} ({e})", board.moves().len()))?;
+            self.play_turn(turn).await?;
+        }
+
+        Ok((
+            board,
+            BoardSync::Incremental {
+                undone,
+                played: moves.len() - common,
+            },
+        ))
     }
 }
 
-pub async fn spawn_engine(command: &str, args: &[String]) -> Result<EngineHandle, String> {
-    info!("[engine] spawning {command} {}", args.join(" "));
-    let handle = EngineHandle::spawn(command, args)?;
+/// Ownership and the best move's `scoreLead` from one `kata-analyze` line.
+fn parse_analysis(line: &str) -> (Vec<f32>, Option<f64>) {
+    let mut tokens = line.split_whitespace();
+    let mut lead = None;
+    let mut ownership = Vec::new();
+    while let Some(token) = tokens.next() {
+        match token {
+            "scoreLead" if lead.is_none() => lead = tokens.next().and_then(|v| v.parse().ok()),
+            "ownership" => ownership = tokens.by_ref().filter_map(|v| v.parse().ok()).collect(),
+            _ => {}
+        }
+    }
+    (ownership, lead)
+}
+
+fn time_left_command(stone: Stone, left: TimeLeft, kgs: bool) -> String {
+    let (secs, stones) = match left {
+        TimeLeft::Main { secs } => (secs, 0),
+        TimeLeft::ByoYomi { secs, periods } => (secs, if kgs { periods } else { 1 }),
+    };
+    format!("time_left {} {secs} {stones}", stone_to_gtp(stone))
+}
+
+/// Commands the bridge can do without, but uses when the engine has them.
+const OPTIONAL_COMMANDS: &[&str] = &[
+    "place_free_handicap",
+    "set_free_handicap",
+    "kgs-time_settings",
+    "time_settings",
+    "time_left",
+    "final_status_list",
+    "kata-analyze",
+    "undo",
+];
+
+pub async fn spawn_engine(config: &EngineConfig) -> Result<EngineHandle, String> {
+    let args = config.profile.args(&config.args);
+    info!("[engine] spawning {} {}", config.command, args.join(" "));
+    let handle = EngineHandle::spawn_with_profile(&config.command, &args, config.profile)?;
 
     let name = handle.name().await?;
     let version = handle.version().await?;
     info!("[engine] {name} v{version}");
 
+    let capabilities = handle.probe().await;
+    let missing: Vec<&str> = OPTIONAL_COMMANDS
+        .iter()
+        .copied()
+        .filter(|cmd| !capabilities.supports(cmd))
+        .collect();
+    if !missing.is_empty() {
+        info!("[engine] {name} lacks {}", missing.join(", "));
+    }
+
     Ok(handle)
 }
+
+/// Run `script` under `sh` as a stand-in GTP engine.
+#[cfg(test)]
+pub(crate) fn scripted_engine(script: &str) -> EngineHandle {
+    EngineHandle::spawn("sh", &["-c".to_string(), script.to_string()]).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Accepts every command except the `kgs-*` extensions.
+    fn plain_gtp_engine() -> EngineHandle {
+        let script = r#"while read -r cmd rest; do
+            case "$cmd" in
+                kgs-*) printf '? unknown command\n\n' ;;
+                *) printf '= \n\n' ;;
+            esac
+        done"#;
+        scripted_engine(script)
+    }
+
+    #[tokio::test]
+    async fn time_settings_fall_back_to_standard_gtp() {
+        let engine = plain_gtp_engine();
+        let settings = TimeSettings::ByoYomi {
+            main_secs: 600,
+            period_secs: 30,
+            periods: 5,
+        };
+        engine.time_settings(settings).await.unwrap();
+
+        let inner = engine.inner.lock().await;
+        assert_eq!(inner.time_settings, Some(settings));
+        assert!(!inner.kgs_time);
+    }
+
+    #[tokio::test]
+    async fn missing_set_free_handicap_falls_back_to_black_moves() {
+        // Rejects set_free_handicap, so sending it anyway would fail the test.
+        let script = r#"while read -r cmd rest; do
+            case "$cmd" in
+                list_commands) printf '= name\nplay\ngenmove\nlist_commands\n\n' ;;
+                set_free_handicap) printf '? unknown command\n\n' ;;
+                *) printf '= \n\n' ;;
+            esac
+        done"#;
+        let engine = scripted_engine(script);
+
+        let capabilities = engine.probe().await;
+        assert!(capabilities.supports("play"));
+        assert!(!capabilities.supports("set_free_handicap"));
+        engine
+            .set_free_handicap(&["D4".to_string(), "Q16".to_string()])
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn katago_profile_genmoves_with_analysis() {
+        let script = r#"while read -r cmd rest; do
+            case "$cmd" in
+                list_commands) printf '= name\nkata-genmove_analyze\n\n' ;;
+                kata-genmove_analyze)
+                    printf '=\ninfo move D4 visits 50 scoreLead 2.5 pv D4\nplay D4\n\n' ;;
+                genmove) printf '= A1\n\n' ;;
+                *) printf '= \n\n' ;;
+            esac
+        done"#;
+        let engine = EngineHandle::spawn_with_profile(
+            "sh",
+            &["-c".to_string(), script.to_string()],
+            EngineProfile::Katago,
+        )
+        .unwrap();
+        engine.probe().await;
+        engine.boardsize(19, 19).await.unwrap();
+
+        let result = engine.genmove(Stone::Black).await.unwrap();
+        assert!(matches!(result, MoveResult::Coord { col: 3, row: 15 }));
+    }
+
+    fn nine_by_nine(game_id: i64) -> GameSetup {
+        GameSetup {
+            game_id,
+            cols: 9,
+            rows: 9,
+            komi: 6.5,
+            handicap: 0,
+            handicap_placement: HandicapPlacement::Fixed,
+            ruleset: Ruleset::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn known_games_only_get_the_moves_that_changed() {
+        let script = r#"while read -r cmd rest; do
+            case "$cmd" in
+                genmove) printf '= E5\n\n' ;;
+                *) printf '= \n\n' ;;
+            esac
+        done"#;
+        let engine = scripted_engine(script);
+        let setup = nine_by_nine(1);
+        let moves = [
+            Turn::play(Stone::Black, (2, 2)),
+            Turn::play(Stone::White, (6, 6)),
+            Turn::pass(Stone::Black),
+        ];
+
+        assert_eq!(
+            engine.load_game(&setup, &moves[..1]).await.unwrap(),
+            BoardSync::Replayed
+        );
+        assert_eq!(
+            engine.load_game(&setup, &moves).await.unwrap(),
+            BoardSync::Incremental {
+                undone: 0,
+                played: 2
+            }
+        );
+        // An accepted undo takes the last two moves back.
+        assert_eq!(
+            engine.load_game(&setup, &moves[..1]).await.unwrap(),
+            BoardSync::Incremental {
+                undone: 2,
+                played: 0
+            }
+        );
+
+        // The engine's own move is already on its board.
+        engine.genmove(Stone::White).await.unwrap();
+        let with_reply = [moves[0].clone(), Turn::play(Stone::White, (4, 4))];
+        assert_eq!(
+            engine.load_game(&setup, &with_reply).await.unwrap(),
+            BoardSync::Incremental {
+                undone: 0,
+                played: 0
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn mirror_follows_the_game_ruleset() {
+        let engine = scripted_engine(r#"while read -r cmd rest; do printf '= \n\n'; done"#);
+        let setup = GameSetup {
+            ruleset: Ruleset::NEW_ZEALAND,
+            ..nine_by_nine(1)
+        };
+        // Black's last stone is a two-stone suicide, which New Zealand rules allow.
+        let moves = [
+            Turn::play(Stone::Black, (0, 0)),
+            Turn::play(Stone::White, (2, 0)),
+            Turn::play(Stone::Black, (5, 5)),
+            Turn::play(Stone::White, (0, 1)),
+            Turn::play(Stone::Black, (6, 6)),
+            Turn::play(Stone::White, (1, 1)),
+            Turn::play(Stone::Black, (1, 0)),
+            Turn::play(Stone::White, (4, 4)),
+        ];
+
+        assert_eq!(
+            engine.load_game(&setup, &moves[..7]).await.unwrap(),
+            BoardSync::Replayed
+        );
+        assert_eq!(
+            engine.load_game(&setup, &moves).await.unwrap(),
+            BoardSync::Incremental {
+                undone: 0,
+                played: 1
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn other_games_and_missing_undo_replay_from_scratch() {
+        let script = r#"while read -r cmd rest; do
+            case "$cmd" in
+                list_commands) printf '= boardsize\nclear_board\nkomi\nplay\ngenmove\n\n' ;;
+                *) printf '= \n\n' ;;
+            esac
+        done"#;
+        let engine = scripted_engine(script);
+        engine.probe().await;
+        let moves = [
+            Turn::play(Stone::Black, (2, 2)),
+            Turn::play(Stone::White, (6, 6)),
+        ];
+
+        engine.load_game(&nine_by_nine(1), &moves).await.unwrap();
+        assert_eq!(
+            engine.load_game(&nine_by_nine(2), &moves).await.unwrap(),
+            BoardSync::Replayed
+        );
+        assert_eq!(
+            engine
+                .load_game(&nine_by_nine(2), &moves[..1])
+                .await
+                .unwrap(),
+            BoardSync::Replayed
+        );
+        // A move the mirror rejects means the positions disagree.
+        let occupied = [moves[0].clone(), Turn::play(Stone::White, (2, 2))];
+        assert_eq!(
+            engine.load_game(&nine_by_nine(2), &occupied).await.unwrap(),
+            BoardSync::Replayed
+        );
+
+        engine.restart().await.unwrap();
+        assert_eq!(
+            engine.load_game(&nine_by_nine(2), &moves).await.unwrap(),
+            BoardSync::Replayed
+        );
+    }
+
+    #[test]
+    fn unprobed_engines_are_assumed_to_support_everything() {
+        assert!(Capabilities::default().supports("undo"));
+        let listed = Capabilities::from_list("= name\nplay\nundo\n");
+        assert!(listed.supports("undo"));
+        assert!(!listed.supports("kata-analyze"));
+    }
+
+    #[test]
+    fn byo_yomi_stones_depend_on_the_time_system() {
+        let left = TimeLeft::ByoYomi {
+            secs: 25,
+            periods: 4,
+        };
+        assert_eq!(
+            time_left_command(Stone::Black, left, true),
+            "time_left B 25 4"
+        );
+        assert_eq!(
+            time_left_command(Stone::Black, left, false),
+            "time_left B 25 1"
+        );
+        assert_eq!(
+            time_left_command(Stone::White, TimeLeft::Main { secs: 90 }, true),
+            "time_left W 90 0"
+        );
+    }
+}
@@ -43,18 +43,19 @@ impl fmt::Display for GtpResponse {
 }
 
 /// Convert a seki (0-indexed, top-left) coordinate to GTP format (column letter A-T, row from bottom).
-pub fn seki_to_gtp(col: u8, row: u8, size: u8) -> String {
+pub fn seki_to_gtp(col: u8, row: u8, cols: u8, rows: u8) -> String {
+    debug_assert!(col < cols && row < rows, "point off a {cols}x{rows} board");
     let col_char = if col >= 8 {
         (b'A' + col + 1) as char
     } else {
         (b'A' + col) as char
     };
-    let row_num = size - row;
+    let row_num = rows - row;
     format!("{col_char}{row_num}")
 }
 
-/// Convert a GTP coordinate string to seki (0-indexed, top-left).
-pub fn gtp_to_seki(gtp: &str, size: u8) -> Option<(u8, u8)> {
+/// Convert a GTP coordinate string to seki (0-indexed, top-left) on a `cols`x`rows` board.
+pub fn gtp_to_seki(gtp: &str, cols: u8, rows: u8) -> Option<(u8, u8)> {
     let gtp = gtp.trim().to_uppercase();
     if gtp.len() < 2 {
         return None;
@@ -63,7 +64,7 @@ pub fn gtp_to_seki(gtp: &str, size: u8) -> Option<(u8, u8)> {
     let col_char = bytes[0];
     let row_str = &gtp[1..];
     let row_num: u8 = row_str.parse().ok()?;
-    if row_num < 1 || row_num > size {
+    if row_num < 1 || row_num > rows {
         return None;
     }
     let col = if col_char > b'I' {
@@ -71,10 +72,10 @@ pub fn gtp_to_seki(gtp: &str, size: u8) -> Option<(u8, u8)> {
     } else {
         col_char - b'A'
     };
-    if col >= size {
+    if col >= cols {
         return None;
     }
-    let row = size - row_num;
+    let row = rows - row_num;
     Some((col, row))
 }
 
@@ -132,19 +133,35 @@ mod tests {
 
     #[test]
     fn test_gtp_to_seki() {
-        assert_eq!(gtp_to_seki("A1", 19), Some((0, 18)));
-        assert_eq!(gtp_to_seki("T19", 19), Some((18, 0)));
-        assert_eq!(gtp_to_seki("J1", 19), Some((8, 18)));
-        assert_eq!(gtp_to_seki("A19", 19), Some((0, 0)));
-        assert_eq!(gtp_to_seki("pass", 19), None);
+        assert_eq!(gtp_to_seki("A1", 19, 19), Some((0, 18)));
+        assert_eq!(gtp_to_seki("T19", 19, 19), Some((18, 0)));
+        assert_eq!(gtp_to_seki("J1", 19, 19), Some((8, 18)));
+        assert_eq!(gtp_to_seki("A19", 19, 19), Some((0, 0)));
+        assert_eq!(gtp_to_seki("pass", 19, 19), None);
+    }
+
+    #[test]
+    fn test_rectangular_coords() {
+        // 9 columns (A-J, no I), 13 rows
+        assert_eq!(gtp_to_seki("A13", 9, 13), Some((0, 0)));
+        assert_eq!(gtp_to_seki("J1", 9, 13), Some((8, 12)));
+        assert_eq!(gtp_to_seki("K1", 9, 13), None);
+        assert_eq!(gtp_to_seki("A14", 9, 13), None);
+        assert_eq!(seki_to_gtp(8, 12, 9, 13), "J1");
+        assert_eq!(seki_to_gtp(0, 0, 9, 13), "A13");
+
+        // 19 columns, 7 rows
+        assert_eq!(gtp_to_seki("T7", 19, 7), Some((18, 0)));
+        assert_eq!(gtp_to_seki("T8", 19, 7), None);
+        assert_eq!(seki_to_gtp(18, 6, 19, 7), "T1");
     }
 
     #[test]
     fn test_seki_to_gtp() {
-        assert_eq!(seki_to_gtp(0, 18, 19), "A1");
-        assert_eq!(seki_to_gtp(18, 0, 19), "T19");
-        assert_eq!(seki_to_gtp(8, 18, 19), "J1");
-        assert_eq!(seki_to_gtp(0, 0, 19), "A19");
+        assert_eq!(seki_to_gtp(0, 18, 19, 19), "A1");
+        assert_eq!(seki_to_gtp(18, 0, 19, 19), "T19");
+        assert_eq!(seki_to_gtp(8, 18, 19, 19), "J1");
+        assert_eq!(seki_to_gtp(0, 0, 19, 19), "A19");
     }
 
     #[test]
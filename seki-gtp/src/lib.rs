@@ -1,4 +1,7 @@
 pub mod bot;
+pub mod clock;
 pub mod config;
 pub mod engine;
 pub mod gtp;
+pub mod pool;
+pub mod scoring;
@@ -1,11 +1,12 @@
 use std::path::PathBuf;
+use std::time::Duration;
 
 use clap::Parser;
 use tracing_subscriber::EnvFilter;
 
 use seki_gtp::bot::Bot;
 use seki_gtp::config::Config;
-use seki_gtp::engine::spawn_engine;
+use seki_gtp::pool::EnginePool;
 
 #[derive(Parser, Debug)]
 #[command(name = "seki-gtp", about = "GTP bridge for the seki Go server")]
@@ -28,6 +29,18 @@ struct Cli {
     )]
     accept_delay: Option<u64>,
 
+    #[arg(long, help = "Number of engine processes (overrides config)")]
+    engines: Option<usize>,
+
+    #[arg(long, value_enum, help = "Engine profile (overrides config)")]
+    profile: Option<seki_gtp::config::EngineProfile>,
+
+    #[arg(
+        long,
+        help = "Serve territory scoring for seki-web on this address (overrides config)"
+    )]
+    score_listen: Option<String>,
+
     #[arg(short, long, help = "Enable verbose logging")]
     verbose: bool,
 
@@ -61,6 +74,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             accept_delay_s: 30,
             engine: None,
             time: seki_gtp::config::TimeConfig::default(),
+            scoring: None,
         }
     };
 
@@ -76,12 +90,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     if let Some(delay) = cli.accept_delay {
         config.accept_delay_s = delay;
     }
-
-    let engine_cfg = if !cli.engine.is_empty() {
-        seki_gtp::config::EngineConfig {
-            command: cli.engine[0].clone(),
-            args: cli.engine[1..].to_vec(),
+    if let Some(listen) = cli.score_listen {
+        match &mut config.scoring {
+            Some(scoring) => scoring.listen = listen,
+            None => config.scoring = Some(seki_gtp::config::ScoringConfig::new(listen)),
         }
+    }
+
+    let mut engine_cfg = if !cli.engine.is_empty() {
+        let mut engine_cfg = config.engine.clone().unwrap_or_default();
+        engine_cfg.command = cli.engine[0].clone();
+        engine_cfg.args = cli.engine[1..].to_vec();
+        engine_cfg
     } else {
         config.engine.clone().unwrap_or_else(|| {
             eprintln!("Error: Engine command is required. Pass it after --.");
@@ -89,11 +109,38 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         })
     };
 
-    if config.api_token.is_empty() {
+    if let Some(processes) = cli.engines {
+        engine_cfg.processes = processes;
+    }
+    if let Some(profile) = cli.profile {
+        engine_cfg.profile = profile;
+    }
+
+    if config.api_token.is_empty() && config.scoring.is_none() {
         eprintln!("Error: API token is required. Set it in config or pass --token.");
         std::process::exit(1);
     }
 
+    let engines = EnginePool::spawn(&engine_cfg).await?;
+    engines.spawn_health_checks(Duration::from_secs(
+        engine_cfg.health_check_interval_s.max(1),
+    ));
+
+    if let Some(scoring) = &config.scoring {
+        let listener = tokio::net::TcpListener::bind(&scoring.listen).await?;
+        let serve = seki_gtp::scoring::serve(
+            listener,
+            engines.clone(),
+            Duration::from_millis(scoring.analyze_ms),
+        );
+        // Without a token this is a scoring worker only.
+        if config.api_token.is_empty() {
+            serve.await;
+            return Ok(());
+        }
+        tokio::spawn(serve);
+    }
+
     let http = seki_client::http::HttpClient::new(&config.server_url, &config.api_token);
     let me = http.get_with_retry::<serde_json::Value>("/api/me").await;
     let user_id = me["id"]
@@ -102,9 +149,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let username = me["username"].as_str().unwrap_or("unknown");
     tracing::info!("[auth] authenticated as {} (id={})", username, user_id);
 
-    let engine = spawn_engine(&engine_cfg.command, &engine_cfg.args).await?;
-
-    Bot::run(config, engine, user_id)
+    Bot::run(config, engines, user_id)
         .await
         .map_err(|e| format!("Bot error: {e}"))?;
 
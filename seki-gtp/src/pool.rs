@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tracing::{error, info, warn};
+
+use crate::config::EngineConfig;
+use crate::engine::{EngineHandle, spawn_engine};
+
+const PING_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A fixed set of engine processes. Each game is pinned to one process for
+/// its whole lifetime, so the engine's board can carry over between moves.
+#[derive(Clone)]
+pub struct EnginePool {
+    engines: Arc<Vec<EngineHandle>>,
+    games: Arc<Mutex<HashMap<i64, usize>>>,
+}
+
+impl EnginePool {
+    pub fn new(engines: Vec<EngineHandle>) -> Self {
+        assert!(
+            !engines.is_empty(),
+            "engine pool needs at least one process"
+        );
+        EnginePool {
+            engines: Arc::new(engines),
+            games: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub async fn spawn(config: &EngineConfig) -> Result<Self, String> {
+        let mut engines = Vec::with_capacity(config.processes.max(1));
+        for _ in 0..config.processes.max(1) {
+            engines.push(spawn_engine(config).await?);
+        }
+        Ok(EnginePool::new(engines))
+    }
+
+    /// The engine pinned to `game_id`. New games go to the process serving
+    /// the fewest games.
+    pub fn engine_for(&self, game_id: i64) -> EngineHandle {
+        let mut games = self.games.lock().unwrap();
+        let index = match games.get(&game_id) {
+            Some(&index) => index,
+            None => {
+                let index = self.least_loaded(&games);
+                info!("[pool] game={game_id} assigned to engine #{index}");
+                games.insert(game_id, index);
+                index
+            }
+        };
+        self.engines[index].clone()
+    }
+
+    /// An engine for a one-off job, such as scoring, without pinning it.
+    pub fn any_engine(&self) -> EngineHandle {
+        let games = self.games.lock().unwrap();
+        self.engines[self.least_loaded(&games)].clone()
+    }
+
+    fn least_loaded(&self, games: &HashMap<i64, usize>) -> usize {
+        let mut load = vec![0usize; self.engines.len()];
+        for &index in games.values() {
+            load[index] += 1;
+        }
+        (0..load.len()).min_by_key(|&i| load[i]).unwrap_or(0)
+    }
+
+    pub fn release(&self, game_id: i64) {
+        self.games.lock().unwrap().remove(&game_id);
+    }
+
+    /// Respawn every idle engine that has exited or stopped answering.
+    /// Engines busy with a game are left alone.
+    pub async fn check_health(&self) {
+        for (index, engine) in self.engines.iter().enumerate() {
+            let Some(_turn) = engine.try_reserve() else {
+                continue;
+            };
+            let healthy = engine.is_alive().await
+                && matches!(
+                    tokio::time::timeout(PING_TIMEOUT, engine.name()).await,
+                    Ok(Ok(_))
+                );
+            if !healthy {
+                warn!("[pool] engine #{index} is unresponsive, respawning");
+                if let Err(e) = engine.restart().await {
+                    error!("[pool] engine #{index} respawn failed - {e}");
+                }
+            }
+        }
+    }
+
+    pub fn spawn_health_checks(&self, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let pool = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                pool.check_health().await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::scripted_engine;
+
+    /// Answers every command with an empty success and dies on `crash`.
+    fn fake_engine() -> EngineHandle {
+        let script = r#"while read -r cmd rest; do
+            case "$cmd" in
+                crash) exit 1 ;;
+                *) printf '= \n\n' ;;
+            esac
+        done"#;
+        scripted_engine(script)
+    }
+
+    #[tokio::test]
+    async fn games_stick_to_one_engine_and_spread_across_the_pool() {
+        let pool = EnginePool::new(vec![fake_engine(), fake_engine()]);
+
+        let a = pool.engine_for(1);
+        let b = pool.engine_for(2);
+        assert!(!a.same_process(&b));
+        assert!(pool.engine_for(1).same_process(&a));
+
+        pool.release(1);
+        let c = pool.engine_for(3);
+        assert!(c.same_process(&a));
+    }
+
+    #[tokio::test]
+    async fn health_check_respawns_a_crashed_engine() {
+        let pool = EnginePool::new(vec![fake_engine()]);
+        let engine = pool.engine_for(1);
+
+        assert!(engine.send_command("crash").await.is_err());
+        assert!(!engine.is_alive().await);
+        assert!(engine.send_command("name").await.is_err());
+
+        pool.check_health().await;
+        assert!(engine.is_alive().await);
+        assert_eq!(engine.restarts().await, 1);
+        assert!(engine.send_command("name").await.is_ok());
+    }
+}
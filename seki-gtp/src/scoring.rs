@@ -0,0 +1,247 @@
+use std::time::Duration;
+
+use go_engine::Stone;
+use seki_api::scoring::{ScoreRequest, ScoreResponse};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tracing::{info, warn};
+
+use crate::engine::EngineHandle;
+use crate::gtp::seki_to_gtp;
+use crate::pool::EnginePool;
+
+/// Ownership at least this far toward the opponent marks a stone dead.
+const DEAD_OWNERSHIP: f32 = 0.5;
+
+/// Answer scoring requests from seki-web, one per connection.
+pub async fn serve(listener: TcpListener, engines: EnginePool, analyze_time: Duration) {
+    if let Ok(addr) = listener.local_addr() {
+        info!("[scoring] listening on {addr}");
+    }
+    loop {
+        match listener.accept().await {
+            Ok((stream, peer)) => {
+                let engines = engines.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = handle_connection(stream, &engines, analyze_time).await {
+                        warn!("[scoring] {peer}: {e}");
+                    }
+                });
+            }
+            Err(e) => warn!("[scoring] accept failed - {e}"),
+        }
+    }
+}
+
+async fn handle_connection(
+    stream: TcpStream,
+    engines: &EnginePool,
+    analyze_time: Duration,
+) -> Result<(), String> {
+    let (reader, mut writer) = stream.into_split();
+    let mut line = String::new();
+    BufReader::new(reader)
+        .read_line(&mut line)
+        .await
+        .map_err(|e| format!("Failed to read request: {e}"))?;
+
+    let response = match serde_json::from_str::<ScoreRequest>(&line) {
+        Ok(request) => {
+            info!("[scoring] scoring game={}", request.game_id);
+            score(&engines.any_engine(), &request, analyze_time)
+                .await
+                .unwrap_or_else(|e| ScoreResponse {
+                    error: Some(e),
+                    ..ScoreResponse::default()
+                })
+        }
+        Err(e) => ScoreResponse {
+            error: Some(format!("Invalid request: {e}")),
+            ..ScoreResponse::default()
+        },
+    };
+
+    let mut json = serde_json::to_string(&response).map_err(|e| e.to_string())?;
+    json.push('\n');
+    writer
+        .write_all(json.as_bytes())
+        .await
+        .map_err(|e| format!("Failed to write response: {e}"))
+}
+
+/// Load the position and ask for dead stones with `final_status_list`,
+/// falling back to `kata-analyze` ownership for engines without it.
+pub async fn score(
+    engine: &EngineHandle,
+    request: &ScoreRequest,
+    analyze_time: Duration,
+) -> Result<ScoreResponse, String> {
+    let _turn = engine.reserve().await;
+    if !engine.is_alive().await {
+        engine.restart().await?;
+    }
+
+    let (cols, rows) = (request.cols, request.rows);
+    engine.setup_position(cols, rows, request.komi).await?;
+    // Placing a legal position's stones in any order never leaves a chain
+    // without liberties, so nothing is captured on the way.
+    for (stone, points) in [
+        (Stone::Black, &request.black),
+        (Stone::White, &request.white),
+    ] {
+        for &[col, row] in points {
+            engine
+                .play(stone, &seki_to_gtp(col, row, cols, rows))
+                .await?;
+        }
+    }
+
+    let mut status_err = None;
+    if engine.supports("final_status_list").await {
+        match engine.final_status_dead().await {
+            Ok(dead) => {
+                let score = if engine.supports("final_score").await {
+                    engine.final_score().await.ok()
+                } else {
+                    None
+                };
+                return Ok(ScoreResponse {
+                    dead_stones: dead.into_iter().map(|(col, row)| [col, row]).collect(),
+                    score,
+                    error: None,
+                });
+            }
+            Err(e) => status_err = Some(e),
+        }
+    }
+    if !engine.supports("kata-analyze").await {
+        return Err(status_err
+            .unwrap_or_else(|| "Engine has neither final_status_list nor kata-analyze".into()));
+    }
+
+    let (ownership, lead) = engine
+        .kata_ownership(Stone::Black, analyze_time)
+        .await
+        .map_err(|e| match &status_err {
+            Some(status_err) => format!("{status_err}; {e}"),
+            None => e,
+        })?;
+    if ownership.len() != cols as usize * rows as usize {
+        return Err(format!(
+            "Engine returned {} ownership values for a {cols}x{rows} board",
+            ownership.len()
+        ));
+    }
+    let owner = |[col, row]: [u8; 2]| ownership[row as usize * cols as usize + col as usize];
+    let dead_black = request
+        .black
+        .iter()
+        .copied()
+        .filter(|&p| owner(p) <= -DEAD_OWNERSHIP);
+    let dead_white = request
+        .white
+        .iter()
+        .copied()
+        .filter(|&p| owner(p) >= DEAD_OWNERSHIP);
+    Ok(ScoreResponse {
+        dead_stones: dead_black.chain(dead_white).collect(),
+        score: lead,
+        error: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::scripted_engine;
+
+    fn request() -> ScoreRequest {
+        ScoreRequest {
+            game_id: 1,
+            cols: 3,
+            rows: 3,
+            komi: 0.5,
+            black: vec![[0, 0], [1, 1]],
+            white: vec![[2, 2]],
+        }
+    }
+
+    #[tokio::test]
+    async fn dead_stones_come_from_final_status_list() {
+        let engine = scripted_engine(
+            r#"while read -r cmd rest; do
+                case "$cmd" in
+                    final_status_list) printf '= C1\n\n' ;;
+                    final_score) printf '= B+4.5\n\n' ;;
+                    *) printf '= \n\n' ;;
+                esac
+            done"#,
+        );
+
+        let response = score(&engine, &request(), Duration::from_millis(50))
+            .await
+            .unwrap();
+        assert_eq!(response.dead_stones, vec![[2, 2]]);
+        assert_eq!(response.score, Some(4.5));
+    }
+
+    #[tokio::test]
+    async fn kata_analyze_ownership_is_the_fallback() {
+        // Analysis keeps streaming until the next command arrives, then ends
+        // with a blank line before that command's response.
+        let engine = scripted_engine(
+            r#"while read -r cmd rest; do
+                case "$cmd" in
+                    final_status_list) printf '? unknown command\n\n' ;;
+                    kata-analyze)
+                        printf '=\n'
+                        printf 'info move B2 visits 10 scoreLead -3.0 pv B2 ownership -0.9 0.1 0.2 0 0.8 0.3 0 0 -0.95\n'
+                        read -r next
+                        printf '\n= KataGo\n\n' ;;
+                    *) printf '= \n\n' ;;
+                esac
+            done"#,
+        );
+
+        let response = score(&engine, &request(), Duration::from_millis(200))
+            .await
+            .unwrap();
+        assert_eq!(response.dead_stones, vec![[0, 0]]);
+        assert_eq!(response.score, Some(-3.0));
+        // The engine is still in step after stopping the analysis.
+        assert_eq!(engine.name().await.unwrap(), "");
+    }
+
+    #[tokio::test]
+    async fn serve_answers_one_json_line_per_connection() {
+        let engine = scripted_engine(
+            r#"while read -r cmd rest; do
+                case "$cmd" in
+                    final_status_list) printf '= A3 B2\n\n' ;;
+                    final_score) printf '= W+12\n\n' ;;
+                    *) printf '= \n\n' ;;
+                esac
+            done"#,
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(serve(
+            listener,
+            EnginePool::new(vec![engine]),
+            Duration::from_millis(50),
+        ));
+
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let (reader, mut writer) = stream.into_split();
+        let mut line = serde_json::to_string(&request()).unwrap();
+        line.push('\n');
+        writer.write_all(line.as_bytes()).await.unwrap();
+        let mut reply = String::new();
+        BufReader::new(reader).read_line(&mut reply).await.unwrap();
+
+        let response: ScoreResponse = serde_json::from_str(&reply).unwrap();
+        assert_eq!(response.dead_stones, vec![[0, 0], [1, 1]]);
+        assert_eq!(response.score, Some(-12.0));
+        assert!(response.error.is_none());
+    }
+}
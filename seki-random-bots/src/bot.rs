@@ -337,7 +337,7 @@ impl BotRunner {
             .filter(|g| {
                 matches!(
                     g.stage.as_str(),
-                    "black_to_play" | "white_to_play" | "territory_review"
+                    "handicap_placement" | "black_to_play" | "white_to_play" | "territory_review"
                 ) || (matches!(g.stage.as_str(), "challenge" | "unstarted") && g.has_opponent)
             })
             .count();
@@ -384,10 +384,14 @@ impl BotRunner {
             .my_games
             .iter()
             .filter(|(_, g)| {
-                matches!(g.stage.as_str(), "black_to_play" | "white_to_play")
-                    && g.board.is_some()
+                matches!(
+                    g.stage.as_str(),
+                    "handicap_placement" | "black_to_play" | "white_to_play"
+                ) && g.board.is_some()
                     && g.our_stone.is_some_and(|s| match s {
-                        Stone::Black => g.stage == "black_to_play",
+                        Stone::Black => {
+                            matches!(g.stage.as_str(), "handicap_placement" | "black_to_play")
+                        }
                         Stone::White => g.stage == "white_to_play",
                     })
             })
@@ -396,10 +400,18 @@ impl BotRunner {
 
         for game_id in &playable {
             let action = pick_game_action(&mut self.rng, &self.config.probabilities);
+            // Passing is not allowed while free handicap stones are being placed.
+            let placing = self
+                .my_games
+                .get(game_id)
+                .is_some_and(|g| g.stage == "handicap_placement");
             match action {
                 GameAction::Play => {
                     self.play_random_move(*game_id);
                 }
+                GameAction::Pass if placing => {
+                    self.play_random_move(*game_id);
+                }
                 GameAction::Pass => {
                     info!("{} passing in game {game_id}", self.bot_name());
                     send_json(&self.ws_tx, &ClientMsg::pass(*game_id));
@@ -511,7 +523,12 @@ fn is_joinable(game: &LiveGameItem) -> bool {
     }
     matches!(
         game.stage.as_str(),
-        "challenge" | "unstarted" | "black_to_play" | "white_to_play" | "territory_review"
+        "challenge"
+            | "unstarted"
+            | "handicap_placement"
+            | "black_to_play"
+            | "white_to_play"
+            | "territory_review"
     ) && (game.black.is_none() || game.white.is_none())
 }
 
@@ -32,6 +32,8 @@ type EngineWasmAiPoc = EngineWasm & {
 type PolicyMctsSearch = {
   next_batch_json(batchSize: number): string;
   apply_batch_json(evaluationsJson: string): string;
+  advance_json(moveJson: string): string;
+  set_elapsed_ms(elapsedMs: number): void;
   summary_json(): string;
 };
 type RustMctsRequest = AiPocRandomMctsRequest | AiPocRustPolicyMctsRequest;
@@ -74,6 +76,10 @@ type RustRandomMctsResponse = {
   rootEdges: AiPocRandomMctsEdge[];
   principalVariation: AiPocRandomMctsMove[];
   diagnostics?: AiPocRandomMctsDiagnostics;
+  elapsedMs?: number;
+  stopReason?: "visits" | "time" | "decided" | null;
+  ownership?: number[] | null;
+  scoreLead?: number | null;
 };
 
 type PolicyMctsBatchResponse = {
@@ -96,6 +102,10 @@ type PolicyMctsSummaryResponse = {
   rootEdges: AiPocRandomMctsEdge[];
   principalVariation: AiPocRandomMctsMove[];
   diagnostics: AiPocRandomMctsDiagnostics;
+  elapsedMs: number;
+  stopReason: "visits" | "time" | "decided" | null;
+  ownership: number[] | null;
+  scoreLead: number | null;
 };
 type PolicyRankResponse = {
   error?: string | null;
@@ -286,6 +296,7 @@ export async function runLeafPolicyMcts(
     });
 
     const applyStartedAt = performance.now();
+    search.set_elapsed_ms(applyStartedAt - wasmStartedAt);
     const statusJson = search.apply_batch_json(JSON.stringify({ evaluations }));
     wasmSearchMs += performance.now() - applyStartedAt;
     const status = JSON.parse(statusJson) as { error?: string | null };
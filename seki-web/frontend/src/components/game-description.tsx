@@ -56,6 +56,7 @@ export function isMyTurn(
     return false;
   }
   switch (game.stage) {
+    case GameStage.HandicapPlacement:
     case GameStage.BlackToPlay:
       return game.black?.id === playerId;
     case GameStage.WhiteToPlay:
@@ -107,7 +108,10 @@ export function GameListItem({
 }
 
 function activeStone(stage: GameStage): "black" | "white" | undefined {
-  if (stage === GameStage.BlackToPlay) {
+  if (
+    stage === GameStage.HandicapPlacement ||
+    stage === GameStage.BlackToPlay
+  ) {
     return "black";
   }
 
@@ -65,7 +65,7 @@ export function GameInfo(props: GameInfoProps) {
         : `${settings.max_rating_difference_lower ?? "Unlimited"} / ${settings.max_rating_difference_higher ?? "Unlimited"}`;
 
   // Compact bar parts
-  const parts = [size];
+  const parts = [settings.topology === "torus" ? `${size} torus` : size];
 
   if (settings.ranked) {
     parts.push("Ranked");
@@ -77,6 +77,10 @@ export function GameInfo(props: GameInfoProps) {
     parts.push(`H${settings.handicap}`);
   }
 
+  if (settings.capture_target) {
+    parts.push(`Capture ${settings.capture_target}`);
+  }
+
   if (tc) {
     parts.push(tc);
   }
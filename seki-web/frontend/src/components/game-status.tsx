@@ -69,6 +69,12 @@ export function getStatusText(input: StatusInput): string | undefined {
     return "Waiting for opponent";
   }
 
+  if (stage === GameStage.HandicapPlacement) {
+    return input.isMyTurn
+      ? "Place your handicap stones"
+      : "Black is placing handicap stones";
+  }
+
   if (stage === GameStage.BlackToPlay) {
     if (input.isMyTurn) {
       return "Your turn";
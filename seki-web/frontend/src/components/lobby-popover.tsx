@@ -224,6 +224,7 @@ export function PregameSettingsPopover({
     handicap: number;
     komi: number;
     color: "black" | "white" | "random";
+    handicap_placement?: "fixed" | "free";
   }) => void;
   onAccept: () => void;
   onReject: () => void;
@@ -249,10 +250,12 @@ export function PregameSettingsPopover({
     const komi = patch.komi ?? pregame.komi;
     const handicap = patch.handicap ?? pregame.handicap;
     const color = patch.color ?? pregame.color;
+    const handicap_placement =
+      patch.handicap_placement ?? pregame.handicap_placement;
     lastSubmittedKomi.current = komi;
     lastSubmittedHandicap.current = handicap;
     lastSubmittedColor.current = color;
-    onUpdate({ handicap, komi, color });
+    onUpdate({ handicap, komi, color, handicap_placement });
   };
   const colorLabel = (value: "black" | "white" | "random") => {
     if (value === "random") {
@@ -361,6 +364,35 @@ export function PregameSettingsPopover({
               />
             )}
           </dd>
+          {pregame.handicap >= 2 && (
+            <>
+              <dt>Placement</dt>
+              <dd>
+                {disabled ? (
+                  pregame.handicap_placement === "free" ? (
+                    "Free"
+                  ) : (
+                    "Star points"
+                  )
+                ) : (
+                  <select
+                    value={pregame.handicap_placement ?? "fixed"}
+                    disabled={disabled}
+                    onChange={(e) =>
+                      submit({
+                        handicap_placement: e.currentTarget.value as
+                          | "fixed"
+                          | "free",
+                      })
+                    }
+                  >
+                    <option value="fixed">Star points</option>
+                    <option value="free">Free</option>
+                  </select>
+                )}
+              </dd>
+            </>
+          )}
           {disabled ? (
             <>
               <dt>Black</dt>
@@ -112,7 +112,10 @@ export function MiniGobanCell({ game }: MiniGobanCellProps) {
       <MiniPanel
         user={game.black}
         stone="black"
-        strong={game.stage === GameStage.BlackToPlay}
+        strong={
+          game.stage === GameStage.HandicapPlacement ||
+          game.stage === GameStage.BlackToPlay
+        }
         settings={game.settings}
         clock={game.clock}
         captures={boardState?.captures?.black}
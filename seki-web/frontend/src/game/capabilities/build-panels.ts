@@ -13,12 +13,12 @@ export function buildPlayerPanels(input: ScoreInput): {
     black: {
       captures: score ? score.black.captures : captures.black,
       komi: komi < 0 ? -komi : undefined,
-      territory: score?.black.territory,
+      territory: score && score.black.territory + (score.black.stones ?? 0),
     },
     white: {
       captures: score ? score.white.captures : captures.white,
       komi: komi > 0 ? komi : undefined,
-      territory: score?.white.territory,
+      territory: score && score.white.territory + (score.white.stones ?? 0),
     },
   };
 }
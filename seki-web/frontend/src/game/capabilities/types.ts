@@ -160,8 +160,8 @@ type ScoreInput = {
   captures: { black: number; white: number };
   score:
     | {
-        black: { territory: number; captures: number };
-        white: { territory: number; captures: number };
+        black: { territory: number; captures: number; stones?: number };
+        white: { territory: number; captures: number; stones?: number };
       }
     | undefined;
 };
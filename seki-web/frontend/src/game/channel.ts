@@ -14,6 +14,7 @@ export type GameChannel = {
     handicap: number;
     komi: number;
     color: "black" | "white" | "random";
+    handicap_placement?: "fixed" | "free";
   }): void;
   acceptPregameSettings(): void;
   rejectPregameSettings(): void;
@@ -2,6 +2,7 @@
 export enum GameStage {
   Unstarted = "unstarted",
   Challenge = "challenge",
+  HandicapPlacement = "handicap_placement",
   BlackToPlay = "black_to_play",
   WhiteToPlay = "white_to_play",
   TerritoryReview = "territory_review",
@@ -11,7 +12,11 @@ export enum GameStage {
 }
 
 export function isPlayStage(stage: GameStage): boolean {
-  return stage === GameStage.BlackToPlay || stage === GameStage.WhiteToPlay;
+  return (
+    stage === GameStage.HandicapPlacement ||
+    stage === GameStage.BlackToPlay ||
+    stage === GameStage.WhiteToPlay
+  );
 }
 
 export type Captures = {
@@ -36,16 +41,57 @@ export type TurnData = {
   pos: [number, number] | null;
 };
 
+export type SetupData = {
+  black?: [number, number][];
+  white?: [number, number][];
+  empty?: [number, number][];
+  to_play?: number;
+};
+
+export type GlyphKind =
+  | "good_for_black"
+  | "good_for_white"
+  | "even"
+  | "unclear"
+  | "hotspot"
+  | "bad_move"
+  | "tesuji"
+  | "doubtful"
+  | "interesting";
+
+// Keep in sync with go-engine Annotation (go-engine/src/annotation.rs)
+export type AnnotationData = {
+  name?: string;
+  comment?: string;
+  value?: number;
+  markup?: {
+    circles?: [number, number][];
+    triangles?: [number, number][];
+    squares?: [number, number][];
+    crosses?: [number, number][];
+    selected?: [number, number][];
+    dimmed?: [number, number][];
+    labels?: [[number, number], string][];
+    arrows?: [[number, number], [number, number]][];
+    lines?: [[number, number], [number, number]][];
+  };
+  glyphs?: { kind: GlyphKind; emphasized?: boolean }[];
+};
+
 // Keep in sync with go-engine GameTree (go-engine/src/game_tree.rs)
 export type TreeNodeData = {
   turn: TurnData;
   parent: number | null;
   children: number[];
+  setup?: SetupData;
+  annotation?: AnnotationData;
 };
 
 export type GameTreeData = {
   nodes: TreeNodeData[];
   root_children: number[];
+  setup?: SetupData;
+  annotation?: AnnotationData;
 };
 
 export type UserPreferences = {
@@ -92,11 +138,14 @@ export type UserData = {
 export type PlayerPoints = {
   territory: number;
   captures: number;
+  /** Living stones on the board; only non-zero under area scoring. */
+  stones?: number;
 };
 
 export type ScoreData = {
   black: PlayerPoints;
   white: PlayerPoints;
+  handicap_bonus?: number;
 };
 
 export type TerritoryData = {
@@ -116,6 +165,7 @@ export type PregameSettingsData = {
   opponent_approved: boolean;
   expires_at?: string;
   max_handicap: number;
+  handicap_placement?: "fixed" | "free";
 };
 
 export type SettledTerritoryData = {
@@ -128,6 +178,10 @@ export type GameSettings = {
   cols: number;
   rows: number;
   handicap: number;
+  handicap_placement?: "fixed" | "free";
+  topology?: "planar" | "torus";
+  capture_target?: number;
+  ruleset?: string;
   rating_range_mode?: "unlimited" | "absolute" | "asymmetric";
   max_rating_difference_lower?: number | null;
   max_rating_difference_higher?: number | null;
@@ -35,6 +35,10 @@ export type BoardConfig = {
   cols: number;
   rows: number;
   handicap?: number;
+  handicapPlacement?: "fixed" | "free";
+  ruleset?: string;
+  topology?: "planar" | "torus";
+  captureTarget?: number;
   showCoordinates?: boolean;
   gobanEl: HTMLDivElement;
   moveTreeEl?: HTMLElement | null;
@@ -160,6 +164,18 @@ class BoardController implements Board {
     const wasm = await ensureWasm();
     const engine = new wasm.WasmEngine(config.cols, config.rows);
 
+    if (config.ruleset) {
+      engine.set_ruleset(config.ruleset);
+    }
+    if (config.topology) {
+      engine.set_topology(config.topology);
+    }
+    if (config.captureTarget) {
+      engine.set_capture_target(config.captureTarget);
+    }
+    if (config.handicapPlacement) {
+      engine.set_handicap_placement(config.handicapPlacement);
+    }
     if (config.handicap && config.handicap >= 2) {
       engine.set_handicap(config.handicap);
     }
@@ -911,10 +927,12 @@ class BoardController implements Board {
         score: {
           black:
             (this.territoryState.score?.black.territory ?? 0) +
-            (this.territoryState.score?.black.captures ?? 0),
+            (this.territoryState.score?.black.captures ?? 0) +
+            (this.territoryState.score?.black.stones ?? 0),
           white:
             (this.territoryState.score?.white.territory ?? 0) +
-            (this.territoryState.score?.white.captures ?? 0),
+            (this.territoryState.score?.white.captures ?? 0) +
+            (this.territoryState.score?.white.stones ?? 0),
         },
       };
     }
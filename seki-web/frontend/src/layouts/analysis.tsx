@@ -201,7 +201,10 @@ export function initAnalysis(root: HTMLElement) {
 
     // Restore move_times from saved SGF text (tree already restored via storageKey)
     if (sgfText) {
-      board.engine.load_sgf_move_times(sgfText);
+      board.engine.load_sgf_game_move_times(
+        sgfText,
+        analysisMeta.value?.index ?? 0,
+      );
     }
   }
 
@@ -230,7 +233,7 @@ export function initAnalysis(root: HTMLElement) {
     const board = analysisBoard.value;
 
     if (board) {
-      board.engine.load_sgf_tree(text);
+      board.engine.load_sgf_game(text, meta.index ?? 0);
       board.engine.to_start();
       board.save();
       board.render();
@@ -259,6 +262,7 @@ export function initAnalysis(root: HTMLElement) {
       white_name: analysisMeta.value?.white_name,
       game_name: analysisMeta.value?.game_name,
       result: analysisMeta.value?.result,
+      date: analysisMeta.value?.date,
       time_limit_secs: analysisMeta.value?.time_limit_secs,
       overtime: analysisMeta.value?.overtime,
     };
@@ -1,8 +1,12 @@
 import {
   BoardSizeField,
+  CaptureGoField,
   ColorPickerField,
+  HandicapPlacementField,
   HandicapSelectField,
   KomiField,
+  RulesetField,
+  TopologyField,
   type BaseGameSettings,
   type GameSettingsSetter,
 } from "./shared";
@@ -46,13 +50,19 @@ export function BoardSettingsFields<T extends BaseGameSettings>({
         disabled={colorLocked}
       />
       <BoardSizeField s={s} set={set} locked={boardLocked} />
+      <TopologyField s={s} set={set} locked={boardLocked || s.ranked} />
       <HandicapSelectField
         s={s}
         set={set}
         value={handicapValue}
         disabled={handicapDisabled}
       />
+      {handicapValue >= 2 && (
+        <HandicapPlacementField s={s} set={set} disabled={handicapDisabled} />
+      )}
       <KomiField value={komiValue} set={set} disabled={komiDisabled} />
+      <RulesetField s={s} set={set} />
+      <CaptureGoField s={s} set={set} locked={s.ranked} />
     </>
   );
 }
@@ -79,6 +79,10 @@ export function DirectChallengeForm({
     ? (rankedUnavailableReason ?? "Register or sign in to create ranked games.")
     : (rankedUnavailableReason ??
       (s.isPrivate ? "Ranked games must be public." : undefined) ??
+      (s.topology === "torus" ? "Torus games cannot be ranked." : undefined) ??
+      (s.captureTarget != null
+        ? "Capture Go games cannot be ranked."
+        : undefined) ??
       (selectedOpponentCannotRank
         ? "Opponent is not participating in ranking."
         : undefined));
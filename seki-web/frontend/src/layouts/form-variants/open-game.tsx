@@ -116,7 +116,11 @@ export function OpenGameForm({
   const rankedBlockedReason = !isRegistered
     ? (rankedUnavailableReason ?? "Register or sign in to create ranked games.")
     : (rankedUnavailableReason ??
-      (s.isPrivate ? "Ranked games must be public." : undefined));
+      (s.isPrivate ? "Ranked games must be public." : undefined) ??
+      (s.topology === "torus" ? "Torus games cannot be ranked." : undefined) ??
+      (s.captureTarget != null
+        ? "Capture Go games cannot be ranked."
+        : undefined));
 
   return (
     <SettingsFieldset>
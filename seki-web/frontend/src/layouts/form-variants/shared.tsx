@@ -9,6 +9,7 @@ import {
   IconPlus,
   IconPrivate,
   IconSettings,
+  IconStonesBw,
   IconUndo,
   StoneBlack,
   StoneWhite,
@@ -24,6 +25,11 @@ export type BaseGameSettings = {
   color: string;
   allowUndo: boolean;
   isPrivate: boolean;
+  ruleset?: string;
+  handicapPlacement?: "fixed" | "free";
+  topology?: "planar" | "torus";
+  /** Capture Go: first to this many captures wins. Unset for regular Go. */
+  captureTarget?: number;
 };
 
 export type GameSettingsSetter<T extends BaseGameSettings> = <
@@ -185,6 +191,145 @@ export function KomiField<T extends BaseGameSettings>({
   );
 }
 
+export function RulesetField<T extends BaseGameSettings>({
+  s,
+  set,
+}: {
+  s: T;
+  set: GameSettingsSetter<T>;
+}) {
+  return (
+    <div>
+      <label for="ruleset">
+        <IconBalance /> Rules
+      </label>
+      <select
+        name="ruleset"
+        id="ruleset"
+        value={s.ruleset ?? "japanese"}
+        onChange={(e) => set("ruleset", e.currentTarget.value as T["ruleset"])}
+      >
+        <option value="japanese">Japanese</option>
+        <option value="chinese">Chinese</option>
+        <option value="aga">AGA</option>
+        <option value="new_zealand">New Zealand</option>
+      </select>
+    </div>
+  );
+}
+
+export function TopologyField<T extends BaseGameSettings>({
+  s,
+  set,
+  locked,
+}: {
+  s: T;
+  set: GameSettingsSetter<T>;
+  locked?: boolean;
+}) {
+  return (
+    <div title={locked ? "Ranked games use a regular board." : undefined}>
+      <label for="topology">
+        <IconGrid4x4 /> Edges
+      </label>
+      <select
+        name="topology"
+        id="topology"
+        value={locked ? "planar" : (s.topology ?? "planar")}
+        disabled={locked}
+        onChange={(e) =>
+          set("topology", e.currentTarget.value as T["topology"])
+        }
+      >
+        <option value="planar">Regular</option>
+        <option value="torus">Torus (wrap around)</option>
+      </select>
+      {locked && <input type="hidden" name="topology" value="planar" />}
+    </div>
+  );
+}
+
+export function CaptureGoField<T extends BaseGameSettings>({
+  s,
+  set,
+  locked,
+}: {
+  s: T;
+  set: GameSettingsSetter<T>;
+  locked?: boolean;
+}) {
+  const enabled = !locked && s.captureTarget != null;
+  return (
+    <div title={locked ? "Ranked games are won on score." : undefined}>
+      <label for="capture_go">
+        <IconStonesBw /> Capture Go
+        <input
+          type="checkbox"
+          id="capture_go"
+          checked={enabled}
+          disabled={locked}
+          onChange={(e) =>
+            set(
+              "captureTarget",
+              (e.currentTarget.checked ? 1 : undefined) as T["captureTarget"],
+            )
+          }
+        />
+      </label>
+      {enabled && (
+        <input
+          type="number"
+          name="capture_target"
+          id="capture_target"
+          min={1}
+          max={50}
+          value={s.captureTarget}
+          title="First player to capture this many stones wins"
+          onChange={(e) =>
+            set(
+              "captureTarget",
+              (parseInt(e.currentTarget.value, 10) || 1) as T["captureTarget"],
+            )
+          }
+        />
+      )}
+    </div>
+  );
+}
+
+export function HandicapPlacementField<T extends BaseGameSettings>({
+  s,
+  set,
+  disabled,
+}: {
+  s: T;
+  set: GameSettingsSetter<T>;
+  disabled?: boolean;
+}) {
+  return (
+    <div>
+      <label for="handicap_placement">
+        <IconHoshi /> Placement
+      </label>
+      <select
+        name="handicap_placement"
+        id="handicap_placement"
+        value={s.handicapPlacement ?? "fixed"}
+        disabled={disabled}
+        onChange={(e) =>
+          set(
+            "handicapPlacement",
+            e.currentTarget.value as T["handicapPlacement"],
+          )
+        }
+      >
+        <option value="fixed">Star points</option>
+        <option value="free">Free</option>
+      </select>
+    </div>
+  );
+}
+
 export function ColorPickerField<T extends BaseGameSettings>({
   s,
   set,
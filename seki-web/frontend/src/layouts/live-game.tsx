@@ -79,7 +79,7 @@ import {
   handleMoveConfirmClick,
 } from "../utils/move-confirm";
 import type { SgfMeta } from "../utils/sgf";
-import { downloadSgf } from "../utils/sgf";
+import { downloadSgf, rulesetSgfName } from "../utils/sgf";
 import { gameAnalysisKey } from "../utils/storage";
 import { joinGame, subscribe } from "../ws";
 import {
@@ -591,6 +591,7 @@ export function liveGame(
       rows: gameState.value.rows,
       komi: initialPropsSignal.value.komi,
       handicap: initialProps.settings.handicap || undefined,
+      rules: rulesetSgfName(initialProps.settings.ruleset),
       black_name: black.value?.display_name,
       white_name: white.value?.display_name,
       result: result.value ?? undefined,
@@ -730,6 +731,12 @@ export function liveGame(
     cols: gameState.value.cols,
     rows: gameState.value.rows,
     handicap: pregameSettings.value?.handicap ?? initialProps.settings.handicap,
+    handicapPlacement:
+      pregameSettings.value?.handicap_placement ??
+      initialProps.settings.handicap_placement,
+    ruleset: initialProps.settings.ruleset,
+    topology: initialProps.settings.topology,
+    captureTarget: initialProps.settings.capture_target,
     showCoordinates: showCoordinates.value,
     gobanEl: gobanRef.current!,
     ghostStone,
@@ -33,8 +33,14 @@ export function formatPoints(bTotal: number, wTotal: number, komi: number) {
 }
 
 export function formatResult(score: ScoreData, komi: number): string {
-  const bTotal = score.black.territory + score.black.captures;
-  const wTotal = score.white.territory + score.white.captures + komi;
+  const bTotal =
+    score.black.territory + score.black.captures + (score.black.stones ?? 0);
+  const wTotal =
+    score.white.territory +
+    score.white.captures +
+    (score.white.stones ?? 0) +
+    (score.handicap_bonus ?? 0) +
+    komi;
   const diff = bTotal - wTotal;
 
   if (diff > 0) {
@@ -197,6 +203,14 @@ export type DescriptionInput = {
 export function buildDescriptionParts(g: DescriptionInput): string[] {
   const parts: string[] = [formatSize(g.settings.cols, g.settings.rows)];
 
+  if (g.settings.topology === "torus") {
+    parts.push("torus");
+  }
+
+  if (g.settings.capture_target) {
+    parts.push(`Capture ${g.settings.capture_target}`);
+  }
+
   if (g.settings.handicap >= 2) {
     parts.push(`H${g.settings.handicap}`);
   }
@@ -1,5 +1,5 @@
 import { ensureWasm } from "../goban/init-wasm";
-import { readFileAsText, type SgfMeta } from "./sgf";
+import { readFileAsText, type SgfGameSummary, type SgfMeta } from "./sgf";
 
 const VALID_SIZES = [9, 13, 19];
 
@@ -7,6 +7,8 @@ export type ParsedSgf = {
   meta: SgfMeta;
   size: number;
   text: string;
+  // Every game in the file; `meta` describes the one being loaded.
+  games: SgfGameSummary[];
 };
 
 export type ParseSgfResult =
@@ -35,7 +37,10 @@ export function validateSgfMeta(
   return { ok: true, size: meta.cols };
 }
 
-export async function parseSgfFile(file: File): Promise<ParseSgfResult> {
+export async function parseSgfFile(
+  file: File,
+  index = 0,
+): Promise<ParseSgfResult> {
   let text: string;
 
   try {
@@ -45,13 +50,28 @@ export async function parseSgfFile(file: File): Promise<ParseSgfResult> {
   }
 
   const wasm = await ensureWasm();
-  const metaJson = wasm.parse_sgf(text);
-  const meta: SgfMeta = JSON.parse(metaJson);
+  const listed: SgfGameSummary[] | { error: string } = JSON.parse(
+    wasm.list_sgf_games(text),
+  );
+
+  if (!Array.isArray(listed)) {
+    return { ok: false, error: `SGF error: ${listed.error}` };
+  }
+
+  const meta = listed[index];
+
+  if (!meta) {
+    return { ok: false, error: `The SGF file has no game ${index + 1}.` };
+  }
+
   const validated = validateSgfMeta(meta);
 
   if (!validated.ok) {
     return validated;
   }
 
-  return { ok: true, value: { meta, size: validated.size, text } };
+  return {
+    ok: true,
+    value: { meta, size: validated.size, text, games: listed },
+  };
 }
@@ -3,15 +3,24 @@ export type SgfMeta = {
   rows: number;
   komi?: number;
   handicap?: number;
+  rules?: string;
   black_name?: string;
   white_name?: string;
   game_name?: string;
   result?: string;
+  date?: string;
   time_limit_secs?: number;
   overtime?: string;
+  // Position of the game in a multi-game collection.
+  index?: number;
   error?: string;
 };
 
+export type SgfGameSummary = SgfMeta & {
+  index: number;
+  move_count: number;
+};
+
 export function readFileAsText(file: File): Promise<string> {
   return new Promise((resolve, reject) => {
     const reader = new FileReader();
@@ -33,3 +42,15 @@ export function downloadSgf(content: string, filename: string) {
 
   URL.revokeObjectURL(url);
 }
+
+const SGF_RULE_NAMES: Record<string, string> = {
+  japanese: "Japanese",
+  chinese: "Chinese",
+  aga: "AGA",
+  new_zealand: "NZ",
+};
+
+/** SGF `RU[]` value for a server ruleset name. */
+export function rulesetSgfName(ruleset?: string): string | undefined {
+  return ruleset ? SGF_RULE_NAMES[ruleset] : undefined;
+}
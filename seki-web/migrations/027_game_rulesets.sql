@@ -0,0 +1,5 @@
+-- Each game records the ruleset it is played under (scoring method, ko rule,
+-- suicide, pass stones, handicap compensation). Existing games were scored
+-- with territory counting, so they stay Japanese.
+alter table games add column ruleset text not null default 'japanese'
+    check (ruleset in ('japanese', 'chinese', 'aga', 'new_zealand'));
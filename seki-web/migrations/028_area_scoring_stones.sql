@@ -0,0 +1,4 @@
+-- Area scoring counts living stones on the board instead of prisoners.
+-- Territory-scored reviews leave both columns at zero.
+alter table territory_reviews add column black_stones integer not null default 0;
+alter table territory_reviews add column white_stones integer not null default 0;
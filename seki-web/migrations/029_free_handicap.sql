@@ -0,0 +1,6 @@
+-- Handicap stones either go on the star points before the first move
+-- ('fixed') or are placed by Black as the opening moves ('free').
+alter table games add column handicap_placement text not null default 'fixed'
+    check (handicap_placement in ('fixed', 'free'));
+alter table pregame_setting_negotiations add column handicap_placement text not null default 'fixed'
+    check (handicap_placement in ('fixed', 'free'));
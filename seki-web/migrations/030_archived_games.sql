@@ -0,0 +1,21 @@
+-- Games imported from SGF files. They are read-only records of games played
+-- elsewhere, so players are free-text names rather than users, and the game
+-- itself is kept as its single-game SGF.
+create table archived_games (
+    id integer primary key autoincrement,
+    owner_id integer not null references users(id) on delete cascade,
+    cols integer not null,
+    rows integer not null,
+    komi real,
+    handicap integer not null default 0,
+    black_name text,
+    white_name text,
+    game_name text,
+    played_on text,
+    result text,
+    move_count integer not null,
+    sgf text not null,
+    created_at text not null default current_timestamp
+);
+
+create index idx_archived_games_owner_id on archived_games(owner_id);
@@ -0,0 +1,4 @@
+-- Edgeless variant: on a 'torus' board the left edge touches the right and
+-- the top touches the bottom.
+alter table games add column topology text not null default 'planar'
+    check (topology in ('planar', 'torus'));
@@ -0,0 +1,3 @@
+-- Capture Go: when set, the first player to capture this many stones wins.
+alter table games add column capture_target integer
+    check (capture_target is null or capture_target > 0);
@@ -0,0 +1,7 @@
+-- Position identity after each play or pass: the engine's 64-bit Zobrist
+-- hash (stones, side to move and ko point), stored as its signed
+-- reinterpretation. Resign turns and turns from before this column leave it
+-- null.
+alter table turns add column position_hash integer;
+
+create index idx_turns_position_hash on turns (position_hash);
@@ -0,0 +1,41 @@
+-- Life-and-death problems. Each is kept as a single-game SGF whose root
+-- setup is the problem position and whose variations are the solution
+-- tree; leaves with "RIGHT" in their comment are correct, other leaves
+-- are wrong. Problems carry a Glicko-2 difficulty rating that moves as
+-- players attempt them.
+create table problems (
+    id integer primary key autoincrement,
+    author_id integer references users(id) on delete set null,
+    title text not null,
+    cols integer not null,
+    rows integer not null,
+    to_play text not null,
+    sgf text not null,
+    rating real not null default 1500.0,
+    deviation real not null default 350.0,
+    volatility real not null default 0.06,
+    attempt_count integer not null default 0,
+    solve_count integer not null default 0,
+    created_at text not null default current_timestamp
+);
+
+-- One row per user and problem. Only the first finished attempt is rated.
+create table problem_progress (
+    user_id integer not null references users(id) on delete cascade,
+    problem_id integer not null references problems(id) on delete cascade,
+    attempts integer not null default 0,
+    solved boolean not null default 0,
+    rated boolean not null default 0,
+    updated_at text not null default current_timestamp,
+    primary key (user_id, problem_id)
+);
+
+-- Puzzle ratings are kept apart from game ratings.
+create table problem_ratings (
+    user_id integer primary key references users(id) on delete cascade,
+    rating real not null default 1500.0,
+    deviation real not null default 350.0,
+    volatility real not null default 0.06,
+    rated_attempts integer not null default 0,
+    updated_at text not null default current_timestamp
+);
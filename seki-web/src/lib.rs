@@ -36,6 +36,7 @@ pub struct AppState {
     pub presence_subs: ws::presence_subscriptions::PresenceSubscriptions,
     pub live_tx: broadcast::Sender<String>,
     pub mailer: services::mailer::Mailer,
+    pub scorer: services::scoring::Scorer,
 }
 
 fn no_store_layer() -> SetResponseHeaderLayer<HeaderValue> {
@@ -138,6 +139,7 @@ pub async fn build_router_with_registry_and_presence(
     let (live_tx, _) = broadcast::channel::<String>(256);
 
     let mailer = services::mailer::Mailer::from_env();
+    let scorer = services::scoring::Scorer::from_env();
 
     // TODO: Read from config instead of env var (keep fallback)
     let static_dir = std::env::var("STATIC_DIR")
@@ -151,6 +153,7 @@ pub async fn build_router_with_registry_and_presence(
         presence_subs: ws::presence_subscriptions::PresenceSubscriptions::new(),
         live_tx,
         mailer,
+        scorer,
     };
 
     // Deploy layout: <releases>/<id>/static/dist is what each release serves;
@@ -0,0 +1,75 @@
+use chrono::{DateTime, Utc};
+use go_engine::sgf::convert::SgfGameSummary;
+use sqlx::FromRow;
+
+/// A game imported from an SGF file. Read-only once stored.
+#[derive(Debug, Clone, FromRow)]
+pub struct ArchivedGame {
+    pub id: i64,
+    pub owner_id: i64,
+    pub cols: i32,
+    pub rows: i32,
+    pub komi: Option<f64>,
+    pub handicap: i32,
+    pub black_name: Option<String>,
+    pub white_name: Option<String>,
+    pub game_name: Option<String>,
+    pub played_on: Option<String>,
+    pub result: Option<String>,
+    pub move_count: i32,
+    pub sgf: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ArchivedGame {
+    pub async fn create(
+        executor: impl sqlx::SqliteExecutor<'_>,
+        owner_id: i64,
+        summary: &SgfGameSummary,
+        sgf: &str,
+    ) -> Result<ArchivedGame, sqlx::Error> {
+        let meta = &summary.metadata;
+        sqlx::query_as::<_, ArchivedGame>(
+            "INSERT INTO archived_games \
+             (owner_id, cols, rows, komi, handicap, black_name, white_name, game_name, played_on, result, move_count, sgf) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12) \
+             RETURNING *",
+        )
+        .bind(owner_id)
+        .bind(meta.cols as i32)
+        .bind(meta.rows as i32)
+        .bind(meta.komi)
+        .bind(meta.handicap.unwrap_or(0) as i32)
+        .bind(&meta.black_name)
+        .bind(&meta.white_name)
+        .bind(&meta.game_name)
+        .bind(&meta.date)
+        .bind(&meta.result)
+        .bind(summary.move_count as i32)
+        .bind(sgf)
+        .fetch_one(executor)
+        .await
+    }
+
+    pub async fn find_by_id(
+        executor: impl sqlx::SqliteExecutor<'_>,
+        id: i64,
+    ) -> Result<ArchivedGame, sqlx::Error> {
+        sqlx::query_as::<_, ArchivedGame>("SELECT * FROM archived_games WHERE id = $1")
+            .bind(id)
+            .fetch_one(executor)
+            .await
+    }
+
+    pub async fn list_by_owner(
+        executor: impl sqlx::SqliteExecutor<'_>,
+        owner_id: i64,
+    ) -> Result<Vec<ArchivedGame>, sqlx::Error> {
+        sqlx::query_as::<_, ArchivedGame>(
+            "SELECT * FROM archived_games WHERE owner_id = $1 ORDER BY id ASC",
+        )
+        .bind(owner_id)
+        .fetch_all(executor)
+        .await
+    }
+}
@@ -39,6 +39,10 @@ pub struct Game {
     pub rows: i32,
     pub komi: f64,
     pub handicap: i32,
+    pub ruleset: String,
+    pub handicap_placement: String,
+    pub topology: String,
+    pub capture_target: Option<i32>,
     pub is_private: bool,
     pub allow_undo: bool,
     pub started_at: Option<DateTime<Utc>>,
@@ -283,7 +287,7 @@ impl Game {
             "SELECT id FROM games \
              WHERE (black_id = $1 OR white_id = $1) \
              AND result IS NULL \
-             AND stage IN ('black_to_play', 'white_to_play', 'territory_review')",
+             AND stage IN ('handicap_placement', 'black_to_play', 'white_to_play', 'territory_review')",
         )
         .bind(user_id)
         .fetch_all(pool)
@@ -356,6 +360,10 @@ impl Game {
         rows: i32,
         komi: f64,
         handicap: i32,
+        ruleset: &str,
+        handicap_placement: &str,
+        topology: &str,
+        capture_target: Option<i32>,
         is_private: bool,
         allow_undo: bool,
         access_token: &str,
@@ -379,13 +387,13 @@ impl Game {
         rating_difference_higher_unlimited: bool,
     ) -> Result<Game, sqlx::Error> {
         sqlx::query_as::<_, Game>(
-            "INSERT INTO games (creator_id, opponent_id, black_id, white_id, cols, rows, komi, handicap, \
-             is_private, allow_undo, access_token, time_control, main_time_secs, \
+            "INSERT INTO games (creator_id, opponent_id, black_id, white_id, cols, rows, komi, handicap, ruleset, \
+             handicap_placement, topology, capture_target, is_private, allow_undo, access_token, time_control, main_time_secs, \
              increment_secs, byoyomi_time_secs, byoyomi_periods, \
              clock_black_ms, clock_white_ms, clock_black_periods, clock_white_periods, nigiri, creator_color, open_to, ranked, \
              rating_range_mode, max_rating_difference_lower, max_rating_difference_higher, \
              rating_difference_lower_unlimited, rating_difference_higher_unlimited)
-             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26, $27, $28, $29)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26, $27, $28, $29, $30, $31, $32, $33)
              RETURNING *",
         )
         .bind(creator_id)
@@ -396,6 +404,10 @@ impl Game {
         .bind(rows)
         .bind(komi)
         .bind(handicap)
+        .bind(ruleset)
+        .bind(handicap_placement)
+        .bind(topology)
+        .bind(capture_target)
         .bind(is_private)
         .bind(allow_undo)
         .bind(access_token)
@@ -501,10 +513,12 @@ impl Game {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn update_rules(
         executor: impl sqlx::SqliteExecutor<'_>,
         game_id: i64,
         handicap: i32,
+        handicap_placement: &str,
         komi: f64,
         black_id: Option<i64>,
         white_id: Option<i64>,
@@ -512,7 +526,7 @@ impl Game {
     ) -> Result<(), sqlx::Error> {
         sqlx::query(
             "UPDATE games SET handicap = $2, komi = $3, black_id = $4, white_id = $5, \
-             nigiri = false, stage = $6, cached_engine_state = NULL, updated_at = CURRENT_TIMESTAMP \
+             nigiri = false, stage = $6, handicap_placement = $7, cached_engine_state = NULL, updated_at = CURRENT_TIMESTAMP \
              WHERE id = $1",
         )
         .bind(game_id)
@@ -521,6 +535,7 @@ impl Game {
         .bind(black_id)
         .bind(white_id)
         .bind(stage)
+        .bind(handicap_placement)
         .execute(executor)
         .await?;
         Ok(())
@@ -690,9 +705,10 @@ impl Game {
     pub async fn load_settled_territory(
         executor: impl sqlx::SqliteExecutor<'_>,
         game_id: i64,
-    ) -> Result<Option<(Option<serde_json::Value>, i32, i32, i32, i32)>, sqlx::Error> {
-        sqlx::query_as::<_, (Option<serde_json::Value>, i32, i32, i32, i32)>(
-            "SELECT dead_stones, black_territory, black_captures, white_territory, white_captures \
+    ) -> Result<Option<SettledTerritoryRow>, sqlx::Error> {
+        sqlx::query_as::<_, SettledTerritoryRow>(
+            "SELECT dead_stones, black_territory, black_captures, black_stones, \
+             white_territory, white_captures, white_stones \
              FROM territory_reviews \
              WHERE game_id = $1 AND settled = TRUE \
              AND black_territory IS NOT NULL \
@@ -785,6 +801,10 @@ impl Game {
     }
 }
 
+/// Settled `territory_reviews` row: dead stones JSON, then territory, captures and
+/// stones for Black followed by the same three for White.
+pub type SettledTerritoryRow = (Option<serde_json::Value>, i32, i32, i32, i32, i32, i32);
+
 #[derive(Debug, Clone)]
 pub struct RankedGameSnapshotUpdate {
     pub ranked: bool,
@@ -1,9 +1,11 @@
 pub mod app_credential;
+pub mod archived_game;
 pub mod fcm_token;
 pub mod game;
 pub mod game_read;
 pub mod message;
 pub mod pregame_settings;
+pub mod problem;
 pub mod push_destination;
 pub mod rating;
 pub mod turn;
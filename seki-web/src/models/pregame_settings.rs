@@ -5,6 +5,7 @@ use sqlx::FromRow;
 pub struct PregameSettingsNegotiation {
     pub game_id: i64,
     pub handicap: i32,
+    pub handicap_placement: String,
     pub komi: f64,
     pub color: String,
     pub creator_approved: bool,
@@ -17,21 +18,24 @@ impl PregameSettingsNegotiation {
         executor: impl sqlx::SqliteExecutor<'_>,
         game_id: i64,
         handicap: i32,
+        handicap_placement: &str,
         komi: f64,
         color: &str,
     ) -> Result<Self, sqlx::Error> {
         sqlx::query_as::<_, Self>(
-            "INSERT INTO pregame_setting_negotiations (game_id, handicap, komi, color) \
-             VALUES ($1, $2, $3, $4) \
+            "INSERT INTO pregame_setting_negotiations (game_id, handicap, komi, color, handicap_placement) \
+             VALUES ($1, $2, $3, $4, $5) \
              ON CONFLICT(game_id) DO UPDATE SET \
              handicap = excluded.handicap, komi = excluded.komi, color = excluded.color, \
+             handicap_placement = excluded.handicap_placement, \
              creator_approved = false, opponent_approved = false, expires_at = NULL, updated_at = CURRENT_TIMESTAMP \
-             RETURNING game_id, handicap, komi, color, creator_approved, opponent_approved, expires_at",
+             RETURNING game_id, handicap, handicap_placement, komi, color, creator_approved, opponent_approved, expires_at",
         )
         .bind(game_id)
         .bind(handicap)
         .bind(komi)
         .bind(color)
+        .bind(handicap_placement)
         .fetch_one(executor)
         .await
     }
@@ -40,20 +44,22 @@ impl PregameSettingsNegotiation {
         executor: impl sqlx::SqliteExecutor<'_>,
         game_id: i64,
         handicap: i32,
+        handicap_placement: &str,
         komi: f64,
         color: &str,
     ) -> Result<Self, sqlx::Error> {
         sqlx::query_as::<_, Self>(
             "UPDATE pregame_setting_negotiations SET \
-             handicap = $2, komi = $3, color = $4, \
+             handicap = $2, komi = $3, color = $4, handicap_placement = $5, \
              creator_approved = false, opponent_approved = false, expires_at = NULL, updated_at = CURRENT_TIMESTAMP \
              WHERE game_id = $1 \
-             RETURNING game_id, handicap, komi, color, creator_approved, opponent_approved, expires_at",
+             RETURNING game_id, handicap, handicap_placement, komi, color, creator_approved, opponent_approved, expires_at",
         )
         .bind(game_id)
         .bind(handicap)
         .bind(komi)
         .bind(color)
+        .bind(handicap_placement)
         .fetch_one(executor)
         .await
     }
@@ -63,7 +69,7 @@ impl PregameSettingsNegotiation {
         game_id: i64,
     ) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as::<_, Self>(
-            "SELECT game_id, handicap, komi, color, creator_approved, opponent_approved, expires_at \
+            "SELECT game_id, handicap, handicap_placement, komi, color, creator_approved, opponent_approved, expires_at \
              FROM pregame_setting_negotiations WHERE game_id = $1",
         )
         .bind(game_id)
@@ -82,7 +88,7 @@ impl PregameSettingsNegotiation {
             "UPDATE pregame_setting_negotiations SET \
              creator_approved = $2, opponent_approved = $3, expires_at = $4, updated_at = CURRENT_TIMESTAMP \
              WHERE game_id = $1 \
-             RETURNING game_id, handicap, komi, color, creator_approved, opponent_approved, expires_at",
+             RETURNING game_id, handicap, handicap_placement, komi, color, creator_approved, opponent_approved, expires_at",
         )
         .bind(game_id)
         .bind(creator_approved)
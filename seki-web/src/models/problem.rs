@@ -0,0 +1,226 @@
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+/// A life-and-death problem stored as SGF with its solution tree.
+#[derive(Debug, Clone, FromRow)]
+pub struct Problem {
+    pub id: i64,
+    pub author_id: Option<i64>,
+    pub title: String,
+    pub cols: i32,
+    pub rows: i32,
+    pub to_play: String,
+    pub sgf: String,
+    pub rating: f64,
+    pub deviation: f64,
+    pub volatility: f64,
+    pub attempt_count: i32,
+    pub solve_count: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct ProblemProgress {
+    pub user_id: i64,
+    pub problem_id: i64,
+    pub attempts: i32,
+    pub solved: bool,
+    pub rated: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A user's puzzle rating, kept apart from their game rating.
+#[derive(Debug, Clone, FromRow)]
+pub struct ProblemRating {
+    pub user_id: i64,
+    pub rating: f64,
+    pub deviation: f64,
+    pub volatility: f64,
+    pub rated_attempts: i32,
+    pub updated_at: DateTime<Utc>,
+}
+
+pub struct NewProblem<'a> {
+    pub author_id: i64,
+    pub title: &'a str,
+    pub cols: i32,
+    pub rows: i32,
+    pub to_play: &'a str,
+    pub sgf: &'a str,
+}
+
+impl Problem {
+    pub async fn create(
+        executor: impl sqlx::SqliteExecutor<'_>,
+        new: &NewProblem<'_>,
+    ) -> Result<Problem, sqlx::Error> {
+        sqlx::query_as::<_, Problem>(
+            "INSERT INTO problems (author_id, title, cols, rows, to_play, sgf) \
+             VALUES ($1, $2, $3, $4, $5, $6) \
+             RETURNING *",
+        )
+        .bind(new.author_id)
+        .bind(new.title)
+        .bind(new.cols)
+        .bind(new.rows)
+        .bind(new.to_play)
+        .bind(new.sgf)
+        .fetch_one(executor)
+        .await
+    }
+
+    pub async fn find_by_id(
+        executor: impl sqlx::SqliteExecutor<'_>,
+        id: i64,
+    ) -> Result<Problem, sqlx::Error> {
+        sqlx::query_as::<_, Problem>("SELECT * FROM problems WHERE id = $1")
+            .bind(id)
+            .fetch_one(executor)
+            .await
+    }
+
+    /// Every problem, easiest first.
+    pub async fn list(
+        executor: impl sqlx::SqliteExecutor<'_>,
+    ) -> Result<Vec<Problem>, sqlx::Error> {
+        sqlx::query_as::<_, Problem>("SELECT * FROM problems ORDER BY rating ASC, id ASC")
+            .fetch_all(executor)
+            .await
+    }
+
+    pub async fn record_attempt(
+        executor: impl sqlx::SqliteExecutor<'_>,
+        id: i64,
+        solved: bool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE problems SET attempt_count = attempt_count + 1, \
+             solve_count = solve_count + $2 WHERE id = $1",
+        )
+        .bind(id)
+        .bind(i32::from(solved))
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn update_rating(
+        executor: impl sqlx::SqliteExecutor<'_>,
+        id: i64,
+        rating: f64,
+        deviation: f64,
+        volatility: f64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE problems SET rating = $2, deviation = $3, volatility = $4 WHERE id = $1",
+        )
+        .bind(id)
+        .bind(rating)
+        .bind(deviation)
+        .bind(volatility)
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+}
+
+impl ProblemProgress {
+    pub async fn find(
+        executor: impl sqlx::SqliteExecutor<'_>,
+        user_id: i64,
+        problem_id: i64,
+    ) -> Result<Option<ProblemProgress>, sqlx::Error> {
+        sqlx::query_as::<_, ProblemProgress>(
+            "SELECT * FROM problem_progress WHERE user_id = $1 AND problem_id = $2",
+        )
+        .bind(user_id)
+        .bind(problem_id)
+        .fetch_optional(executor)
+        .await
+    }
+
+    pub async fn list_by_user(
+        executor: impl sqlx::SqliteExecutor<'_>,
+        user_id: i64,
+    ) -> Result<Vec<ProblemProgress>, sqlx::Error> {
+        sqlx::query_as::<_, ProblemProgress>("SELECT * FROM problem_progress WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_all(executor)
+            .await
+    }
+
+    pub async fn record_attempt(
+        executor: impl sqlx::SqliteExecutor<'_>,
+        user_id: i64,
+        problem_id: i64,
+        solved: bool,
+    ) -> Result<ProblemProgress, sqlx::Error> {
+        sqlx::query_as::<_, ProblemProgress>(
+            "INSERT INTO problem_progress (user_id, problem_id, attempts, solved) \
+             VALUES ($1, $2, 1, $3) \
+             ON CONFLICT (user_id, problem_id) DO UPDATE SET \
+             attempts = attempts + 1, solved = solved OR excluded.solved, \
+             updated_at = CURRENT_TIMESTAMP \
+             RETURNING *",
+        )
+        .bind(user_id)
+        .bind(problem_id)
+        .bind(solved)
+        .fetch_one(executor)
+        .await
+    }
+
+    /// Mark the pair rated. Returns false if it already was, so the rating
+    /// is applied once however many attempts race.
+    pub async fn set_rated(
+        executor: impl sqlx::SqliteExecutor<'_>,
+        user_id: i64,
+        problem_id: i64,
+    ) -> Result<bool, sqlx::Error> {
+        let res = sqlx::query(
+            "UPDATE problem_progress SET rated = 1 \
+             WHERE user_id = $1 AND problem_id = $2 AND rated = 0",
+        )
+        .bind(user_id)
+        .bind(problem_id)
+        .execute(executor)
+        .await?;
+        Ok(res.rows_affected() > 0)
+    }
+}
+
+impl ProblemRating {
+    pub async fn find(
+        executor: impl sqlx::SqliteExecutor<'_>,
+        user_id: i64,
+    ) -> Result<Option<ProblemRating>, sqlx::Error> {
+        sqlx::query_as::<_, ProblemRating>("SELECT * FROM problem_ratings WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_optional(executor)
+            .await
+    }
+
+    pub async fn upsert(
+        executor: impl sqlx::SqliteExecutor<'_>,
+        user_id: i64,
+        rating: f64,
+        deviation: f64,
+        volatility: f64,
+    ) -> Result<ProblemRating, sqlx::Error> {
+        sqlx::query_as::<_, ProblemRating>(
+            "INSERT INTO problem_ratings (user_id, rating, deviation, volatility, rated_attempts) \
+             VALUES ($1, $2, $3, $4, 1) \
+             ON CONFLICT (user_id) DO UPDATE SET \
+             rating = excluded.rating, deviation = excluded.deviation, \
+             volatility = excluded.volatility, rated_attempts = rated_attempts + 1, \
+             updated_at = CURRENT_TIMESTAMP \
+             RETURNING *",
+        )
+        .bind(user_id)
+        .bind(rating)
+        .bind(deviation)
+        .bind(volatility)
+        .fetch_one(executor)
+        .await
+    }
+}
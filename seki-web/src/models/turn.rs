@@ -27,6 +27,8 @@ pub struct TurnRow {
     pub clock_white_ms: Option<i64>,
     pub clock_black_periods: Option<i32>,
     pub clock_white_periods: Option<i32>,
+    /// `Engine::position_hash` after the turn, as a signed integer.
+    pub position_hash: Option<i64>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -54,12 +56,14 @@ impl TurnRow {
         stone: i32,
         col: Option<i32>,
         row: Option<i32>,
+        position_hash: Option<u64>,
         clock: Option<&ClockSnapshot>,
     ) -> Result<TurnRow, sqlx::Error> {
         sqlx::query_as::<_, TurnRow>(
             "INSERT INTO turns (game_id, user_id, turn_number, kind, stone, col, row, \
-             clock_black_ms, clock_white_ms, clock_black_periods, clock_white_periods) \
-             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11) \
+             clock_black_ms, clock_white_ms, clock_black_periods, clock_white_periods, \
+             position_hash) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12) \
              RETURNING *",
         )
         .bind(game_id)
@@ -73,10 +77,33 @@ impl TurnRow {
         .bind(clock.map(|c| c.white_ms))
         .bind(clock.map(|c| c.black_periods))
         .bind(clock.map(|c| c.white_periods))
+        .bind(position_hash.map(|h| h as i64))
         .fetch_one(executor)
         .await
     }
 
+    /// Turns that led to the position `position_hash`, in games `viewer` may
+    /// see: public games plus any they created or play in. Newest games first.
+    pub async fn find_by_position_hash(
+        executor: impl sqlx::SqliteExecutor<'_>,
+        position_hash: u64,
+        viewer: Option<i64>,
+        limit: i64,
+    ) -> Result<Vec<TurnRow>, sqlx::Error> {
+        sqlx::query_as::<_, TurnRow>(
+            "SELECT t.* FROM turns t JOIN games g ON g.id = t.game_id \
+             WHERE t.position_hash = $1 \
+             AND (g.is_private = 0 OR $2 IN (g.creator_id, g.black_id, g.white_id)) \
+             ORDER BY t.game_id DESC, t.turn_number ASC \
+             LIMIT $3",
+        )
+        .bind(position_hash as i64)
+        .bind(viewer)
+        .bind(limit)
+        .fetch_all(executor)
+        .await
+    }
+
     pub async fn delete_last(
         executor: impl sqlx::SqliteExecutor<'_>,
         game_id: i64,
@@ -0,0 +1,140 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::AppState;
+use crate::error::{ApiError, ApiErrorResponse, AppError};
+use crate::models::archived_game::ArchivedGame;
+use crate::services::game_archiver;
+use crate::session::ApiUser;
+
+#[derive(Serialize, ToSchema)]
+pub(crate) struct ArchivedGameResponse {
+    id: i64,
+    cols: i32,
+    rows: i32,
+    komi: Option<f64>,
+    handicap: i32,
+    black_name: Option<String>,
+    white_name: Option<String>,
+    game_name: Option<String>,
+    /// Raw SGF `DT[]` value.
+    date: Option<String>,
+    result: Option<String>,
+    move_count: i32,
+    created_at: DateTime<Utc>,
+    /// The game as a single-game SGF; only included when fetching one game.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sgf: Option<String>,
+}
+
+impl ArchivedGameResponse {
+    fn from_game(g: ArchivedGame, with_sgf: bool) -> Self {
+        Self {
+            id: g.id,
+            cols: g.cols,
+            rows: g.rows,
+            komi: g.komi,
+            handicap: g.handicap,
+            black_name: g.black_name,
+            white_name: g.white_name,
+            game_name: g.game_name,
+            date: g.played_on,
+            result: g.result,
+            move_count: g.move_count,
+            created_at: g.created_at,
+            sgf: with_sgf.then_some(g.sgf),
+        }
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub(crate) struct ImportSgfRequest {
+    /// SGF collection text; may hold any number of games.
+    sgf: String,
+    /// Zero-based positions of the games to import. Imports every game when omitted.
+    #[serde(default)]
+    games: Option<Vec<usize>>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/archive",
+    tag = "Archive",
+    security(("bearer" = [])),
+    request_body = ImportSgfRequest,
+    responses(
+        (status = 201, description = "Imported games", body = Vec<ArchivedGameResponse>),
+        (status = 401, description = "Unauthorized", body = ApiErrorResponse),
+        (status = 422, description = "Invalid SGF or game selection", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse)
+    )
+)]
+pub(super) async fn import_sgf(
+    State(state): State<AppState>,
+    api_user: ApiUser,
+    Json(body): Json<ImportSgfRequest>,
+) -> Result<(axum::http::StatusCode, Json<Vec<ArchivedGameResponse>>), ApiError> {
+    let games =
+        game_archiver::import_collection(&state.db, api_user.id, &body.sgf, body.games.as_deref())
+            .await?;
+
+    Ok((
+        axum::http::StatusCode::CREATED,
+        Json(
+            games
+                .into_iter()
+                .map(|g| ArchivedGameResponse::from_game(g, false))
+                .collect(),
+        ),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/archive",
+    tag = "Archive",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Archived games of the current user", body = Vec<ArchivedGameResponse>),
+        (status = 401, description = "Unauthorized", body = ApiErrorResponse)
+    )
+)]
+pub(super) async fn list_archived_games(
+    State(state): State<AppState>,
+    api_user: ApiUser,
+) -> Result<Json<Vec<ArchivedGameResponse>>, ApiError> {
+    let games = ArchivedGame::list_by_owner(&state.db, api_user.id).await?;
+    Ok(Json(
+        games
+            .into_iter()
+            .map(|g| ArchivedGameResponse::from_game(g, false))
+            .collect(),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/archive/{id}",
+    tag = "Archive",
+    security(("bearer" = [])),
+    params(("id" = i64, Path, description = "Archived game ID")),
+    responses(
+        (status = 200, description = "Archived game with its SGF", body = ArchivedGameResponse),
+        (status = 401, description = "Unauthorized", body = ApiErrorResponse),
+        (status = 404, description = "Archived game not found", body = ApiErrorResponse)
+    )
+)]
+pub(super) async fn get_archived_game(
+    State(state): State<AppState>,
+    api_user: ApiUser,
+    Path(id): Path<i64>,
+) -> Result<Json<ArchivedGameResponse>, ApiError> {
+    let game = ArchivedGame::find_by_id(&state.db, id).await?;
+    if game.owner_id != api_user.id {
+        return Err(AppError::NotFound("Archived game not found".to_string()).into());
+    }
+    Ok(Json(ArchivedGameResponse::from_game(game, true)))
+}
@@ -25,6 +25,12 @@ pub(crate) struct GameResponse {
     pub(crate) rows: i32,
     pub(crate) komi: f64,
     pub(crate) handicap: i32,
+    pub(crate) ruleset: String,
+    pub(crate) handicap_placement: String,
+    pub(crate) topology: String,
+    /// Capture Go: the first player to capture this many stones wins.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) capture_target: Option<i32>,
     /// Hidden from non-participants unless they have the access token.
     pub(crate) is_private: bool,
     /// For email invites: single-use login link for the opponent.
@@ -96,6 +102,20 @@ pub(crate) struct CreateGameRequest {
     /// Create an open game with creator-chosen handicap/komi/color instead of deriving them at join.
     #[serde(default)]
     custom_settings: bool,
+    /// Rules preset: `japanese` (default), `chinese`, `aga` or `new_zealand`.
+    #[serde(default)]
+    ruleset: Option<String>,
+    /// Handicap placement: `fixed` star points (default) or `free`, placed by Black.
+    #[serde(default)]
+    handicap_placement: Option<String>,
+    /// Board topology: `planar` (default) or `torus`, whose edges wrap around.
+    /// Torus games cannot be ranked.
+    #[serde(default)]
+    topology: Option<String>,
+    /// Capture Go: the first player to capture this many stones wins
+    /// (`B+C`/`W+C`). Capture Go games cannot be ranked.
+    #[serde(default)]
+    capture_target: Option<i32>,
 }
 
 #[derive(Deserialize, ToSchema)]
@@ -180,6 +200,10 @@ pub(super) async fn create_game(
             .ok_or_else(|| AppError::UnprocessableEntity("Missing color".to_string()))?
     };
     let creator_color = custom_settings.then(|| color.clone());
+    let ruleset = game_creator::parse_ruleset(body.ruleset.as_deref())?;
+    let handicap_placement =
+        game_creator::parse_handicap_placement(body.handicap_placement.as_deref())?;
+    let topology = game_creator::parse_topology(body.topology.as_deref())?;
 
     let params = game_creator::CreateGameParams {
         cols: body.cols,
@@ -211,6 +235,10 @@ pub(super) async fn create_game(
         rating_range,
         open_game: is_open,
         creator_color,
+        ruleset,
+        handicap_placement,
+        topology,
+        capture_target: body.capture_target,
     };
 
     let (game, challenge_token) = game_creator::create_game(&state, &api_user, params).await?;
@@ -1,7 +1,9 @@
+mod archive;
 mod challenges;
 mod game_actions;
 mod games;
 mod messages;
+mod problems;
 mod turns;
 mod users;
 
@@ -18,13 +20,15 @@ use crate::routes::auth;
 use crate::routes::fcm;
 use crate::routes::push;
 
+use self::archive::{get_archived_game, import_sgf, list_archived_games};
 use self::challenges::{accept_challenge, decline_challenge, rematch_game};
 use self::game_actions::{
     abort, approve_territory, pass, play_move, request_undo, resign, respond_to_undo, toggle_chain,
 };
 use self::games::{create_game, delete_game, get_game, join_game, list_games};
 use self::messages::{get_messages, send_message};
-use self::turns::get_turns;
+use self::problems::{create_problem, get_problem, list_problems, submit_attempt};
+use self::turns::{get_position_turns, get_turns};
 use self::users::{get_me, get_user, get_user_games};
 
 struct ApiModifier;
@@ -68,12 +72,19 @@ impl Modify for ApiModifier {
         game_actions::toggle_chain, game_actions::approve_territory,
         challenges::accept_challenge, challenges::decline_challenge, challenges::rematch_game,
         messages::get_messages, messages::send_message, turns::get_turns,
-        users::get_user, users::get_user_games, users::get_me
+        turns::get_position_turns,
+        users::get_user, users::get_user_games, users::get_me,
+        archive::import_sgf, archive::list_archived_games, archive::get_archived_game,
+        problems::create_problem, problems::list_problems, problems::get_problem,
+        problems::submit_attempt
     ),
     components(schemas(
-        users::UserResponse, games::GameResponse, turns::TurnResponse, messages::MessageResponse,
+        users::UserResponse, games::GameResponse, turns::TurnResponse, turns::PositionMatchResponse, messages::MessageResponse,
         games::CreateGameRequest, game_actions::PlayRequest, game_actions::UndoResponseRequest, game_actions::ToggleChainRequest,
         messages::ChatRequest, challenges::RematchRequest, games::JoinGameRequest,
+        archive::ArchivedGameResponse, archive::ImportSgfRequest,
+        problems::ProblemResponse, problems::CreateProblemRequest, problems::AttemptRequest,
+        problems::AttemptResponse, crate::services::problems::AttemptStatus,
         crate::services::live::LiveGameItem,
         crate::services::live::GameSettings,
         crate::models::game::TimeControlType,
@@ -88,7 +99,9 @@ impl Modify for ApiModifier {
         (name = "Messages", description = "In-game chat"),
         (name = "Turns", description = "Move history"),
         (name = "Users", description = "User profiles and game history"),
-        (name = "Auth", description = "Current user info")
+        (name = "Auth", description = "Current user info"),
+        (name = "Archive", description = "Read-only games imported from SGF"),
+        (name = "Problems", description = "Life-and-death problems, attempts and progress")
     )
 )]
 pub struct ApiDoc;
@@ -170,9 +183,17 @@ pub fn router() -> Router<AppState> {
         .route("/games/{id}/messages", get(get_messages).post(send_message))
         // Turns
         .route("/games/{id}/turns", get(get_turns))
+        .route("/positions/{hash}", get(get_position_turns))
         // Users
         .route("/users/{username}", get(get_user))
         .route("/users/{username}/games", get(get_user_games))
         // Auth
         .route("/me", get(get_me))
+        // Archive
+        .route("/archive", get(list_archived_games).post(import_sgf))
+        .route("/archive/{id}", get(get_archived_game))
+        // Problems
+        .route("/problems", get(list_problems).post(create_problem))
+        .route("/problems/{id}", get(get_problem))
+        .route("/problems/{id}/attempts", post(submit_attempt))
 }
@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+
+use axum::Json;
+use axum::extract::{Path, State};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::AppState;
+use crate::error::{ApiError, ApiErrorResponse};
+use crate::models::problem::{Problem, ProblemProgress};
+use crate::services::problems::{self, AttemptStatus};
+use crate::session::ApiUser;
+
+#[derive(Serialize, ToSchema)]
+pub(crate) struct ProblemResponse {
+    id: i64,
+    title: String,
+    cols: i32,
+    rows: i32,
+    /// `black` or `white`.
+    to_play: String,
+    rating: f64,
+    attempt_count: i32,
+    solve_count: i32,
+    created_at: DateTime<Utc>,
+    /// The current user's finished attempts.
+    attempts: i32,
+    /// Whether the current user has solved the problem.
+    solved: bool,
+    /// Setup stones as `[col, row]`; only included when fetching one problem.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Vec<Vec<u8>>>)]
+    black: Option<Vec<(u8, u8)>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Vec<Vec<u8>>>)]
+    white: Option<Vec<(u8, u8)>>,
+    /// The problem with its solution tree; only included for its author or
+    /// once the current user has solved it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sgf: Option<String>,
+}
+
+impl ProblemResponse {
+    fn from_problem(p: Problem, progress: Option<&ProblemProgress>) -> Self {
+        Self {
+            id: p.id,
+            title: p.title,
+            cols: p.cols,
+            rows: p.rows,
+            to_play: p.to_play,
+            rating: p.rating,
+            attempt_count: p.attempt_count,
+            solve_count: p.solve_count,
+            created_at: p.created_at,
+            attempts: progress.map_or(0, |pp| pp.attempts),
+            solved: progress.is_some_and(|pp| pp.solved),
+            black: None,
+            white: None,
+            sgf: None,
+        }
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub(crate) struct CreateProblemRequest {
+    /// Single-game SGF: root setup stones and the solution tree. Leaves whose
+    /// comment contains `RIGHT` are correct; all other leaves are wrong.
+    sgf: String,
+    /// Defaults to the SGF's `GN[]`.
+    #[serde(default)]
+    title: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub(crate) struct AttemptRequest {
+    /// The player's moves so far as `[col, row]`, without the replies.
+    #[schema(value_type = Vec<Vec<u8>>)]
+    moves: Vec<(u8, u8)>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub(crate) struct AttemptResponse {
+    status: AttemptStatus,
+    /// The opponent's reply to the last move as `[col, row]`.
+    #[schema(value_type = Option<Vec<u8>>)]
+    response: Option<(u8, u8)>,
+    comment: Option<String>,
+    /// Whether this attempt changed the ratings.
+    rated: bool,
+    problem_rating: f64,
+    /// The current user's puzzle rating, once they have one.
+    user_rating: Option<f64>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/problems",
+    tag = "Problems",
+    security(("bearer" = [])),
+    request_body = CreateProblemRequest,
+    responses(
+        (status = 201, description = "Created problem", body = ProblemResponse),
+        (status = 401, description = "Unauthorized", body = ApiErrorResponse),
+        (status = 422, description = "Invalid problem SGF or title", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse)
+    )
+)]
+pub(super) async fn create_problem(
+    State(state): State<AppState>,
+    api_user: ApiUser,
+    Json(body): Json<CreateProblemRequest>,
+) -> Result<(axum::http::StatusCode, Json<ProblemResponse>), ApiError> {
+    let problem =
+        problems::create_problem(&state.db, api_user.id, body.title.as_deref(), &body.sgf).await?;
+    Ok((
+        axum::http::StatusCode::CREATED,
+        Json(ProblemResponse::from_problem(problem, None)),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/problems",
+    tag = "Problems",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "All problems, easiest first, with the current user's progress", body = Vec<ProblemResponse>),
+        (status = 401, description = "Unauthorized", body = ApiErrorResponse)
+    )
+)]
+pub(super) async fn list_problems(
+    State(state): State<AppState>,
+    api_user: ApiUser,
+) -> Result<Json<Vec<ProblemResponse>>, ApiError> {
+    let (list, progress) = tokio::try_join!(
+        Problem::list(&state.db),
+        ProblemProgress::list_by_user(&state.db, api_user.id),
+    )?;
+    let progress: HashMap<i64, ProblemProgress> =
+        progress.into_iter().map(|pp| (pp.problem_id, pp)).collect();
+    Ok(Json(
+        list.into_iter()
+            .map(|p| {
+                let pp = progress.get(&p.id);
+                ProblemResponse::from_problem(p, pp)
+            })
+            .collect(),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/problems/{id}",
+    tag = "Problems",
+    security(("bearer" = [])),
+    params(("id" = i64, Path, description = "Problem ID")),
+    responses(
+        (status = 200, description = "Problem with its setup stones", body = ProblemResponse),
+        (status = 401, description = "Unauthorized", body = ApiErrorResponse),
+        (status = 404, description = "Problem not found", body = ApiErrorResponse)
+    )
+)]
+pub(super) async fn get_problem(
+    State(state): State<AppState>,
+    api_user: ApiUser,
+    Path(id): Path<i64>,
+) -> Result<Json<ProblemResponse>, ApiError> {
+    let problem = Problem::find_by_id(&state.db, id).await?;
+    let progress = ProblemProgress::find(&state.db, api_user.id, id).await?;
+    let parsed = problems::parse_problem(&problem.sgf)?;
+    let reveal =
+        problem.author_id == Some(api_user.id) || progress.as_ref().is_some_and(|pp| pp.solved);
+    let sgf = reveal.then(|| problem.sgf.clone());
+
+    let mut response = ProblemResponse::from_problem(problem, progress.as_ref());
+    response.black = Some(parsed.setup().black.clone());
+    response.white = Some(parsed.setup().white.clone());
+    response.sgf = sgf;
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    post,
+    path = "/problems/{id}/attempts",
+    tag = "Problems",
+    security(("bearer" = [])),
+    params(("id" = i64, Path, description = "Problem ID")),
+    request_body = AttemptRequest,
+    responses(
+        (status = 200, description = "Where the moves lead in the solution tree", body = AttemptResponse),
+        (status = 401, description = "Unauthorized", body = ApiErrorResponse),
+        (status = 404, description = "Problem not found", body = ApiErrorResponse),
+        (status = 422, description = "Moves continue past a finished line", body = ApiErrorResponse)
+    )
+)]
+pub(super) async fn submit_attempt(
+    State(state): State<AppState>,
+    api_user: ApiUser,
+    Path(id): Path<i64>,
+    Json(body): Json<AttemptRequest>,
+) -> Result<Json<AttemptResponse>, ApiError> {
+    let problem = Problem::find_by_id(&state.db, id).await?;
+    let outcome = problems::submit_attempt(&state.db, api_user.id, &problem, &body.moves).await?;
+    Ok(Json(AttemptResponse {
+        status: outcome.check.status,
+        response: outcome.check.response,
+        comment: outcome.check.comment,
+        rated: outcome.rated,
+        problem_rating: outcome.problem_rating,
+        user_rating: outcome.user_rating,
+    }))
+}
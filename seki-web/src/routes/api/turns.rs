@@ -21,9 +21,35 @@ pub(crate) struct TurnResponse {
     col: Option<i32>,
     row: Option<i32>,
     user_id: i64,
+    /// Position reached by this turn, as 16 hex digits. See `GET /positions/{hash}`.
+    position_hash: Option<String>,
     created_at: DateTime<Utc>,
 }
 
+impl From<TurnRow> for TurnResponse {
+    fn from(t: TurnRow) -> Self {
+        TurnResponse {
+            id: t.id,
+            turn_number: t.turn_number,
+            kind: t.kind,
+            stone: t.stone,
+            col: t.col,
+            row: t.row,
+            user_id: t.user_id,
+            position_hash: t.position_hash.map(|h| format!("{:016x}", h as u64)),
+            created_at: t.created_at,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub(crate) struct PositionMatchResponse {
+    game_id: i64,
+    turn: TurnResponse,
+}
+
+const MAX_POSITION_MATCHES: i64 = 50;
+
 #[utoipa::path(
     get,
     path = "/games/{id}/turns",
@@ -53,17 +79,46 @@ pub(super) async fn get_turns(
 
     let turns = TurnRow::find_by_game_id(&state.db, id).await?;
 
-    let items: Vec<TurnResponse> = turns
+    let items: Vec<TurnResponse> = turns.into_iter().map(TurnResponse::from).collect();
+
+    Ok(Json(items))
+}
+
+#[utoipa::path(
+    get,
+    path = "/positions/{hash}",
+    tag = "Turns",
+    params(("hash" = String, Path, description = "Position hash from a turn, as 16 hex digits")),
+    responses(
+        (status = 200, description = "Turns in visible games that reached this position", body = Vec<PositionMatchResponse>),
+        (status = 422, description = "Malformed position hash")
+    )
+)]
+pub(super) async fn get_position_turns(
+    State(state): State<AppState>,
+    OptionalApiUser(api_user): OptionalApiUser,
+    Path(hash): Path<String>,
+) -> Result<Json<Vec<PositionMatchResponse>>, ApiError> {
+    let position_hash = u64::from_str_radix(&hash, 16)
+        .ok()
+        .filter(|_| hash.len() == 16)
+        .ok_or_else(|| {
+            AppError::UnprocessableEntity("Position hash must be 16 hex digits".to_string())
+        })?;
+
+    let turns = TurnRow::find_by_position_hash(
+        &state.db,
+        position_hash,
+        api_user.as_ref().map(|u| u.id),
+        MAX_POSITION_MATCHES,
+    )
+    .await?;
+
+    let items = turns
         .into_iter()
-        .map(|t| TurnResponse {
-            id: t.id,
-            turn_number: t.turn_number,
-            kind: t.kind,
-            stone: t.stone,
-            col: t.col,
-            row: t.row,
-            user_id: t.user_id,
-            created_at: t.created_at,
+        .map(|t| PositionMatchResponse {
+            game_id: t.game_id,
+            turn: TurnResponse::from(t),
         })
         .collect();
 
@@ -108,6 +108,10 @@ pub(crate) async fn build_game_response(
             rows: gwp.game.rows,
             komi: gwp.game.komi,
             handicap: gwp.game.handicap,
+            ruleset: gwp.game.ruleset.clone(),
+            handicap_placement: gwp.game.handicap_placement.clone(),
+            topology: gwp.game.topology.clone(),
+            capture_target: gwp.game.capture_target,
             is_private: gwp.game.is_private,
             invite_link: None,
             allow_undo: gwp.game.allow_undo,
@@ -137,6 +141,10 @@ pub(crate) async fn build_game_response(
         rows: gwp.game.rows,
         komi: gwp.game.komi,
         handicap: gwp.game.handicap,
+        ruleset: gwp.game.ruleset.clone(),
+        handicap_placement: gwp.game.handicap_placement.clone(),
+        topology: gwp.game.topology.clone(),
+        capture_target: gwp.game.capture_target,
         is_private: gwp.game.is_private,
         invite_link: None,
         allow_undo: gwp.game.allow_undo,
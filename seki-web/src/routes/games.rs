@@ -39,6 +39,10 @@ pub struct CreateGameForm {
     pub variant: Option<String>,
     pub custom_settings: Option<String>,
     pub invite_message: Option<String>,
+    pub ruleset: Option<String>,
+    pub handicap_placement: Option<String>,
+    pub topology: Option<String>,
+    pub capture_target: Option<i32>,
 }
 
 // POST /games
@@ -151,6 +155,10 @@ pub async fn create_game(
             };
 
         let invite_email = form.invite_email.clone();
+        let ruleset = game_creator::parse_ruleset(form.ruleset.as_deref())?;
+        let handicap_placement =
+            game_creator::parse_handicap_placement(form.handicap_placement.as_deref())?;
+        let topology = game_creator::parse_topology(form.topology.as_deref())?;
 
         let params = CreateGameParams {
             cols,
@@ -172,6 +180,10 @@ pub async fn create_game(
             rating_range,
             open_game: is_open,
             creator_color,
+            ruleset,
+            handicap_placement,
+            topology,
+            capture_target: form.capture_target,
         };
 
         match game_creator::create_game(&state, &current_user, params).await {
@@ -185,7 +185,7 @@ impl TimeControl {
 /// Returns `Some(stone)` if a user's clock should be ticking, `None` if paused.
 pub fn active_stone_from_stage(stage: &str) -> Option<Stone> {
     match stage {
-        "black_to_play" => Some(Stone::Black),
+        "handicap_placement" | "black_to_play" => Some(Stone::Black),
         "white_to_play" => Some(Stone::White),
         _ => None,
     }
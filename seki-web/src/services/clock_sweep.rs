@@ -111,13 +111,7 @@ async fn sweep(state: &AppState) -> Result<(), Box<dyn std::error::Error>> {
         }
 
         // Ensure territory review state exists (may have been lost on restart)
-        if state.registry.get_territory_review(game_id).await.is_none() {
-            let dead_stones = go_engine::territory::detect_dead_stones(engine.goban());
-            state
-                .registry
-                .init_territory_review(game_id, dead_stones)
-                .await;
-        }
+        game_actions::open_territory_review(state, game_id, &engine, gwp.game.komi).await;
 
         let tr = match state.registry.get_territory_review(game_id).await {
             Some(tr) => tr,
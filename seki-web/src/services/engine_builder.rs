@@ -1,6 +1,9 @@
 use std::str::FromStr;
 
-use go_engine::{Engine, GameState, Move, Stage, Stone, Turn};
+use go_engine::{
+    Engine, GameState, HandicapPlacement, Move, PositionHistory, Ruleset, Stage, Stone, Topology,
+    Turn,
+};
 
 use crate::db::DbPool;
 use crate::models::game::Game;
@@ -10,10 +13,30 @@ pub(crate) fn game_handicap(game: &Game) -> u8 {
     game.handicap as u8
 }
 
-/// Build an Engine from the database state of a game. Uses cached_engine_state when the turn count matches.
+pub(crate) fn game_ruleset(game: &Game) -> Ruleset {
+    game.ruleset.parse().unwrap_or_default()
+}
+
+pub(crate) fn game_handicap_placement(game: &Game) -> HandicapPlacement {
+    game.handicap_placement.parse().unwrap_or_default()
+}
+
+pub(crate) fn game_topology(game: &Game) -> Topology {
+    game.topology.parse().unwrap_or_default()
+}
+
+pub(crate) fn game_capture_target(game: &Game) -> Option<u32> {
+    game.capture_target.map(|target| target as u32)
+}
+
+/// Build an Engine from the database state of a game. Uses cached_engine_state when the turn count matches
+/// and the cache carries the superko position history.
 pub async fn build_engine(pool: &DbPool, game: &Game) -> Result<Engine, sqlx::Error> {
     let turn_count = TurnRow::count_by_game_id(pool, game.id).await?;
     let handicap = game_handicap(game);
+    let ruleset = game_ruleset(game);
+    let placement = game_handicap_placement(game);
+    let topology = game_topology(game);
 
     // Check cache (lightweight: only fetches turns on hit, avoids full load on miss path below)
     if turn_count > 0
@@ -21,23 +44,38 @@ pub async fn build_engine(pool: &DbPool, game: &Game) -> Result<Engine, sqlx::Er
         && let Ok(value) = serde_json::from_str::<serde_json::Value>(cached)
         && value.get("turn_count").and_then(|v| v.as_i64()) == Some(turn_count)
         && value.get("handicap").and_then(|v| v.as_u64()) == Some(handicap as u64)
+        && let Some(Ok(history)) = value
+            .get("position_history")
+            .map(|v| serde_json::from_value::<PositionHistory>(v.clone()))
         && let Ok(gs) = serde_json::from_value::<GameState>(value)
     {
         let db_turns = TurnRow::find_by_game_id(pool, game.id).await?;
         let turns = convert_turns(&db_turns);
-        return Ok(Engine::from_game_state(
-            game.cols as u8,
-            game.rows as u8,
-            handicap,
-            turns,
-            gs,
-        ));
+        let mut engine =
+            Engine::from_game_state(game.cols as u8, game.rows as u8, handicap, turns, gs);
+        engine.restore_ruleset(ruleset);
+        engine.restore_handicap_placement(placement);
+        engine.restore_topology(topology);
+        engine.restore_position_history(history);
+        if let Some(target) = game_capture_target(game) {
+            engine = engine.with_capture_target(target);
+        }
+        return Ok(engine);
     }
 
     // Build from scratch
     let db_turns = TurnRow::find_by_game_id(pool, game.id).await?;
     let turns = convert_turns(&db_turns);
-    let engine = Engine::with_handicap_and_moves(game.cols as u8, game.rows as u8, handicap, turns);
+    // Stored turns were legal when played; replay them without re-checking superko.
+    let mut engine = Engine::with_handicap(game.cols as u8, game.rows as u8, handicap)
+        .with_handicap_placement(placement)
+        .with_ruleset(ruleset)
+        .with_topology(topology)
+        .replay(turns)
+        .map_err(|e| sqlx::Error::Decode(format!("game {}: {e}", game.id).into()))?;
+    if let Some(target) = game_capture_target(game) {
+        engine = engine.with_capture_target(target);
+    }
 
     // Cache the result
     cache_engine_state(pool, game.id, &engine, turn_count, None).await?;
@@ -62,6 +100,10 @@ pub async fn cache_engine_state(
             "handicap".to_string(),
             serde_json::Value::Number(serde_json::Number::from(engine.handicap())),
         );
+        map.insert(
+            "position_history".to_string(),
+            serde_json::to_value(engine.position_history()).unwrap_or_default(),
+        );
         if let Some(serde_json::Value::Object(meta_map)) = metadata {
             for (k, v) in meta_map {
                 map.insert(k, v);
@@ -1,9 +1,11 @@
+use go_engine::Stage;
+
 use crate::AppState;
 use crate::error::AppError;
 use crate::models::game::Game;
 use crate::models::rating::RatingProfile;
 use crate::models::user::User;
-use crate::services::{live, rating};
+use crate::services::{engine_builder, live, rating};
 
 use super::{broadcast_game_state, load_game_and_check_player};
 
@@ -80,16 +82,16 @@ pub async fn accept_challenge(
     let handicap = ranked_settings
         .as_ref()
         .map_or(gwp.game.handicap, |settings| settings.handicap);
-    let start_stage = if handicap >= 2 {
-        "white_to_play"
-    } else {
-        "black_to_play"
-    };
+    let start_stage = Stage::opening(
+        handicap as u8,
+        engine_builder::game_handicap_placement(&gwp.game),
+    )
+    .to_string();
 
     let mut tx = state.db.begin().await?;
     Game::set_black(&mut *tx, game_id, black_id).await?;
     Game::set_white(&mut *tx, game_id, white_id).await?;
-    Game::set_stage(&mut *tx, game_id, start_stage).await?;
+    Game::set_stage(&mut *tx, game_id, &start_stage).await?;
     tx.commit().await?;
 
     let gwp = Game::find_with_players(&state.db, game_id).await?;
@@ -123,6 +123,10 @@ mod tests {
             9,
             6.5,
             0,
+            "japanese",
+            "fixed",
+            "planar",
+            None,
             false,
             false,
             "access-token",
@@ -19,7 +19,7 @@ pub use pregame_settings::{
 };
 pub use rematch::rematch_game;
 pub use resign::{abort, resign};
-pub use territory::{approve_territory, settle_territory, toggle_chain};
+pub use territory::{approve_territory, open_territory_review, settle_territory, toggle_chain};
 pub use undo::{request_undo, respond_to_undo};
 
 use chrono::Utc;
@@ -69,6 +69,7 @@ pub async fn play_move(
         stone.to_int() as i32,
         Some(col),
         Some(row),
+        Some(engine.position_hash()),
         clock_snapshot.as_ref(),
     )
     .await
@@ -102,11 +103,26 @@ pub async fn play_move(
         gwp.game.undo_rejected = false;
     }
 
+    // Capture Go: reaching the capture target ends the game.
+    let ended = match engine.result() {
+        Some(result) => {
+            pause_clock(state, &mut *tx, game_id, &gwp.game).await?;
+            Game::set_ended(&mut *tx, game_id, result, "completed").await?
+        }
+        None => false,
+    };
+
     tx.commit().await?;
 
     // Non-transactional post-actions
     state.registry.set_undo_requested(game_id, false).await;
-    broadcast_game_state(state, &gwp, &engine).await;
+    if let Some(result) = engine.result()
+        && ended
+    {
+        super::end_game::finalize_and_broadcast(state, &mut gwp, game_id, result, &[]).await;
+    } else {
+        broadcast_game_state(state, &gwp, &engine).await;
+    }
 
     Ok(engine)
 }
@@ -161,6 +177,7 @@ pub async fn pass(
         stone.to_int() as i32,
         None,
         None,
+        Some(engine.position_hash()),
         clock_snapshot.as_ref(),
     )
     .await
@@ -194,20 +211,125 @@ pub async fn pass(
         pause_clock(state, &mut *tx, game_id, &gwp.game).await?;
     }
 
+    // Capture Go with pass stones: a pass can hand the opponent the winning prisoner.
+    let ended = match engine.result() {
+        Some(result) => {
+            pause_clock(state, &mut *tx, game_id, &gwp.game).await?;
+            Game::set_ended(&mut *tx, game_id, result, "completed").await?
+        }
+        None => false,
+    };
+
     tx.commit().await?;
 
     // Non-transactional post-actions
     state.registry.set_undo_requested(game_id, false).await;
 
     if engine.stage() == Stage::TerritoryReview {
-        let dead_stones = go_engine::territory::detect_dead_stones(engine.goban());
-        state
-            .registry
-            .init_territory_review(game_id, dead_stones)
-            .await;
+        super::open_territory_review(state, game_id, &engine, gwp.game.komi).await;
     }
 
-    broadcast_game_state(state, &gwp, &engine).await;
+    if let Some(result) = engine.result()
+        && ended
+    {
+        super::end_game::finalize_and_broadcast(state, &mut gwp, game_id, result, &[]).await;
+    } else {
+        broadcast_game_state(state, &gwp, &engine).await;
+    }
 
     Ok(engine)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::Utc;
+    use go_engine::Stage;
+    use tokio::net::TcpListener;
+
+    use super::pass;
+    use crate::models::game::{Game, TimeControlType};
+    use crate::models::user::User;
+    use crate::services::scoring::Scorer;
+
+    async fn test_pool() -> crate::db::DbPool {
+        let path = std::env::temp_dir().join(format!(
+            "seki-play-test-{}-{}.db",
+            std::process::id(),
+            Utc::now().timestamp_nanos_opt().unwrap()
+        ));
+        let url = format!("sqlite://{}", path.display());
+        let pool = crate::db::create_pool(&url).await.unwrap();
+        crate::db::run_migrations(&pool).await.unwrap();
+        pool
+    }
+
+    #[tokio::test]
+    async fn passing_into_review_does_not_wait_for_the_scoring_worker() {
+        let pool = test_pool().await;
+        let black = User::create(&pool).await.unwrap();
+        let white = User::create(&pool).await.unwrap();
+
+        let game = Game::create(
+            &pool,
+            black.id,
+            Some(white.id),
+            Some(black.id),
+            Some(white.id),
+            9,
+            9,
+            6.5,
+            0,
+            "japanese",
+            "fixed",
+            "planar",
+            None,
+            false,
+            false,
+            "access-token",
+            TimeControlType::None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            false,
+            None,
+            None,
+            false,
+            "unlimited",
+            None,
+            None,
+            true,
+            true,
+        )
+        .await
+        .unwrap();
+
+        // A worker that accepts connections but never answers.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let (_router, mut state) = crate::build_router(pool.clone(), false).await;
+        state.scorer = Scorer::new(Some(addr), Duration::from_secs(30));
+
+        let passes = async {
+            pass(&state, game.id, black.id, None).await.unwrap();
+            pass(&state, game.id, white.id, None).await.unwrap()
+        };
+        let engine = tokio::time::timeout(Duration::from_secs(5), passes)
+            .await
+            .expect("pass waited for the scoring worker");
+
+        assert_eq!(engine.stage(), Stage::TerritoryReview);
+        let review = state.registry.get_territory_review(game.id).await.unwrap();
+        assert_eq!(
+            review.dead_stones,
+            go_engine::territory::detect_dead_stones(engine.goban())
+        );
+        drop(listener);
+    }
+}
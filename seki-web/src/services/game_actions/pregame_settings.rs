@@ -1,10 +1,11 @@
+use go_engine::Stage;
 use rand::RngExt;
 
 use crate::AppState;
 use crate::error::AppError;
 use crate::models::game::Game;
 use crate::models::pregame_settings::PregameSettingsNegotiation;
-use crate::services::engine_builder;
+use crate::services::{engine_builder, game_creator};
 
 use super::{broadcast_game_state, load_game_and_check_player, require_both_players};
 
@@ -15,12 +16,31 @@ pub async fn update_pregame_settings(
     handicap: i32,
     komi: f64,
     color: String,
+    handicap_placement: Option<String>,
 ) -> Result<(), AppError> {
     let gwp = load_game_and_check_player(state, game_id, player_id).await?;
     require_pregame_settings(&gwp)?;
     validate_settings(&gwp.game, handicap, komi, &color)?;
 
-    PregameSettingsNegotiation::update_proposal(&state.db, game_id, handicap, komi, &color).await?;
+    let handicap_placement = match handicap_placement {
+        Some(name) => game_creator::parse_handicap_placement(Some(&name))?.to_string(),
+        None => PregameSettingsNegotiation::find(&state.db, game_id)
+            .await?
+            .map_or_else(
+                || gwp.game.handicap_placement.clone(),
+                |n| n.handicap_placement,
+            ),
+    };
+
+    PregameSettingsNegotiation::update_proposal(
+        &state.db,
+        game_id,
+        handicap,
+        &handicap_placement,
+        komi,
+        &color,
+    )
+    .await?;
 
     let engine = state
         .registry
@@ -112,21 +132,19 @@ async fn finalize_pregame_settings(
         &negotiation.color,
     )?;
     let (black_id, white_id) = final_player_assignment(&gwp, &negotiation.color)?;
-    let stage = if negotiation.handicap >= 2 {
-        "white_to_play"
-    } else {
-        "black_to_play"
-    };
+    let placement = negotiation.handicap_placement.parse().unwrap_or_default();
+    let stage = Stage::opening(negotiation.handicap as u8, placement).to_string();
 
     let mut tx = state.db.begin().await?;
     Game::update_rules(
         &mut *tx,
         gwp.game.id,
         negotiation.handicap,
+        &negotiation.handicap_placement,
         negotiation.komi,
         black_id,
         white_id,
-        stage,
+        &stage,
     )
     .await?;
     PregameSettingsNegotiation::delete(&mut *tx, gwp.game.id).await?;
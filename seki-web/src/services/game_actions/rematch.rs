@@ -2,6 +2,7 @@ use crate::AppState;
 use crate::error::AppError;
 use crate::models::game::Game;
 use crate::models::user::User;
+use crate::services::engine_builder;
 use crate::services::game_creator::{self, CreateGameParams, RatingRangePreference};
 use crate::services::live;
 
@@ -83,6 +84,10 @@ async fn ranked_rematch(
         rating_range: RatingRangePreference::Unlimited,
         open_game: false,
         creator_color: None,
+        ruleset: engine_builder::game_ruleset(&gwp.game),
+        handicap_placement: engine_builder::game_handicap_placement(&gwp.game),
+        topology: engine_builder::game_topology(&gwp.game),
+        capture_target: gwp.game.capture_target,
     };
 
     let (game, _) = game_creator::create_game(state, player, params).await?;
@@ -122,6 +127,10 @@ async fn unranked_rematch(
         rating_range: RatingRangePreference::Unlimited,
         open_game: false,
         creator_color: None,
+        ruleset: engine_builder::game_ruleset(&gwp.game),
+        handicap_placement: engine_builder::game_handicap_placement(&gwp.game),
+        topology: engine_builder::game_topology(&gwp.game),
+        capture_target: gwp.game.capture_target,
     };
 
     let (game, _) = game_creator::create_game(state, player, params).await?;
@@ -57,6 +57,7 @@ pub async fn resign(state: &AppState, game_id: i64, player_id: i64) -> Result<En
             None,
             None,
             None,
+            None,
         )
         .await
         {
@@ -111,7 +112,10 @@ pub async fn abort(state: &AppState, game_id: i64, player_id: i64) -> Result<(),
         ));
     }
 
-    let started = gwp.game.stage == "black_to_play" || gwp.game.stage == "white_to_play";
+    let started = matches!(
+        gwp.game.stage.as_str(),
+        "handicap_placement" | "black_to_play" | "white_to_play"
+    );
     if !started && gwp.game.creator_id != Some(player_id) {
         return Err(AppError::UnprocessableEntity(
             "Only the game creator can abort".to_string(),
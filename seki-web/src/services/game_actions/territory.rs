@@ -13,6 +13,47 @@ use super::{
     require_both_players, require_not_challenge,
 };
 
+/// Open territory review with the heuristic dead stones. When a scoring
+/// worker can score the board, it is asked in the background and its answer
+/// replaces the seed, unless a player has already edited the review.
+pub async fn open_territory_review(state: &AppState, game_id: i64, engine: &Engine, komi: f64) {
+    let dead_stones = go_engine::territory::detect_dead_stones(engine.goban());
+    if !state
+        .registry
+        .open_territory_review(game_id, dead_stones)
+        .await
+        || !state.scorer.scores(engine)
+    {
+        return;
+    }
+
+    let state = state.clone();
+    let engine = engine.clone();
+    tokio::spawn(async move {
+        let Some(dead_stones) = state
+            .scorer
+            .worker_dead_stones(game_id, &engine, komi)
+            .await
+        else {
+            return;
+        };
+        // The review may have been settled or undone while the worker ran.
+        let current = state.registry.get_engine(game_id).await;
+        if current.map(|e| e.position_hash()) != Some(engine.position_hash())
+            || !state
+                .registry
+                .reseed_territory_review(game_id, dead_stones)
+                .await
+        {
+            return;
+        }
+        match Game::find_with_players(&state.db, game_id).await {
+            Ok(gwp) => broadcast_game_state(&state, &gwp, &engine).await,
+            Err(e) => tracing::error!("Failed to load game {game_id} after scoring: {e}"),
+        }
+    });
+}
+
 pub async fn toggle_chain(
     state: &AppState,
     game_id: i64,
@@ -107,7 +148,14 @@ pub async fn settle_territory(
     dead_stones: &HashSet<go_engine::Point>,
 ) -> Result<(), AppError> {
     let ownership = go_engine::territory::estimate_territory(engine.goban(), dead_stones);
-    let gs = go_engine::territory::score(engine.goban(), &ownership, dead_stones, gwp.game.komi);
+    let gs = go_engine::territory::score(
+        engine.goban(),
+        &ownership,
+        dead_stones,
+        gwp.game.komi,
+        engine.handicap(),
+        engine.ruleset(),
+    );
     let result = gs.result();
 
     let dead_json: Vec<serde_json::Value> =
@@ -123,15 +171,18 @@ pub async fn settle_territory(
 
     sqlx::query(
         "INSERT INTO territory_reviews \
-         (game_id, settled, dead_stones, black_territory, black_captures, white_territory, white_captures) \
-         VALUES ($1, TRUE, $2::jsonb, $3, $4, $5, $6)",
+         (game_id, settled, dead_stones, black_territory, black_captures, black_stones, \
+          white_territory, white_captures, white_stones) \
+         VALUES ($1, TRUE, $2::jsonb, $3, $4, $5, $6, $7, $8)",
     )
     .bind(game_id)
     .bind(&dead_json_str)
     .bind(gs.black.territory as i32)
     .bind(gs.black.captures as i32)
+    .bind(gs.black.stones as i32)
     .bind(gs.white.territory as i32)
     .bind(gs.white.captures as i32)
+    .bind(gs.white.stones as i32)
     .execute(&mut *tx)
     .await?;
 
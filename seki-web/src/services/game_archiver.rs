@@ -0,0 +1,52 @@
+use go_engine::sgf;
+use go_engine::sgf::convert::summarize_collection;
+
+use crate::db::DbPool;
+use crate::error::AppError;
+use crate::models::archived_game::ArchivedGame;
+
+const MAX_IMPORT_GAMES: usize = 500;
+
+/// Store games from an SGF collection as archived games owned by `owner_id`.
+/// `indices` picks games by their position in the collection; `None` imports all of them.
+pub async fn import_collection(
+    db: &DbPool,
+    owner_id: i64,
+    sgf_text: &str,
+    indices: Option<&[usize]>,
+) -> Result<Vec<ArchivedGame>, AppError> {
+    let collection = sgf::parse(sgf_text)
+        .map_err(|e| AppError::UnprocessableEntity(format!("Invalid SGF: {e}")))?;
+    let summaries = summarize_collection(&collection);
+
+    let selected: Vec<usize> = match indices {
+        Some(indices) => indices.to_vec(),
+        None => (0..collection.len()).collect(),
+    };
+    if selected.is_empty() {
+        return Err(AppError::UnprocessableEntity(
+            "No games selected".to_string(),
+        ));
+    }
+    if selected.len() > MAX_IMPORT_GAMES {
+        return Err(AppError::UnprocessableEntity(format!(
+            "At most {MAX_IMPORT_GAMES} games can be imported at once"
+        )));
+    }
+    if let Some(&index) = selected.iter().find(|&&i| i >= collection.len()) {
+        return Err(AppError::UnprocessableEntity(format!(
+            "No game at index {index} (the collection has {})",
+            collection.len()
+        )));
+    }
+
+    let mut tx = db.begin().await?;
+    let mut games = Vec::with_capacity(selected.len());
+    for index in selected {
+        let text = sgf::serialize(&vec![collection[index].clone()]);
+        games.push(ArchivedGame::create(&mut *tx, owner_id, &summaries[index], &text).await?);
+    }
+    tx.commit().await?;
+
+    Ok(games)
+}
@@ -1,3 +1,5 @@
+use go_engine::{HandicapPlacement, Ruleset, Topology};
+
 use crate::AppState;
 use crate::error::AppError;
 use crate::models::game::{Game, TimeControlType};
@@ -38,7 +40,50 @@ pub struct CreateGameParams {
     /// Creator pre-selected color for custom-settings open games ("black"/"white"/"random").
     /// When set, handicap/komi come from the game row and are NOT re-derived at join.
     pub creator_color: Option<String>,
-    // TODO: Add support for ruleset selection (Japanese, Chinese, etc.)
+    pub ruleset: Ruleset,
+    pub handicap_placement: HandicapPlacement,
+    pub topology: Topology,
+    /// Capture Go: the first player to capture this many stones wins.
+    pub capture_target: Option<i32>,
+}
+
+/// Largest "first to N captures" target a Capture Go game may use.
+pub const MAX_CAPTURE_TARGET: i32 = 50;
+
+/// Parse a requested ruleset name, defaulting to Japanese when absent.
+pub fn parse_ruleset(name: Option<&str>) -> Result<Ruleset, AppError> {
+    match name.map(str::trim).filter(|name| !name.is_empty()) {
+        None => Ok(Ruleset::default()),
+        Some(name) => name.parse().map_err(|_| {
+            AppError::UnprocessableEntity(format!(
+                "Unknown ruleset '{name}' (expected japanese, chinese, aga or new_zealand)"
+            ))
+        }),
+    }
+}
+
+/// Parse a requested handicap placement, defaulting to fixed star points when absent.
+pub fn parse_handicap_placement(name: Option<&str>) -> Result<HandicapPlacement, AppError> {
+    match name.map(str::trim).filter(|name| !name.is_empty()) {
+        None => Ok(HandicapPlacement::default()),
+        Some(name) => name.parse().map_err(|_| {
+            AppError::UnprocessableEntity(format!(
+                "Unknown handicap placement '{name}' (expected fixed or free)"
+            ))
+        }),
+    }
+}
+
+/// Parse a requested board topology, defaulting to a planar board when absent.
+pub fn parse_topology(name: Option<&str>) -> Result<Topology, AppError> {
+    match name.map(str::trim).filter(|name| !name.is_empty()) {
+        None => Ok(Topology::default()),
+        Some(name) => name.parse().map_err(|_| {
+            AppError::UnprocessableEntity(format!(
+                "Unknown topology '{name}' (expected planar or torus)"
+            ))
+        }),
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -92,6 +137,15 @@ pub async fn create_game(
         ));
     }
 
+    if params
+        .capture_target
+        .is_some_and(|target| !(1..=MAX_CAPTURE_TARGET).contains(&target))
+    {
+        return Err(AppError::UnprocessableEntity(format!(
+            "Capture target must be between 1 and {MAX_CAPTURE_TARGET}"
+        )));
+    }
+
     // Handicap validation
     if params.handicap < 0 {
         return Err(AppError::UnprocessableEntity(
@@ -166,6 +220,10 @@ pub async fn create_game(
             rating::RankedCreateEligibility {
                 is_private: params.is_private,
                 has_direct_opponent: opponent.is_some(),
+                cols: params.cols,
+                rows: params.rows,
+                topology: params.topology,
+                capture_target: params.capture_target,
                 handicap: params.handicap,
                 komi: params.komi,
                 time_control: params.time_control,
@@ -233,6 +291,10 @@ pub async fn create_game(
         params.rows,
         params.komi,
         params.handicap,
+        &params.ruleset.to_string(),
+        &params.handicap_placement.to_string(),
+        &params.topology.to_string(),
+        params.capture_target,
         is_private,
         params.allow_undo,
         &access_token,
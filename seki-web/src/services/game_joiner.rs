@@ -1,3 +1,4 @@
+use go_engine::Stage;
 use rand::RngExt;
 
 use crate::db::DbPool;
@@ -7,6 +8,7 @@ use crate::models::game::GameWithPlayers;
 use crate::models::pregame_settings::PregameSettingsNegotiation;
 use crate::models::rating::RatingProfile;
 use crate::models::user::User;
+use crate::services::engine_builder;
 use crate::services::rating::{self, RatingCalibrationPolicy};
 
 // TODO: Function is too big, refactor
@@ -145,14 +147,14 @@ pub async fn join_open_game(
         let handicap = ranked_settings
             .as_ref()
             .map_or(gwp.game.handicap, |settings| settings.handicap);
-        let start_stage = if handicap >= 2 {
-            "white_to_play"
-        } else {
-            "black_to_play"
-        };
+        let start_stage = Stage::opening(
+            handicap as u8,
+            engine_builder::game_handicap_placement(&gwp.game),
+        )
+        .to_string();
         Game::set_black(&mut *tx, gwp.game.id, final_black_id.unwrap()).await?;
         Game::set_white(&mut *tx, gwp.game.id, final_white_id.unwrap()).await?;
-        Game::set_stage(&mut *tx, gwp.game.id, start_stage).await?;
+        Game::set_stage(&mut *tx, gwp.game.id, &start_stage).await?;
     } else if gwp.game.stage == "unstarted" {
         // Custom-settings open games keep the creator's choices;
         // otherwise derive handicap/komi/color from both players' ratings.
@@ -160,8 +162,15 @@ pub async fn join_open_game(
             Some(color) => (gwp.game.handicap, gwp.game.komi, color.to_string()),
             None => initial_unrated_pregame_settings(pool, creator_id, user.id).await?,
         };
-        PregameSettingsNegotiation::upsert_initial(&mut *tx, gwp.game.id, handicap, komi, &color)
-            .await?;
+        PregameSettingsNegotiation::upsert_initial(
+            &mut *tx,
+            gwp.game.id,
+            handicap,
+            &gwp.game.handicap_placement,
+            komi,
+            &color,
+        )
+        .await?;
     }
 
     tx.commit().await?;
@@ -243,6 +252,10 @@ mod tests {
             9,
             0.5,
             3,
+            "japanese",
+            "fixed",
+            "planar",
+            None,
             false,
             false,
             "access-token",
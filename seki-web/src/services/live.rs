@@ -158,6 +158,7 @@ fn extract_board_state_json(
         if let serde_json::Value::Object(ref mut map) = value {
             map.remove("turn_count");
             map.remove("handicap");
+            map.remove("position_history");
         }
 
         Some(value)
@@ -224,6 +225,10 @@ pub fn game_settings_for_game(game: &Game) -> GameSettings {
         cols: game.cols,
         rows: game.rows,
         handicap: engine_builder::game_handicap(game) as i32,
+        ruleset: game.ruleset.clone(),
+        handicap_placement: game.handicap_placement.clone(),
+        topology: game.topology.clone(),
+        capture_target: game.capture_target,
         max_rating_difference_lower: game.max_rating_difference_lower,
         max_rating_difference_higher: game.max_rating_difference_higher,
         rating_difference_lower_unlimited: game.rating_difference_lower_unlimited,
@@ -6,6 +6,7 @@ pub mod engine_builder;
 pub mod fcm;
 pub mod game_access;
 pub mod game_actions;
+pub mod game_archiver;
 pub mod game_creator;
 pub mod game_joiner;
 pub mod live;
@@ -13,8 +14,10 @@ pub mod mailer;
 pub mod maintenance;
 pub mod password_reset;
 pub mod presentation_actions;
+pub mod problems;
 pub mod push;
 pub mod rating;
+pub mod scoring;
 pub mod state_assembly;
 pub mod state_serializer;
 pub mod tokens;
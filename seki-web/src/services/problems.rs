@@ -0,0 +1,366 @@
+use go_engine::game_tree::{GameTree, NodeId};
+use go_engine::sgf;
+use go_engine::sgf::convert::sgf_to_game_tree;
+use go_engine::{Point, Setup, Stone};
+use serde::Serialize;
+use skillratings::glicko2::Glicko2Rating;
+
+use crate::db::DbPool;
+use crate::error::AppError;
+use crate::models::problem::{NewProblem, Problem, ProblemProgress, ProblemRating};
+use crate::services::rating::{GameOutcome, rate_encounter};
+
+const MAX_TITLE_LEN: usize = 100;
+const MAX_TREE_NODES: usize = 5000;
+/// Marks a correct leaf in its comment, as on most tsumego sites.
+const CORRECT_MARKER: &str = "RIGHT";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AttemptStatus {
+    Correct,
+    Wrong,
+    /// The line goes on; the player has another move to find.
+    Continue,
+}
+
+/// Where an attempt's moves lead in a problem's solution tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttemptCheck {
+    pub status: AttemptStatus,
+    /// The opponent's answer to the player's last move, if the tree has one.
+    pub response: Option<Point>,
+    /// Comment on the node the attempt stopped at.
+    pub comment: Option<String>,
+}
+
+/// A problem SGF checked and converted.
+pub struct ParsedProblem {
+    pub tree: GameTree,
+    pub cols: u8,
+    pub rows: u8,
+    pub to_play: Stone,
+    pub game_name: Option<String>,
+    /// The game re-serialized, as it is stored.
+    pub sgf: String,
+}
+
+impl ParsedProblem {
+    pub fn setup(&self) -> &Setup {
+        self.tree.setup()
+    }
+}
+
+/// Parse a single-game problem SGF. The root setup is the position; the
+/// side to move comes from `PL[]` or the first move of the tree.
+pub fn parse_problem(sgf_text: &str) -> Result<ParsedProblem, AppError> {
+    let collection = sgf::parse(sgf_text)
+        .map_err(|e| AppError::UnprocessableEntity(format!("Invalid SGF: {e}")))?;
+    let [game] = &collection[..] else {
+        return Err(AppError::UnprocessableEntity(
+            "A problem SGF must hold exactly one game".to_string(),
+        ));
+    };
+    let conversion = sgf_to_game_tree(game);
+    let tree = conversion.tree;
+
+    if tree.setup().black.is_empty() && tree.setup().white.is_empty() {
+        return Err(AppError::UnprocessableEntity(
+            "A problem needs setup stones".to_string(),
+        ));
+    }
+    if tree.len() > MAX_TREE_NODES {
+        return Err(AppError::UnprocessableEntity(format!(
+            "A problem tree may have at most {MAX_TREE_NODES} moves"
+        )));
+    }
+    let Some(&first) = tree.root_children().first() else {
+        return Err(AppError::UnprocessableEntity(
+            "A problem needs a solution tree".to_string(),
+        ));
+    };
+    let to_play = tree.setup().to_play.unwrap_or(tree.node(first).turn.stone);
+    if !(0..tree.len()).any(|id| is_leaf(&tree, id) && is_correct(&tree, id)) {
+        return Err(AppError::UnprocessableEntity(format!(
+            "No correct variation; mark correct leaves with \"{CORRECT_MARKER}\" in their comment"
+        )));
+    }
+
+    Ok(ParsedProblem {
+        tree,
+        cols: conversion.metadata.cols,
+        rows: conversion.metadata.rows,
+        to_play,
+        game_name: conversion.metadata.game_name,
+        sgf: sgf::serialize(&collection),
+    })
+}
+
+/// Follow the player's `moves` through the tree, answering each with the
+/// first reply listed. A move the tree does not have is wrong; reaching a
+/// leaf, after either side's move, finishes the attempt.
+pub fn check_attempt(
+    tree: &GameTree,
+    to_play: Stone,
+    moves: &[Point],
+) -> Result<AttemptCheck, AppError> {
+    let mut parent = None;
+    let mut check = AttemptCheck {
+        status: AttemptStatus::Continue,
+        response: None,
+        comment: None,
+    };
+    for (i, &point) in moves.iter().enumerate() {
+        if check.status != AttemptStatus::Continue {
+            return Err(AppError::UnprocessableEntity(format!(
+                "Move {i} comes after the attempt finished"
+            )));
+        }
+        let found = tree.children_of(parent).iter().copied().find(|&id| {
+            let turn = &tree.node(id).turn;
+            turn.stone == to_play && turn.pos == Some(point)
+        });
+        let Some(node) = found else {
+            check = AttemptCheck {
+                status: AttemptStatus::Wrong,
+                response: None,
+                comment: None,
+            };
+            continue;
+        };
+        check = match tree.children_of(Some(node)).first() {
+            None => finished(tree, node, None),
+            Some(&reply) => {
+                let response = tree.node(reply).turn.pos;
+                parent = Some(reply);
+                if is_leaf(tree, reply) {
+                    finished(tree, reply, response)
+                } else {
+                    AttemptCheck {
+                        status: AttemptStatus::Continue,
+                        response,
+                        comment: tree.annotation(Some(reply)).comment.clone(),
+                    }
+                }
+            }
+        };
+    }
+    Ok(check)
+}
+
+fn finished(tree: &GameTree, leaf: NodeId, response: Option<Point>) -> AttemptCheck {
+    AttemptCheck {
+        status: if is_correct(tree, leaf) {
+            AttemptStatus::Correct
+        } else {
+            AttemptStatus::Wrong
+        },
+        response,
+        comment: tree.annotation(Some(leaf)).comment.clone(),
+    }
+}
+
+fn is_leaf(tree: &GameTree, node: NodeId) -> bool {
+    tree.children_of(Some(node)).is_empty()
+}
+
+fn is_correct(tree: &GameTree, node: NodeId) -> bool {
+    tree.annotation(Some(node))
+        .comment
+        .as_deref()
+        .is_some_and(|comment| comment.contains(CORRECT_MARKER))
+}
+
+/// Store a problem. The title falls back to the SGF's `GN[]`.
+pub async fn create_problem(
+    db: &DbPool,
+    author_id: i64,
+    title: Option<&str>,
+    sgf_text: &str,
+) -> Result<Problem, AppError> {
+    let parsed = parse_problem(sgf_text)?;
+    let title = title
+        .or(parsed.game_name.as_deref())
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .ok_or_else(|| AppError::UnprocessableEntity("A problem needs a title".to_string()))?;
+    if title.chars().count() > MAX_TITLE_LEN {
+        return Err(AppError::UnprocessableEntity(format!(
+            "Title must be at most {MAX_TITLE_LEN} characters"
+        )));
+    }
+
+    let to_play = stone_name(parsed.to_play);
+    let problem = Problem::create(
+        db,
+        &NewProblem {
+            author_id,
+            title,
+            cols: parsed.cols as i32,
+            rows: parsed.rows as i32,
+            to_play,
+            sgf: &parsed.sgf,
+        },
+    )
+    .await?;
+    Ok(problem)
+}
+
+pub fn stone_name(stone: Stone) -> &'static str {
+    match stone {
+        Stone::Black => "black",
+        Stone::White => "white",
+    }
+}
+
+/// The outcome of an attempt, with the ratings as they stand afterwards.
+pub struct AttemptOutcome {
+    pub check: AttemptCheck,
+    pub problem_rating: f64,
+    /// The player's puzzle rating, once they have one.
+    pub user_rating: Option<f64>,
+    /// Whether this attempt changed the ratings.
+    pub rated: bool,
+}
+
+/// Check an attempt and, if it finished, record it. A user's first finished
+/// attempt at a problem is rated as a Glicko-2 game between the user and
+/// the problem; later attempts and the author's own are only counted.
+pub async fn submit_attempt(
+    db: &DbPool,
+    user_id: i64,
+    problem: &Problem,
+    moves: &[Point],
+) -> Result<AttemptOutcome, AppError> {
+    let parsed = parse_problem(&problem.sgf)?;
+    let check = check_attempt(&parsed.tree, parsed.to_play, moves)?;
+    let mut outcome = AttemptOutcome {
+        check,
+        problem_rating: problem.rating,
+        user_rating: ProblemRating::find(db, user_id).await?.map(|r| r.rating),
+        rated: false,
+    };
+    if outcome.check.status == AttemptStatus::Continue {
+        return Ok(outcome);
+    }
+
+    let solved = outcome.check.status == AttemptStatus::Correct;
+    let mut tx = db.begin().await?;
+    ProblemProgress::record_attempt(&mut *tx, user_id, problem.id, solved).await?;
+    Problem::record_attempt(&mut *tx, problem.id, solved).await?;
+
+    if problem.author_id != Some(user_id)
+        && ProblemProgress::set_rated(&mut *tx, user_id, problem.id).await?
+    {
+        let player = ProblemRating::find(&mut *tx, user_id)
+            .await?
+            .map(|r| Glicko2Rating {
+                rating: r.rating,
+                deviation: r.deviation,
+                volatility: r.volatility,
+            })
+            .unwrap_or_default();
+        // Re-read after the write above takes the lock: a concurrent attempt
+        // may have rated the problem since `problem` was loaded.
+        let current = Problem::find_by_id(&mut *tx, problem.id).await?;
+        let opponent = Glicko2Rating {
+            rating: current.rating,
+            deviation: current.deviation,
+            volatility: current.volatility,
+        };
+        let result = if solved {
+            GameOutcome::BlackWin
+        } else {
+            GameOutcome::WhiteWin
+        };
+        let (player, opponent) = rate_encounter(&player, &opponent, result);
+
+        ProblemRating::upsert(
+            &mut *tx,
+            user_id,
+            player.rating,
+            player.deviation,
+            player.volatility,
+        )
+        .await?;
+        Problem::update_rating(
+            &mut *tx,
+            problem.id,
+            opponent.rating,
+            opponent.deviation,
+            opponent.volatility,
+        )
+        .await?;
+        outcome.problem_rating = opponent.rating;
+        outcome.user_rating = Some(player.rating);
+        outcome.rated = true;
+    }
+    tx.commit().await?;
+
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // One correct line, one try refuted at once, and a try that needs a
+    // second Black move to settle.
+    const PROBLEM: &str = "(;GM[1]SZ[9]PL[B]\
+        AB[ab][bb][cb][db][ea][eb]AW[ac][bc][cc][dc][ec][fb][fa]\
+        (;B[ca];W[ba];B[aa]C[RIGHT: ko-free two eyes])\
+        (;B[ba];W[ca]C[Dead, straight two])\
+        (;B[da];W[ca](;B[ba]C[RIGHT])(;B[aa];W[ba]C[Wrong])))";
+
+    fn parsed() -> ParsedProblem {
+        parse_problem(PROBLEM).unwrap()
+    }
+
+    #[test]
+    fn parse_reads_position_and_side_to_move() {
+        let problem = parsed();
+        assert_eq!((problem.cols, problem.rows), (9, 9));
+        assert_eq!(problem.to_play, Stone::Black);
+        assert_eq!(problem.setup().black.len(), 6);
+    }
+
+    #[test]
+    fn correct_line_ends_on_a_right_leaf() {
+        let problem = parsed();
+        let check = check_attempt(&problem.tree, problem.to_play, &[(2, 0)]).unwrap();
+        assert_eq!(check.status, AttemptStatus::Continue);
+        assert_eq!(check.response, Some((1, 0)));
+
+        let check = check_attempt(&problem.tree, problem.to_play, &[(2, 0), (0, 0)]).unwrap();
+        assert_eq!(check.status, AttemptStatus::Correct);
+        assert_eq!(check.response, None);
+    }
+
+    #[test]
+    fn refutation_and_unknown_moves_are_wrong() {
+        let problem = parsed();
+        let check = check_attempt(&problem.tree, problem.to_play, &[(1, 0)]).unwrap();
+        assert_eq!(check.status, AttemptStatus::Wrong);
+        assert_eq!(check.response, Some((2, 0)));
+        assert_eq!(check.comment.as_deref(), Some("Dead, straight two"));
+
+        let check = check_attempt(&problem.tree, problem.to_play, &[(8, 8)]).unwrap();
+        assert_eq!(check.status, AttemptStatus::Wrong);
+
+        let check = check_attempt(&problem.tree, problem.to_play, &[(3, 0), (0, 0)]).unwrap();
+        assert_eq!(check.status, AttemptStatus::Wrong);
+        assert_eq!(check.response, Some((1, 0)));
+    }
+
+    #[test]
+    fn moves_after_a_finished_attempt_are_rejected() {
+        let problem = parsed();
+        assert!(check_attempt(&problem.tree, problem.to_play, &[(1, 0), (0, 0)]).is_err());
+    }
+
+    #[test]
+    fn problem_without_correct_leaf_is_rejected() {
+        assert!(parse_problem("(;SZ[9]AB[aa]AW[ba](;B[ca]C[Wrong]))").is_err());
+        assert!(parse_problem("(;SZ[9](;B[ca]C[RIGHT]))").is_err());
+    }
+}
@@ -224,6 +224,21 @@ pub fn profile_to_rating(profile: &RatingProfile) -> Glicko2Rating {
     }
 }
 
+/// Glicko-2 ratings after one encounter, with `first` on Black's side of
+/// `outcome`. Also rates puzzle attempts, where the problem is the opponent.
+pub fn rate_encounter(
+    first: &Glicko2Rating,
+    second: &Glicko2Rating,
+    outcome: GameOutcome,
+) -> (Glicko2Rating, Glicko2Rating) {
+    glicko2(
+        first,
+        second,
+        &outcome.as_skillratings(),
+        &Glicko2Config::default(),
+    )
+}
+
 pub fn apply_glicko2(
     black: &RatingProfile,
     white: &RatingProfile,
@@ -231,12 +246,7 @@ pub fn apply_glicko2(
 ) -> RatingUpdate {
     let black_before = profile_to_rating(black);
     let white_before = profile_to_rating(white);
-    let (black_after, white_after) = glicko2(
-        &black_before,
-        &white_before,
-        &outcome.as_skillratings(),
-        &Glicko2Config::default(),
-    );
+    let (black_after, white_after) = rate_encounter(&black_before, &white_before, outcome);
 
     RatingUpdate {
         black_before,
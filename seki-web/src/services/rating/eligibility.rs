@@ -1,3 +1,5 @@
+use go_engine::Topology;
+
 use crate::error::AppError;
 use crate::models::game::TimeControlType;
 use crate::models::rating::RatingProfile;
@@ -7,6 +9,10 @@ use crate::models::user::User;
 pub struct RankedCreateEligibility {
     pub is_private: bool,
     pub has_direct_opponent: bool,
+    pub cols: i32,
+    pub rows: i32,
+    pub topology: Topology,
+    pub capture_target: Option<i32>,
     pub handicap: i32,
     pub komi: f64,
     pub time_control: TimeControlType,
@@ -45,6 +51,25 @@ pub fn can_create_ranked(
         ));
     }
 
+    // Ratings are calibrated on square boards only.
+    if eligibility.cols != eligibility.rows {
+        return Err(AppError::UnprocessableEntity(
+            "Ranked games must be played on a square board".to_string(),
+        ));
+    }
+
+    if eligibility.topology != Topology::Planar {
+        return Err(AppError::UnprocessableEntity(
+            "Torus games cannot be ranked".to_string(),
+        ));
+    }
+
+    if eligibility.capture_target.is_some() {
+        return Err(AppError::UnprocessableEntity(
+            "Capture Go games cannot be ranked".to_string(),
+        ));
+    }
+
     // TODO: Update checks to Some/None after upstream type changes
     // TODO: Also enforce that color is None
     if eligibility.handicap != 0 || (eligibility.komi - 6.5).abs() > f64::EPSILON {
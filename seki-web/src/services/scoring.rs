@@ -0,0 +1,193 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use go_engine::{Engine, Point, Stone, Topology};
+use seki_api::scoring::{ScoreRequest, ScoreResponse};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+
+/// Seeds territory review from a scoring worker (a seki-gtp process running
+/// KataGo or another GTP engine). Reviews open with the heuristic in
+/// `go_engine::territory`; the worker's answer replaces it when it arrives.
+#[derive(Clone)]
+pub struct Scorer {
+    addr: Option<String>,
+    timeout: Duration,
+}
+
+impl Scorer {
+    pub fn new(addr: Option<String>, timeout: Duration) -> Self {
+        Self { addr, timeout }
+    }
+
+    pub fn from_env() -> Self {
+        let addr = std::env::var("SCORING_ADDR")
+            .ok()
+            .filter(|addr| !addr.is_empty());
+        match &addr {
+            Some(addr) => tracing::info!("Territory scoring via worker at {addr}"),
+            None => tracing::info!("SCORING_ADDR not set — using heuristic territory scoring"),
+        }
+        let timeout_ms = std::env::var("SCORING_TIMEOUT_MS")
+            .ok()
+            .and_then(|ms| ms.parse().ok())
+            .unwrap_or(10_000);
+        Self::new(addr, Duration::from_millis(timeout_ms))
+    }
+
+    /// Whether a worker can score `engine`'s board. GTP engines only play on
+    /// planar boards.
+    pub fn scores(&self, engine: &Engine) -> bool {
+        self.addr.is_some() && engine.goban().topology() == Topology::Planar
+    }
+
+    /// Dead stones according to the worker, or `None` when there is no
+    /// worker for this board or it fails or times out.
+    pub async fn worker_dead_stones(
+        &self,
+        game_id: i64,
+        engine: &Engine,
+        komi: f64,
+    ) -> Option<HashSet<Point>> {
+        let addr = self.addr.as_ref().filter(|_| self.scores(engine))?;
+        let request = score_request(game_id, engine, komi);
+        match tokio::time::timeout(self.timeout, ask_worker(addr, &request)).await {
+            Ok(Ok(response)) => {
+                tracing::info!(
+                    "Scoring worker settled game {game_id}: {} dead, score {:?}",
+                    response.dead_stones.len(),
+                    response.score
+                );
+                Some(
+                    response
+                        .dead_stones
+                        .into_iter()
+                        .map(|[col, row]| (col, row))
+                        .filter(|&p| engine.goban().stone_at(p).is_some())
+                        .collect(),
+                )
+            }
+            Ok(Err(e)) => {
+                tracing::warn!("Scoring worker failed for game {game_id}: {e}");
+                None
+            }
+            Err(_) => {
+                tracing::warn!("Scoring worker timed out for game {game_id}");
+                None
+            }
+        }
+    }
+}
+
+fn score_request(game_id: i64, engine: &Engine, komi: f64) -> ScoreRequest {
+    let goban = engine.goban();
+    let mut black = Vec::new();
+    let mut white = Vec::new();
+    for row in 0..goban.rows() {
+        for col in 0..goban.cols() {
+            match goban.stone_at((col, row)) {
+                Some(Stone::Black) => black.push([col, row]),
+                Some(Stone::White) => white.push([col, row]),
+                None => {}
+            }
+        }
+    }
+    ScoreRequest {
+        game_id,
+        cols: goban.cols(),
+        rows: goban.rows(),
+        komi,
+        black,
+        white,
+    }
+}
+
+async fn ask_worker(addr: &str, request: &ScoreRequest) -> Result<ScoreResponse, String> {
+    let stream = TcpStream::connect(addr)
+        .await
+        .map_err(|e| format!("connect to {addr}: {e}"))?;
+    let (reader, mut writer) = stream.into_split();
+
+    let mut line = serde_json::to_string(request).map_err(|e| e.to_string())?;
+    line.push('\n');
+    writer
+        .write_all(line.as_bytes())
+        .await
+        .map_err(|e| format!("send request: {e}"))?;
+
+    let mut reply = String::new();
+    BufReader::new(reader)
+        .read_line(&mut reply)
+        .await
+        .map_err(|e| format!("read response: {e}"))?;
+    let response: ScoreResponse =
+        serde_json::from_str(&reply).map_err(|e| format!("invalid response: {e}"))?;
+    match response.error {
+        Some(e) => Err(e),
+        None => Ok(response),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    /// B at (0,0) and (1,1), W at (2,2) on a 3x3 board.
+    fn engine() -> Engine {
+        let mut engine = Engine::new(3, 3);
+        engine.try_play(Stone::Black, (0, 0)).unwrap();
+        engine.try_play(Stone::White, (2, 2)).unwrap();
+        engine.try_play(Stone::Black, (1, 1)).unwrap();
+        engine
+    }
+
+    /// A worker that answers one request with `reply` and hands back the
+    /// request it received.
+    async fn fake_worker(reply: &'static str) -> (String, tokio::task::JoinHandle<ScoreRequest>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let handle = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (reader, mut writer) = stream.into_split();
+            let mut line = String::new();
+            BufReader::new(reader).read_line(&mut line).await.unwrap();
+            writer.write_all(reply.as_bytes()).await.unwrap();
+            serde_json::from_str(&line).unwrap()
+        });
+        (addr, handle)
+    }
+
+    #[tokio::test]
+    async fn worker_dead_stones_seed_the_review() {
+        let (addr, worker) = fake_worker("{\"dead_stones\":[[2,2],[1,0]],\"score\":9.5}\n").await;
+        let scorer = Scorer::new(Some(addr), Duration::from_secs(5));
+
+        let dead = scorer.worker_dead_stones(7, &engine(), 6.5).await;
+        // Points without a stone are dropped.
+        assert_eq!(dead, Some(HashSet::from([(2, 2)])));
+
+        let request = worker.await.unwrap();
+        assert_eq!(request.game_id, 7);
+        assert_eq!(request.black, vec![[0, 0], [1, 1]]);
+        assert_eq!(request.white, vec![[2, 2]]);
+    }
+
+    #[tokio::test]
+    async fn worker_errors_yield_no_seed() {
+        let (addr, _worker) = fake_worker("{\"error\":\"engine crashed\"}\n").await;
+        let scorer = Scorer::new(Some(addr), Duration::from_secs(5));
+
+        assert_eq!(scorer.worker_dead_stones(1, &engine(), 6.5).await, None);
+    }
+
+    #[tokio::test]
+    async fn slow_workers_yield_no_seed() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let scorer = Scorer::new(Some(addr), Duration::from_millis(50));
+
+        assert_eq!(scorer.worker_dead_stones(1, &engine(), 6.5).await, None);
+        drop(listener);
+    }
+}
@@ -8,7 +8,7 @@ pub use seki_api::game::{
 use seki_api::ws::{GameSettingsWithSnapshots, ServerMsg};
 use serde_json::json;
 
-use crate::models::game::GameWithPlayers;
+use crate::models::game::{GameWithPlayers, SettledTerritoryRow};
 use crate::models::pregame_settings::PregameSettingsNegotiation;
 use crate::models::rating::RatingProfile;
 use crate::services::clock::{self, ClockState, TimeControl};
@@ -22,6 +22,7 @@ pub fn pregame_settings_from_negotiation(
 ) -> PregameSettingsData {
     PregameSettingsData {
         handicap: settings.handicap,
+        handicap_placement: settings.handicap_placement.clone(),
         komi: settings.komi,
         color: settings.color.clone(),
         creator_approved: settings.creator_approved,
@@ -34,12 +35,9 @@ pub fn pregame_settings_from_negotiation(
     }
 }
 
-/// Build a `SettledTerritoryData` from raw DB tuple (dead_stones JSON, bt, bc, wt, wc).
-pub fn build_settled_territory(
-    engine: &Engine,
-    raw: (Option<serde_json::Value>, i32, i32, i32, i32),
-) -> SettledTerritoryData {
-    let (dead_json, bt, bc, wt, wc) = raw;
+/// Build a `SettledTerritoryData` from raw DB tuple (dead_stones JSON, bt, bc, bs, wt, wc, ws).
+pub fn build_settled_territory(engine: &Engine, raw: SettledTerritoryRow) -> SettledTerritoryData {
+    let (dead_json, bt, bc, bs, wt, wc, ws) = raw;
     let dead_stones_set: HashSet<Point> = dead_json
         .as_ref()
         .and_then(|v| serde_json::from_value::<Vec<(u8, u8)>>(v.clone()).ok())
@@ -56,11 +54,14 @@ pub fn build_settled_territory(
             black: TerritorySide {
                 territory: bt as u32,
                 captures: bc as u32,
+                stones: bs as u32,
             },
             white: TerritorySide {
                 territory: wt as u32,
                 captures: wc as u32,
+                stones: ws as u32,
             },
+            handicap_bonus: engine.ruleset().handicap_bonus(engine.handicap()),
         },
     }
 }
@@ -74,7 +75,14 @@ pub fn compute_territory_data(
     expires_at: Option<chrono::DateTime<chrono::Utc>>,
 ) -> TerritoryState {
     let ownership = go_engine::territory::estimate_territory(engine.goban(), dead_stones);
-    let score = go_engine::territory::score(engine.goban(), &ownership, dead_stones, komi);
+    let score = go_engine::territory::score(
+        engine.goban(),
+        &ownership,
+        dead_stones,
+        komi,
+        engine.handicap(),
+        engine.ruleset(),
+    );
 
     let mut dead_list: Vec<(u8, u8)> = dead_stones.iter().copied().collect();
     dead_list.sort();
@@ -86,11 +94,14 @@ pub fn compute_territory_data(
             black: TerritorySide {
                 territory: score.black.territory,
                 captures: score.black.captures,
+                stones: score.black.stones,
             },
             white: TerritorySide {
                 territory: score.white.territory,
                 captures: score.white.captures,
+                stones: score.white.stones,
             },
+            handicap_bonus: score.handicap_bonus,
         },
         black_approved,
         white_approved,
@@ -48,15 +48,8 @@ pub async fn send_initial_state(
     // Restore territory review state on reconnect if needed
     // Skip if the game is already done (engine doesn't know about DB result)
     let game_is_done = gwp.game.result.is_some();
-    if !game_is_done
-        && engine.stage() == Stage::TerritoryReview
-        && state.registry.get_territory_review(game_id).await.is_none()
-    {
-        let dead_stones = go_engine::territory::detect_dead_stones(engine.goban());
-        state
-            .registry
-            .init_territory_review(game_id, dead_stones)
-            .await;
+    if !game_is_done && engine.stage() == Stage::TerritoryReview {
+        game_actions::open_territory_review(state, game_id, &engine, gwp.game.komi).await;
     }
 
     let undo_requested = state.registry.is_undo_requested(game_id).await;
@@ -202,6 +195,7 @@ pub async fn handle_message(
             handicap,
             komi,
             color,
+            handicap_placement,
             ..
         } => {
             game_actions::update_pregame_settings(
@@ -211,6 +205,7 @@ pub async fn handle_message(
                 *handicap,
                 *komi,
                 color.clone(),
+                handicap_placement.clone(),
             )
             .await
         }
@@ -419,7 +419,7 @@ async fn build_init_message(state: &AppState, user_id: i64) -> String {
 /// Check whether it's the given user's turn (or they need to respond to a challenge).
 fn is_user_turn(game: &Game, user_id: i64) -> bool {
     match game.stage.as_str() {
-        "black_to_play" => game.black_id == Some(user_id),
+        "handicap_placement" | "black_to_play" => game.black_id == Some(user_id),
         "white_to_play" => game.white_id == Some(user_id),
         "challenge" => {
             // It's the invited player's turn to accept/decline.
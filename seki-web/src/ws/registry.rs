@@ -17,6 +17,8 @@ pub struct TerritoryReviewState {
     pub dead_stones: HashSet<Point>,
     pub black_approved: bool,
     pub white_approved: bool,
+    /// Whether a player has toggled a chain or approved since the review opened.
+    pub edited: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -280,14 +282,37 @@ impl GameRegistry {
 
     // -- Territory review --
 
-    pub async fn init_territory_review(&self, game_id: i64, dead_stones: HashSet<Point>) {
+    /// Open territory review with `dead_stones`, leaving an existing review
+    /// alone. Returns whether a review was opened.
+    pub async fn open_territory_review(&self, game_id: i64, dead_stones: HashSet<Point>) -> bool {
         let mut rooms = self.rooms.write().await;
-        if let Some(room) = rooms.get_mut(&game_id) {
-            room.territory_review = Some(TerritoryReviewState {
-                dead_stones,
-                black_approved: false,
-                white_approved: false,
-            });
+        match rooms.get_mut(&game_id) {
+            Some(room) if room.territory_review.is_none() => {
+                room.territory_review = Some(TerritoryReviewState {
+                    dead_stones,
+                    black_approved: false,
+                    white_approved: false,
+                    edited: false,
+                });
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Replace the dead stones of a review no player has edited yet.
+    /// Returns whether they were replaced.
+    pub async fn reseed_territory_review(&self, game_id: i64, dead_stones: HashSet<Point>) -> bool {
+        let mut rooms = self.rooms.write().await;
+        match rooms
+            .get_mut(&game_id)
+            .and_then(|room| room.territory_review.as_mut())
+        {
+            Some(tr) if !tr.edited => {
+                tr.dead_stones = dead_stones;
+                true
+            }
+            _ => false,
         }
     }
 
@@ -313,6 +338,7 @@ impl GameRegistry {
         go_engine::territory::toggle_dead_chain(goban, &mut tr.dead_stones, point);
         tr.black_approved = false;
         tr.white_approved = false;
+        tr.edited = true;
         Some(tr.dead_stones.clone())
     }
 
@@ -325,6 +351,7 @@ impl GameRegistry {
                 go_engine::Stone::Black => tr.black_approved = approved,
                 go_engine::Stone::White => tr.white_approved = approved,
             }
+            tr.edited = true;
         }
     }
 
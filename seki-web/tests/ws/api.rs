@@ -265,6 +265,114 @@ async fn create_game_via_api() {
     assert!(body["white"].is_null());
 }
 
+#[tokio::test]
+async fn create_game_with_ruleset() {
+    let server = LightServer::start().await;
+
+    let resp = server
+        .try_create_game_with(json!({"ruleset": "chinese"}))
+        .await;
+    assert_eq!(resp.status(), 201);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["ruleset"], "chinese");
+
+    let resp = server
+        .request(
+            Method::GET,
+            &format!("/api/games/{}", body["id"]),
+            "test-black-api-token-12345",
+            None,
+        )
+        .await;
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["ruleset"], "chinese");
+}
+
+#[tokio::test]
+async fn create_game_defaults_to_japanese_rules() {
+    let server = LightServer::start().await;
+
+    let resp = server.try_create_game_with(json!({})).await;
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["ruleset"], "japanese");
+}
+
+#[tokio::test]
+async fn reject_unknown_ruleset() {
+    let server = LightServer::start().await;
+
+    let resp = server.try_create_game_with(json!({"ruleset": "ing"})).await;
+    assert_eq!(resp.status(), 422);
+}
+
+#[tokio::test]
+async fn free_handicap_stones_are_placed_by_black() {
+    let server = LightServer::start().await;
+
+    let resp = server
+        .try_create_game_with(json!({
+            "handicap": 2,
+            "komi": 0.5,
+            "handicap_placement": "free",
+        }))
+        .await;
+    assert_eq!(resp.status(), 201);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["handicap_placement"], "free");
+    let game_id = body["id"].as_i64().unwrap();
+
+    server
+        .request(
+            Method::POST,
+            &format!("/api/games/{game_id}/accept"),
+            "test-white-api-token-67890",
+            None,
+        )
+        .await;
+
+    let path = format!("/api/games/{game_id}");
+    let token = "test-black-api-token-12345";
+    let body: Value = server
+        .request(Method::GET, &path, token, None)
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["stage"], "handicap_placement");
+
+    let resp = server
+        .request(Method::POST, &format!("{path}/pass"), token, None)
+        .await;
+    assert_eq!(resp.status(), 422, "passing while placing handicap stones");
+
+    for (col, row) in [(0, 0), (8, 8)] {
+        let resp = server
+            .request(
+                Method::POST,
+                &format!("{path}/play"),
+                token,
+                Some(&json!({"col": col, "row": row})),
+            )
+            .await;
+        assert!(
+            resp.status().is_success(),
+            "handicap stone: {}",
+            resp.status()
+        );
+    }
+
+    let body: Value = server
+        .request(Method::GET, &path, token, None)
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["stage"], "white_to_play");
+    // No stones on the star points, only the two Black placed.
+    let board = body["state"]["board"].as_array().unwrap();
+    assert_eq!(board.iter().filter(|v| **v == 1).count(), 2);
+}
+
 #[tokio::test]
 async fn create_random_challenge_leaves_colors_unset_until_accept() {
     let server = LightServer::start().await;
@@ -681,6 +789,78 @@ async fn get_turns_after_moves() {
     assert_eq!(body[1]["row"], 6);
 }
 
+#[tokio::test]
+async fn position_lookup_finds_transposed_games() {
+    let server = LightServer::start().await;
+    let orders = [[(2, 2), (6, 6), (2, 6)], [(2, 6), (6, 6), (2, 2)]];
+    let mut game_ids = Vec::new();
+    let mut hashes = Vec::new();
+    for order in orders {
+        let game_id = server.create_and_join().await;
+        for (i, (col, row)) in order.into_iter().enumerate() {
+            let token = if i % 2 == 0 {
+                "test-black-api-token-12345"
+            } else {
+                "test-white-api-token-67890"
+            };
+            let resp = server
+                .request(
+                    Method::POST,
+                    &format!("/api/games/{game_id}/play"),
+                    token,
+                    Some(&json!({"col": col, "row": row})),
+                )
+                .await;
+            assert_eq!(resp.status(), 200);
+        }
+        let resp = server
+            .request(
+                Method::GET,
+                &format!("/api/games/{game_id}/turns"),
+                "test-black-api-token-12345",
+                None,
+            )
+            .await;
+        let turns: Vec<Value> = resp.json().await.unwrap();
+        hashes.push(turns[2]["position_hash"].as_str().unwrap().to_string());
+        game_ids.push(game_id);
+    }
+    assert_eq!(hashes[0], hashes[1]);
+    assert_eq!(hashes[0].len(), 16);
+
+    let resp = server
+        .request(
+            Method::GET,
+            &format!("/api/positions/{}", hashes[0]),
+            "test-white-api-token-67890",
+            None,
+        )
+        .await;
+    assert_eq!(resp.status(), 200);
+    let matches: Vec<Value> = resp.json().await.unwrap();
+    let mut found: Vec<i64> = matches
+        .iter()
+        .map(|m| m["game_id"].as_i64().unwrap())
+        .collect();
+    found.sort();
+    assert_eq!(found, game_ids);
+    assert!(matches.iter().all(|m| m["turn"]["turn_number"] == 2));
+}
+
+#[tokio::test]
+async fn position_lookup_rejects_malformed_hash() {
+    let server = LightServer::start().await;
+    let resp = server
+        .request(
+            Method::GET,
+            "/api/positions/not-a-hash",
+            "test-black-api-token-12345",
+            None,
+        )
+        .await;
+    assert_eq!(resp.status(), 422);
+}
+
 #[tokio::test]
 async fn rematch_via_api() {
     let server = LightServer::start().await;
@@ -950,6 +1130,166 @@ async fn accept_board_dimensions_within_range() {
     assert!(resp.status().is_success(), "7x11 should be accepted");
 }
 
+#[tokio::test]
+async fn rectangular_board_is_playable_with_handicap() {
+    let server = LightServer::start().await;
+    let resp = server
+        .try_create_game_with(json!({"cols": 9, "rows": 13, "handicap": 2, "komi": 0.5}))
+        .await;
+    assert_eq!(resp.status(), 201);
+    let body: Value = resp.json().await.unwrap();
+    let game_id = body["id"].as_i64().unwrap();
+    server
+        .request(
+            Method::POST,
+            &format!("/api/games/{game_id}/accept"),
+            "test-white-api-token-67890",
+            None,
+        )
+        .await;
+
+    let resp = server
+        .request(
+            Method::GET,
+            &format!("/api/games/{game_id}"),
+            "test-black-api-token-12345",
+            None,
+        )
+        .await;
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["cols"], 9);
+    assert_eq!(body["rows"], 13);
+    assert_eq!(body["handicap"], 2);
+    // Handicap stones on the 9x13 star points (6, 3) and (2, 9); White to move.
+    let board = body["state"]["board"].as_array().unwrap();
+    assert_eq!(board.len(), 9 * 13);
+    assert_eq!(board[3 * 9 + 6], 1);
+    assert_eq!(board[9 * 9 + 2], 1);
+    assert_eq!(body["current_turn_stone"], -1);
+
+    // The far corner is on the board.
+    let resp = server
+        .request(
+            Method::POST,
+            &format!("/api/games/{game_id}/play"),
+            "test-white-api-token-67890",
+            Some(&json!({"col": 8, "row": 12})),
+        )
+        .await;
+    assert_eq!(resp.status(), 200);
+}
+
+#[tokio::test]
+async fn torus_board_captures_across_the_edge() {
+    let server = LightServer::start().await;
+    let game_id = server
+        .create_and_join_with(json!({"cols": 5, "topology": "torus"}))
+        .await;
+
+    // White surrounds Black's corner stone through both wrapped edges.
+    let moves = [
+        ("test-black-api-token-12345", 0, 0),
+        ("test-white-api-token-67890", 1, 0),
+        ("test-black-api-token-12345", 2, 2),
+        ("test-white-api-token-67890", 0, 1),
+        ("test-black-api-token-12345", 2, 3),
+        ("test-white-api-token-67890", 4, 0),
+        ("test-black-api-token-12345", 3, 2),
+        ("test-white-api-token-67890", 0, 4),
+    ];
+    for (token, col, row) in moves {
+        let resp = server
+            .request(
+                Method::POST,
+                &format!("/api/games/{game_id}/play"),
+                token,
+                Some(&json!({"col": col, "row": row})),
+            )
+            .await;
+        assert_eq!(resp.status(), 200, "move ({col}, {row}) rejected");
+    }
+
+    let resp = server
+        .request(
+            Method::GET,
+            &format!("/api/games/{game_id}"),
+            "test-black-api-token-12345",
+            None,
+        )
+        .await;
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["topology"], "torus");
+    assert_eq!(body["state"]["board"][0], 0);
+}
+
+#[tokio::test]
+async fn create_game_rejects_unknown_topology() {
+    let server = LightServer::start().await;
+    let resp = server
+        .try_create_game_with(json!({"topology": "klein"}))
+        .await;
+    assert_eq!(resp.status(), 422);
+}
+
+#[tokio::test]
+async fn capture_go_ends_on_first_capture() {
+    let server = LightServer::start().await;
+    let game_id = server
+        .create_and_join_with(json!({"cols": 5, "capture_target": 1}))
+        .await;
+
+    // Black takes White's corner stone.
+    let moves = [
+        ("test-black-api-token-12345", 1, 0),
+        ("test-white-api-token-67890", 0, 0),
+        ("test-black-api-token-12345", 0, 1),
+    ];
+    for (token, col, row) in moves {
+        let resp = server
+            .request(
+                Method::POST,
+                &format!("/api/games/{game_id}/play"),
+                token,
+                Some(&json!({"col": col, "row": row})),
+            )
+            .await;
+        assert_eq!(resp.status(), 200, "move ({col}, {row}) rejected");
+    }
+
+    let resp = server
+        .request(
+            Method::GET,
+            &format!("/api/games/{game_id}"),
+            "test-black-api-token-12345",
+            None,
+        )
+        .await;
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["capture_target"], 1);
+    assert_eq!(body["result"], "B+C");
+
+    let resp = server
+        .request(
+            Method::POST,
+            &format!("/api/games/{game_id}/play"),
+            "test-white-api-token-67890",
+            Some(&json!({"col": 4, "row": 4})),
+        )
+        .await;
+    assert!(resp.status().is_client_error());
+}
+
+#[tokio::test]
+async fn create_game_rejects_out_of_range_capture_target() {
+    let server = LightServer::start().await;
+    for target in [0, 51] {
+        let resp = server
+            .try_create_game_with(json!({"capture_target": target}))
+            .await;
+        assert_eq!(resp.status(), 422, "capture_target {target} accepted");
+    }
+}
+
 // ============================================================
 // Challenge Accept / Decline
 // ============================================================
@@ -1597,6 +1937,294 @@ async fn approve_territory_requires_auth() {
     assert_eq!(resp.status(), 401);
 }
 
+// ============================================================
+// SGF Archive
+// ============================================================
+
+const TWO_GAME_SGF: &str = "(;GM[1]SZ[19]KM[6.5]PB[Alice]PW[Bob]DT[2024-01-02]RE[B+R];B[pd];W[dp];B[pp])\
+                            (;GM[1]SZ[9]PB[Carol]PW[Dan]RE[W+3.5];B[ee])";
+
+#[tokio::test]
+async fn import_sgf_collection_stores_each_game() {
+    let server = LightServer::start().await;
+    let token = "test-black-api-token-12345";
+
+    let resp = server
+        .request(
+            Method::POST,
+            "/api/archive",
+            token,
+            Some(&json!({"sgf": TWO_GAME_SGF})),
+        )
+        .await;
+    assert_eq!(resp.status(), 201);
+    let body: Vec<Value> = resp.json().await.unwrap();
+    assert_eq!(body.len(), 2);
+    assert_eq!(body[0]["black_name"], "Alice");
+    assert_eq!(body[0]["date"], "2024-01-02");
+    assert_eq!(body[0]["result"], "B+R");
+    assert_eq!(body[0]["move_count"], 3);
+    assert_eq!(body[1]["cols"], 9);
+    assert_eq!(body[1]["white_name"], "Dan");
+    assert!(body[1].get("sgf").is_none());
+
+    let resp = server
+        .request(Method::GET, "/api/archive", token, None)
+        .await;
+    assert_eq!(resp.status(), 200);
+    let listed: Vec<Value> = resp.json().await.unwrap();
+    assert_eq!(listed.len(), 2);
+
+    let resp = server
+        .request(
+            Method::GET,
+            &format!("/api/archive/{}", body[1]["id"]),
+            token,
+            None,
+        )
+        .await;
+    assert_eq!(resp.status(), 200);
+    let game: Value = resp.json().await.unwrap();
+    let sgf = game["sgf"].as_str().unwrap();
+    assert!(sgf.contains("PB[Carol]"));
+    assert!(!sgf.contains("Alice"));
+}
+
+#[tokio::test]
+async fn import_selected_games_only() {
+    let server = LightServer::start().await;
+
+    let resp = server
+        .request(
+            Method::POST,
+            "/api/archive",
+            "test-black-api-token-12345",
+            Some(&json!({"sgf": TWO_GAME_SGF, "games": [1]})),
+        )
+        .await;
+    assert_eq!(resp.status(), 201);
+    let body: Vec<Value> = resp.json().await.unwrap();
+    assert_eq!(body.len(), 1);
+    assert_eq!(body[0]["black_name"], "Carol");
+}
+
+#[tokio::test]
+async fn import_rejects_bad_sgf_and_missing_games() {
+    let server = LightServer::start().await;
+    let token = "test-black-api-token-12345";
+
+    let resp = server
+        .request(
+            Method::POST,
+            "/api/archive",
+            token,
+            Some(&json!({"sgf": "(;GM[1]"})),
+        )
+        .await;
+    assert_eq!(resp.status(), 422);
+
+    let resp = server
+        .request(
+            Method::POST,
+            "/api/archive",
+            token,
+            Some(&json!({"sgf": TWO_GAME_SGF, "games": [2]})),
+        )
+        .await;
+    assert_eq!(resp.status(), 422);
+    let body: Value = resp.json().await.unwrap();
+    assert_api_error(&body, "validation_error");
+}
+
+#[tokio::test]
+async fn archived_games_are_private_to_their_owner() {
+    let server = LightServer::start().await;
+
+    let resp = server
+        .request(
+            Method::POST,
+            "/api/archive",
+            "test-black-api-token-12345",
+            Some(&json!({"sgf": TWO_GAME_SGF, "games": [0]})),
+        )
+        .await;
+    let body: Vec<Value> = resp.json().await.unwrap();
+
+    let resp = server
+        .request(
+            Method::GET,
+            &format!("/api/archive/{}", body[0]["id"]),
+            "test-white-api-token-67890",
+            None,
+        )
+        .await;
+    assert_eq!(resp.status(), 404);
+
+    let resp = server
+        .request(
+            Method::GET,
+            "/api/archive",
+            "test-white-api-token-67890",
+            None,
+        )
+        .await;
+    let listed: Vec<Value> = resp.json().await.unwrap();
+    assert!(listed.is_empty());
+}
+
+// ============================================================
+// Problems
+// ============================================================
+
+const PROBLEM_SGF: &str = "(;GM[1]SZ[9]PL[B]GN[Corner life]\
+                           AB[ab][bb][cb][db][ea][eb]AW[ac][bc][cc][dc][ec][fb][fa]\
+                           (;B[ca];W[ba];B[aa]C[RIGHT])\
+                           (;B[ba];W[ca]C[Dead]))";
+
+async fn create_problem(server: &LightServer) -> Value {
+    let resp = server
+        .request(
+            Method::POST,
+            "/api/problems",
+            "test-black-api-token-12345",
+            Some(&json!({"sgf": PROBLEM_SGF})),
+        )
+        .await;
+    assert_eq!(resp.status(), 201);
+    resp.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn problem_hides_its_solution_until_solved() {
+    let server = LightServer::start().await;
+    let token = "test-white-api-token-67890";
+    let problem = create_problem(&server).await;
+    assert_eq!(problem["title"], "Corner life");
+    assert_eq!(problem["to_play"], "black");
+    let path = format!("/api/problems/{}", problem["id"]);
+
+    let resp = server.request(Method::GET, &path, token, None).await;
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["black"].as_array().unwrap().len(), 6);
+    assert!(body.get("sgf").is_none());
+
+    let resp = server
+        .request(
+            Method::POST,
+            &format!("{path}/attempts"),
+            token,
+            Some(&json!({"moves": [[2, 0]]})),
+        )
+        .await;
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["status"], "continue");
+    assert_eq!(body["response"], json!([1, 0]));
+    assert_eq!(body["rated"], false);
+
+    let resp = server
+        .request(
+            Method::POST,
+            &format!("{path}/attempts"),
+            token,
+            Some(&json!({"moves": [[2, 0], [0, 0]]})),
+        )
+        .await;
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["status"], "correct");
+    assert_eq!(body["rated"], true);
+    assert!(body["user_rating"].as_f64().unwrap() > 1500.0);
+    assert!(body["problem_rating"].as_f64().unwrap() < 1500.0);
+
+    let resp = server.request(Method::GET, &path, token, None).await;
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["solved"], true);
+    assert_eq!(body["attempts"], 1);
+    assert_eq!(body["solve_count"], 1);
+    assert!(body["sgf"].as_str().unwrap().contains("RIGHT"));
+}
+
+#[tokio::test]
+async fn only_the_first_finished_attempt_is_rated() {
+    let server = LightServer::start().await;
+    let token = "test-white-api-token-67890";
+    let problem = create_problem(&server).await;
+    let path = format!("/api/problems/{}/attempts", problem["id"]);
+
+    let resp = server
+        .request(
+            Method::POST,
+            &path,
+            token,
+            Some(&json!({"moves": [[1, 0]]})),
+        )
+        .await;
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["status"], "wrong");
+    assert_eq!(body["response"], json!([2, 0]));
+    assert_eq!(body["comment"], "Dead");
+    assert_eq!(body["rated"], true);
+    let rating = body["user_rating"].as_f64().unwrap();
+    assert!(rating < 1500.0);
+
+    let resp = server
+        .request(
+            Method::POST,
+            &path,
+            token,
+            Some(&json!({"moves": [[2, 0], [0, 0]]})),
+        )
+        .await;
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["status"], "correct");
+    assert_eq!(body["rated"], false);
+    assert_eq!(body["user_rating"].as_f64().unwrap(), rating);
+
+    let resp = server
+        .request(Method::GET, "/api/problems", token, None)
+        .await;
+    let listed: Vec<Value> = resp.json().await.unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0]["attempts"], 2);
+    assert_eq!(listed[0]["solved"], true);
+    assert_eq!(listed[0]["attempt_count"], 2);
+    assert!(listed[0].get("black").is_none());
+}
+
+#[tokio::test]
+async fn problem_rejects_bad_sgf_and_attempts() {
+    let server = LightServer::start().await;
+    let token = "test-black-api-token-12345";
+
+    let resp = server
+        .request(
+            Method::POST,
+            "/api/problems",
+            token,
+            Some(&json!({"sgf": "(;SZ[9]AB[aa]AW[ba](;B[ca]C[Wrong]))", "title": "No answer"})),
+        )
+        .await;
+    assert_eq!(resp.status(), 422);
+    let body: Value = resp.json().await.unwrap();
+    assert_api_error(&body, "validation_error");
+
+    let problem = create_problem(&server).await;
+    let resp = server
+        .request(
+            Method::POST,
+            &format!("/api/problems/{}/attempts", problem["id"]),
+            token,
+            Some(&json!({"moves": [[8, 8], [0, 0]]})),
+        )
+        .await;
+    assert_eq!(resp.status(), 422);
+
+    let resp = server
+        .request(Method::GET, "/api/problems/999", token, None)
+        .await;
+    assert_eq!(resp.status(), 404);
+}
+
 // ============================================================
 // Auth: Registration — needs session cookies, keep on TestServer
 // ============================================================
@@ -326,13 +326,16 @@ impl TestServer {
     }
 
     async fn finalize_pregame_settings_if_present(&self, game_id: i64) {
-        let Some((handicap, komi, color)): Option<(i32, f64, String)> = sqlx::query_as(
-            "SELECT handicap, komi, color FROM pregame_setting_negotiations WHERE game_id = $1",
-        )
-        .bind(game_id)
-        .fetch_optional(&self.pool)
-        .await
-        .unwrap() else {
+        let Some((handicap, placement, komi, color)): Option<(i32, String, f64, String)> =
+            sqlx::query_as(
+                "SELECT handicap, handicap_placement, komi, color \
+                 FROM pregame_setting_negotiations WHERE game_id = $1",
+            )
+            .bind(game_id)
+            .fetch_optional(&self.pool)
+            .await
+            .unwrap()
+        else {
             return;
         };
 
@@ -349,11 +352,8 @@ impl TestServer {
         } else {
             (creator_id, opponent_id)
         };
-        let stage = if handicap >= 2 {
-            "white_to_play"
-        } else {
-            "black_to_play"
-        };
+        let stage =
+            go_engine::Stage::opening(handicap as u8, placement.parse().unwrap()).to_string();
 
         let mut tx = self.pool.begin().await.unwrap();
         sqlx::query(
@@ -973,13 +973,16 @@ impl LightServer {
     }
 
     async fn finalize_pregame_settings_if_present(&self, game_id: i64) {
-        let Some((handicap, komi, color)): Option<(i32, f64, String)> = sqlx::query_as(
-            "SELECT handicap, komi, color FROM pregame_setting_negotiations WHERE game_id = $1",
-        )
-        .bind(game_id)
-        .fetch_optional(&self.pool)
-        .await
-        .unwrap() else {
+        let Some((handicap, placement, komi, color)): Option<(i32, String, f64, String)> =
+            sqlx::query_as(
+                "SELECT handicap, handicap_placement, komi, color \
+                 FROM pregame_setting_negotiations WHERE game_id = $1",
+            )
+            .bind(game_id)
+            .fetch_optional(&self.pool)
+            .await
+            .unwrap()
+        else {
             return;
         };
 
@@ -996,11 +999,8 @@ impl LightServer {
         } else {
             (creator_id, opponent_id)
         };
-        let stage = if handicap >= 2 {
-            "white_to_play"
-        } else {
-            "black_to_play"
-        };
+        let stage =
+            go_engine::Stage::opening(handicap as u8, placement.parse().unwrap()).to_string();
 
         let mut tx = self.pool.begin().await.unwrap();
         sqlx::query(
@@ -1,4 +1,4 @@
-use serde_json::Value;
+use serde_json::{Value, json};
 
 use crate::common::{TestServer, WsClient};
 
@@ -152,6 +152,55 @@ async fn both_approve_game_ends() {
     );
 }
 
+/// Area-scored games count stones on the board and record them in the settled score.
+#[tokio::test]
+async fn area_scoring_counts_stones_on_the_board() {
+    let server = TestServer::start().await;
+    let game_id = server
+        .create_and_join_with(json!({"ruleset": "chinese"}))
+        .await;
+
+    let mut black = server.ws_black().await;
+    let mut white = server.ws_white().await;
+
+    let _state = black.join_game(game_id).await;
+    let _state = white.join_game(game_id).await;
+
+    // B(4,4), then W and B pass: the whole board is Black's.
+    black.play(game_id, 4, 4).await;
+    let _ = black.recv_kind("state").await;
+    let _ = white.recv_kind("state").await;
+
+    white.pass(game_id).await;
+    let _ = black.recv_kind("state").await;
+    let _ = white.recv_kind("state").await;
+
+    black.pass(game_id).await;
+    let state = black.recv_kind("state").await;
+    let _ = white.recv_kind("state").await;
+    assert_eq!(state["stage"], "territory_review");
+    assert_eq!(state["territory"]["score"]["black"]["territory"], 80);
+    assert_eq!(state["territory"]["score"]["black"]["stones"], 1);
+
+    black.approve_territory(game_id).await;
+    let _ = black.recv_kind("state").await;
+    let _ = white.recv_kind("state").await;
+
+    white.approve_territory(game_id).await;
+    let _ = black.recv_kind("chat").await;
+    let state = black.recv_kind("state").await;
+
+    // 80 territory + 1 stone against 6.5 komi (territory scoring would give B+73.5).
+    assert_eq!(state["result"], "B+74.5");
+
+    let mut black2 = server.ws_black().await;
+    let state = black2.join_game(game_id).await;
+    let score = &state["settled_territory"]["score"];
+    assert_eq!(score["black"]["stones"], 1);
+    assert_eq!(score["black"]["captures"], 0);
+    assert_eq!(score["white"]["stones"], 0);
+}
+
 /// 7.4 — Approval reset on toggle: after one player approves, a toggle resets both approvals.
 #[tokio::test]
 async fn approval_reset_on_toggle() {